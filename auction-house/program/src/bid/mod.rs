@@ -101,13 +101,14 @@ pub struct PublicBuy<'info> {
 }
 
 /// Create a bid on a specific SPL token.
-/// Public bids are specific to the token itself, rather than the auction, and remain open indefinitely until either the user closes it or the requirements for the bid are met and it is matched with a counter bid and closed as a transaction.
+/// Public bids are specific to the token itself, rather than the auction, and remain open until either the user closes it, the optional `expiry` passes, or the requirements for the bid are met and it is matched with a counter bid and closed as a transaction.
 pub fn public_bid(
     ctx: Context<PublicBuy>,
     trade_state_bump: u8,
     escrow_payment_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
     bid_logic(
         ctx.accounts.wallet.to_owned(),
@@ -128,6 +129,7 @@ pub fn public_bid(
         escrow_payment_bump,
         buyer_price,
         token_size,
+        expiry,
         true,
         *ctx.bumps
             .get("escrow_payment_account")
@@ -241,7 +243,7 @@ pub struct AuctioneerPublicBuy<'info> {
 }
 
 /// Create a bid on a specific SPL token.
-/// Public bids are specific to the token itself, rather than the auction, and remain open indefinitely until either the user closes it or the requirements for the bid are met and it is matched with a counter bid and closed as a transaction.
+/// Public bids are specific to the token itself, rather than the auction, and remain open until either the user closes it, the optional `expiry` passes, or the requirements for the bid are met and it is matched with a counter bid and closed as a transaction.
 pub fn auctioneer_public_bid(
     ctx: Context<AuctioneerPublicBuy>,
     trade_state_bump: u8,
@@ -385,6 +387,7 @@ pub fn private_bid<'info>(
     escrow_payment_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
     bid_logic(
         ctx.accounts.wallet.to_owned(),
@@ -405,6 +408,7 @@ pub fn private_bid<'info>(
         escrow_payment_bump,
         buyer_price,
         token_size,
+        expiry,
         false,
         *ctx.bumps
             .get("escrow_payment_account")
@@ -585,6 +589,7 @@ pub fn bid_logic<'info>(
     escrow_payment_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
     public: bool,
     escrow_canonical_bump: u8,
     trade_state_canonical_bump: u8,
@@ -614,6 +619,8 @@ pub fn bid_logic<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
+    assert_valid_expiry(&buyer_trade_state, expiry)?;
+
     let auction_house_key = auction_house.key();
     let seeds = [
         PREFIX.as_bytes(),
@@ -708,6 +715,11 @@ pub fn bid_logic<'info>(
 
     let ts_info = buyer_trade_state.to_account_info();
     if ts_info.data_is_empty() {
+        let ts_size = if expiry.is_some() {
+            EXPIRING_TRADE_STATE_SIZE
+        } else {
+            TRADE_STATE_SIZE
+        };
         let wallet_key = wallet.key();
         let token_account_key = token_account.key();
         if public {
@@ -717,7 +729,7 @@ pub fn bid_logic<'info>(
                 &rent.to_account_info(),
                 &system_program,
                 &fee_payer,
-                TRADE_STATE_SIZE,
+                ts_size,
                 fee_seeds,
                 &[
                     PREFIX.as_bytes(),
@@ -737,7 +749,7 @@ pub fn bid_logic<'info>(
                 &rent.to_account_info(),
                 &system_program,
                 &fee_payer,
-                TRADE_STATE_SIZE,
+                ts_size,
                 fee_seeds,
                 &[
                     PREFIX.as_bytes(),
@@ -757,6 +769,9 @@ pub fn bid_logic<'info>(
             trade_state_bump,
             TRADE_STATE_SIZE,
         );
+        if let Some(expiry) = expiry {
+            set_trade_state_expiry(&ts_info, expiry, fee_payer.key)?;
+        }
     }
     // Allow The same bid to be sent with no issues
     Ok(())
@@ -963,3 +978,287 @@ pub fn auctioneer_bid_logic<'info>(
     // Allow The same bid to be sent with no issues
     Ok(())
 }
+
+/// Accounts for the [`collection_bid` handler](fn.collection_bid.html).
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct CollectionBuy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in collection_bid.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in collection_bid.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<Account<'info, Mint>>,
+
+    /// Mint account of the collection NFT.
+    collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Validated in collection_bid.
+    /// Collection NFT metadata.
+    collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in collection_bid.
+    /// Auction House instance authority account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA keyed on the collection mint.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            COLLECTION.as_bytes(),
+            treasury_mint.key().as_ref(),
+            collection_mint.key().as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Create a bid on any token of a verified collection, optionally also requiring a verified `creator`.
+/// The trade state is keyed on the collection mint rather than a token mint, and can be matched against any listing whose metadata has its collection verified as `collection_mint`.
+pub fn collection_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectionBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    creator: Option<Pubkey>,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let payment_account = &ctx.accounts.payment_account;
+    let transfer_authority = &ctx.accounts.transfer_authority;
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let collection_mint = &ctx.accounts.collection_mint;
+    let collection_metadata = &ctx.accounts.collection_metadata;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let authority = &ctx.accounts.authority;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_fee_account = &ctx.accounts.auction_house_fee_account;
+    let buyer_trade_state = &ctx.accounts.buyer_trade_state;
+    let token_program = &ctx.accounts.token_program;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+
+    // If it has an auctioneer authority delegated for public bids, collection bids are not allowed either.
+    if auction_house.has_auctioneer && auction_house.scopes[AuthorityScope::PublicBuy as usize] {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let escrow_canonical_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let trade_state_canonical_bump = *ctx
+        .bumps
+        .get("buyer_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (escrow_canonical_bump != escrow_payment_bump)
+        || (trade_state_canonical_bump != trade_state_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    assert_derivation(
+        &mpl_token_metadata::id(),
+        &collection_metadata.to_account_info(),
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            collection_mint.key().as_ref(),
+        ],
+    )?;
+    if collection_metadata.data_is_empty() {
+        return Err(AuctionHouseError::MetadataDoesntExist.into());
+    }
+
+    let ts_info = buyer_trade_state.to_account_info();
+    if !ts_info.data_is_empty() && get_collection_bid_creator(&ts_info)? != creator {
+        return Err(AuctionHouseError::CollectionBidAlreadyExists.into());
+    }
+
+    let auction_house_key = auction_house.key();
+    let seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        FEE_PAYER.as_bytes(),
+        &[auction_house.fee_payer_bump],
+    ];
+    let (fee_payer, fee_seeds) = get_fee_payer(
+        authority,
+        auction_house,
+        wallet.to_account_info(),
+        auction_house_fee_account.to_account_info(),
+        &seeds,
+    )?;
+
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    let wallet_key = wallet.key();
+    let escrow_signer_seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        wallet_key.as_ref(),
+        &[escrow_payment_bump],
+    ];
+    create_program_token_account_if_not_present(
+        escrow_payment_account,
+        system_program,
+        &fee_payer,
+        token_program,
+        treasury_mint,
+        &auction_house.to_account_info(),
+        rent,
+        &escrow_signer_seeds,
+        fee_seeds,
+        is_native,
+    )?;
+    if is_native {
+        assert_keys_equal(wallet.key(), payment_account.key())?;
+
+        if escrow_payment_account.lamports()
+            < buyer_price
+                .checked_add(rent.minimum_balance(escrow_payment_account.data_len()))
+                .ok_or(AuctionHouseError::NumericalOverflow)?
+        {
+            let diff = buyer_price
+                .checked_add(rent.minimum_balance(escrow_payment_account.data_len()))
+                .ok_or(AuctionHouseError::NumericalOverflow)?
+                .checked_sub(escrow_payment_account.lamports())
+                .ok_or(AuctionHouseError::NumericalOverflow)?;
+
+            invoke(
+                &system_instruction::transfer(
+                    &payment_account.key(),
+                    &escrow_payment_account.key(),
+                    diff,
+                ),
+                &[
+                    payment_account.to_account_info(),
+                    escrow_payment_account.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+        }
+    } else {
+        let escrow_payment_loaded: spl_token::state::Account =
+            assert_initialized(escrow_payment_account)?;
+
+        if escrow_payment_loaded.amount < buyer_price {
+            let diff = buyer_price
+                .checked_sub(escrow_payment_loaded.amount)
+                .ok_or(AuctionHouseError::NumericalOverflow)?;
+            invoke(
+                &spl_token::instruction::transfer(
+                    &token_program.key(),
+                    &payment_account.key(),
+                    &escrow_payment_account.key(),
+                    &transfer_authority.key(),
+                    &[],
+                    diff,
+                )?,
+                &[
+                    transfer_authority.to_account_info(),
+                    payment_account.to_account_info(),
+                    escrow_payment_account.to_account_info(),
+                    token_program.to_account_info(),
+                ],
+            )?;
+        }
+    }
+
+    if ts_info.data_is_empty() {
+        let collection_mint_key = collection_mint.key();
+        create_or_allocate_account_raw(
+            crate::id(),
+            &ts_info,
+            &rent.to_account_info(),
+            system_program,
+            &fee_payer,
+            COLLECTION_TRADE_STATE_SIZE,
+            fee_seeds,
+            &[
+                PREFIX.as_bytes(),
+                wallet_key.as_ref(),
+                auction_house_key.as_ref(),
+                COLLECTION.as_bytes(),
+                auction_house.treasury_mint.as_ref(),
+                collection_mint_key.as_ref(),
+                &buyer_price.to_le_bytes(),
+                &token_size.to_le_bytes(),
+                &[trade_state_bump],
+            ],
+        )?;
+
+        let mut data = ts_info.try_borrow_mut_data()?;
+        data[0] = trade_state_bump;
+        if let Some(creator) = creator {
+            data[TRADE_STATE_SIZE..COLLECTION_TRADE_STATE_SIZE].copy_from_slice(creator.as_ref());
+        }
+    }
+    // Allow The same bid to be sent with no issues
+    Ok(())
+}
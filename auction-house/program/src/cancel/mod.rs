@@ -254,7 +254,108 @@ fn cancel_logic<'info>(
         .lamports()
         .checked_add(curr_lamp)
         .ok_or(AuctionHouseError::NumericalOverflow)?;
-    sol_memset(*trade_state.try_borrow_mut_data()?, 0, TRADE_STATE_SIZE);
+    let trade_state_size = trade_state.data_len();
+    sol_memset(*trade_state.try_borrow_mut_data()?, 0, trade_state_size);
 
     Ok(())
 }
+
+/// Accounts for the [`cancel_collection_buy` handler](auction_house/fn.cancel_collection_buy.html).
+#[derive(Accounts)]
+#[instruction(buyer_price: u64, token_size: u64)]
+pub struct CancelCollectionBuy<'info> {
+    /// CHECK: Verified in cancel_collection_buy.
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Only used as a trade state seed, validated in cancel_collection_buy.
+    /// Mint account of the collection NFT the bid was placed on.
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as a signer in cancel_collection_buy.
+    /// Auction House instance authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in cancel_collection_buy.
+    /// Collection bid trade state PDA account to be canceled.
+    #[account(mut)]
+    pub trade_state: UncheckedAccount<'info>,
+}
+
+/// Cancel a collection bid by transferring all lamports from its trade state account to the fee payer and zeroing its data.
+pub fn cancel_collection_buy<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelCollectionBuy<'info>>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let collection_mint = &ctx.accounts.collection_mint;
+    let authority = &ctx.accounts.authority;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_fee_account = &ctx.accounts.auction_house_fee_account;
+    let trade_state = &ctx.accounts.trade_state;
+
+    // If it has an auctioneer authority delegated must use auctioneer_* handler.
+    if auction_house.has_auctioneer && auction_house.scopes[AuthorityScope::Cancel as usize] {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let ts_bump = trade_state.try_borrow_data()?[0];
+    assert_valid_collection_trade_state(
+        &wallet.key(),
+        auction_house,
+        buyer_price,
+        token_size,
+        &trade_state.to_account_info(),
+        &collection_mint.key(),
+        ts_bump,
+    )?;
+    if !wallet.to_account_info().is_signer && !authority.to_account_info().is_signer {
+        return Err(AuctionHouseError::NoValidSignerPresent.into());
+    }
+
+    let auction_house_key = auction_house.key();
+    let seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        FEE_PAYER.as_bytes(),
+        &[auction_house.fee_payer_bump],
+    ];
+
+    let (fee_payer, _) = get_fee_payer(
+        authority,
+        auction_house,
+        wallet.to_account_info(),
+        auction_house_fee_account.to_account_info(),
+        &seeds,
+    )?;
+
+    close_account(&trade_state.to_account_info(), &fee_payer)
+}
@@ -6,7 +6,15 @@ pub const PURCHASE_RECEIPT_PREFIX: &str = "purchase_receipt";
 pub const BID_RECEIPT_PREFIX: &str = "bid_receipt";
 pub const LISTING_RECEIPT_PREFIX: &str = "listing_receipt";
 pub const AUCTIONEER: &str = "auctioneer";
+pub const COLLECTION: &str = "collection";
 pub const TRADE_STATE_SIZE: usize = 1;
+pub const EXPIRING_TRADE_STATE_SIZE: usize = TRADE_STATE_SIZE + // bump
+8 +                                                         // expiry
+32                                                          // rent payer
+;
+pub const COLLECTION_TRADE_STATE_SIZE: usize = TRADE_STATE_SIZE + // bump
+32                                                          // required creator, zeroed for any creator
+;
 pub const MAX_NUM_SCOPES: usize = 7;
 pub const AUCTIONEER_SIZE: usize = 8 +                      // Anchor discriminator/sighash
 32 +                                                        // Auctioneer authority
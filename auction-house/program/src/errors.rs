@@ -177,4 +177,40 @@ pub enum AuctionHouseError {
     // 6043
     #[msg("Insufficient funds in escrow account to purchase.")]
     InsufficientFunds,
+
+    // 6044
+    #[msg("The trade state has expired.")]
+    TradeStateExpired,
+
+    // 6045
+    #[msg("The trade state has not expired yet.")]
+    TradeStateNotExpired,
+
+    // 6046
+    #[msg("Expiry must be in the future.")]
+    InvalidExpiry,
+
+    // 6047
+    #[msg("The trade state already exists with a different expiry.")]
+    TradeStateExpiryMismatch,
+
+    // 6048
+    #[msg("The trade state was created without an expiry.")]
+    TradeStateHasNoExpiry,
+
+    // 6049
+    #[msg("The token does not belong to the verified collection of the bid.")]
+    CollectionNotVerified,
+
+    // 6050
+    #[msg("The token does not have the verified creator required by the collection bid.")]
+    CollectionBidCreatorMismatch,
+
+    // 6051
+    #[msg("A collection bid requiring a different creator already exists at this price.")]
+    CollectionBidAlreadyExists,
+
+    // 6052
+    #[msg("The collection royalties account does not belong to the collection of the item.")]
+    InvalidCollectionRoyalties,
 }
@@ -234,6 +234,57 @@ pub fn execute_sale<'info>(
         token_size,
         None,
         None,
+        false,
+    )
+}
+
+/// Execute a sale between a collection bid and the listing of any token whose metadata is verified as part of that collection.
+pub fn execute_collection_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    // If it has an auctioneer authority delegated must use auctioneer_* handler.
+    if auction_house.has_auctioneer && auction_house.scopes[AuthorityScope::ExecuteSale as usize] {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let escrow_canonical_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let free_trade_state_canonical_bump = *ctx
+        .bumps
+        .get("free_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_canonical_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (escrow_canonical_bump != escrow_payment_bump)
+        || (free_trade_state_canonical_bump != free_trade_state_bump)
+        || (program_as_signer_canonical_bump != program_as_signer_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    execute_sale_logic(
+        ctx.accounts,
+        ctx.remaining_accounts,
+        escrow_payment_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        None,
+        None,
+        true,
     )
 }
 
@@ -473,6 +524,7 @@ pub fn execute_partial_sale<'info>(
         token_size,
         partial_order_size,
         partial_order_price,
+        false,
     )
 }
 
@@ -1056,6 +1108,9 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         return Err(AuctionHouseError::BothPartiesNeedToAgreeToSale.into());
     }
 
+    assert_trade_state_not_expired(buyer_trade_state)?;
+    assert_trade_state_not_expired(seller_trade_state)?;
+
     let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
 
     let (size, price): (u64, u64) = match (partial_order_size, partial_order_price) {
@@ -1372,6 +1427,7 @@ fn execute_sale_logic<'c, 'info>(
     token_size: u64,
     partial_order_size: Option<u64>,
     partial_order_price: Option<u64>,
+    collection_bid: bool,
 ) -> Result<()> {
     let buyer = &accounts.buyer;
     let seller = &accounts.seller;
@@ -1439,6 +1495,9 @@ fn execute_sale_logic<'c, 'info>(
         return Err(AuctionHouseError::BothPartiesNeedToAgreeToSale.into());
     }
 
+    assert_trade_state_not_expired(buyer_trade_state)?;
+    assert_trade_state_not_expired(seller_trade_state)?;
+
     let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
 
     let (size, price): (u64, u64) = match (partial_order_size, partial_order_price) {
@@ -1469,16 +1528,28 @@ fn execute_sale_logic<'c, 'info>(
             (size, price)
         }
         (None, None) => {
-            assert_valid_trade_state(
-                &buyer.key(),
-                auction_house,
-                buyer_price,
-                token_size,
-                buyer_trade_state,
-                &token_mint.key(),
-                &token_account.key(),
-                ts_bump,
-            )?;
+            if collection_bid {
+                assert_valid_collection_bid(
+                    &buyer.key(),
+                    auction_house,
+                    buyer_price,
+                    token_size,
+                    buyer_trade_state,
+                    metadata,
+                    ts_bump,
+                )?;
+            } else {
+                assert_valid_trade_state(
+                    &buyer.key(),
+                    auction_house,
+                    buyer_price,
+                    token_size,
+                    buyer_trade_state,
+                    &token_mint.key(),
+                    &token_account.key(),
+                    ts_bump,
+                )?;
+            }
 
             if token_account_data.amount < token_size {
                 return Err(AuctionHouseError::NotEnoughTokensAvailableForPurchase.into());
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, utils::*};
+
+/// Accounts for the [`close_expired_trade_state` handler](auction_house/fn.close_expired_trade_state.html).
+#[derive(Accounts)]
+pub struct CloseExpiredTradeState<'info> {
+    /// CHECK: Validated as an expired trade state owned by this program in close_expired_trade_state.
+    /// Buyer or seller trade state created with an expiry.
+    #[account(mut)]
+    pub trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the rent payer recorded in the trade state.
+    /// Account that paid the rent for the trade state, receives the refund.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Close a bid or listing trade state whose expiry has passed and refund its rent to the account that paid for it.
+/// Anyone can crank this, as an expired trade state can no longer be executed.
+pub fn close_expired_trade_state(ctx: Context<CloseExpiredTradeState>) -> Result<()> {
+    let trade_state = &ctx.accounts.trade_state;
+    let payer = &ctx.accounts.payer;
+
+    assert_owned_by(trade_state, &crate::id())?;
+
+    let (expiry, rent_payer) =
+        get_trade_state_expiry(trade_state)?.ok_or(AuctionHouseError::TradeStateHasNoExpiry)?;
+    assert_keys_equal(payer.key(), rent_payer)?;
+
+    if Clock::get()?.unix_timestamp <= expiry {
+        return Err(AuctionHouseError::TradeStateNotExpired.into());
+    }
+
+    close_account(&trade_state.to_account_info(), &payer.to_account_info())
+}
@@ -10,6 +10,7 @@ pub mod constants;
 pub mod deposit;
 pub mod errors;
 pub mod execute_sale;
+pub mod expire;
 pub mod pda;
 pub mod receipt;
 pub mod sell;
@@ -21,7 +22,7 @@ pub use state::*;
 
 use crate::{
     auctioneer::*, bid::*, cancel::*, constants::*, deposit::*, errors::AuctionHouseError,
-    execute_sale::*, receipt::*, sell::*, utils::*, withdraw::*,
+    execute_sale::*, expire::*, receipt::*, sell::*, utils::*, withdraw::*,
 };
 
 use anchor_lang::{
@@ -343,6 +344,26 @@ pub mod auction_house {
             escrow_payment_bump,
             buyer_price,
             token_size,
+            None,
+        )
+    }
+
+    /// Create a private buy bid like `buy` which can no longer be executed once the `expiry` unix timestamp passes.
+    pub fn buy_with_expiry<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        private_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+            Some(expiry),
         )
     }
 
@@ -376,6 +397,26 @@ pub mod auction_house {
             escrow_payment_bump,
             buyer_price,
             token_size,
+            None,
+        )
+    }
+
+    /// Create a public buy bid like `public_buy` which can no longer be executed once the `expiry` unix timestamp passes.
+    pub fn public_buy_with_expiry<'info>(
+        ctx: Context<'_, '_, '_, 'info, PublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        public_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+            Some(expiry),
         )
     }
 
@@ -396,6 +437,25 @@ pub mod auction_house {
         )
     }
 
+    /// Create a collection bid by creating a `buyer_trade_state` account keyed on the collection mint and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount. The bid can be matched against any token verified as part of the collection and, if `creator` is provided, having it as a verified creator.
+    pub fn collection_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectionBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        creator: Option<Pubkey>,
+    ) -> Result<()> {
+        collection_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+            creator,
+        )
+    }
+
     /// Cancel a bid or ask by revoking the token delegate, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
     pub fn cancel<'info>(
         ctx: Context<'_, '_, '_, 'info, Cancel<'info>>,
@@ -414,6 +474,15 @@ pub mod auction_house {
         cancel::auctioneer_cancel(ctx, buyer_price, token_size)
     }
 
+    /// Cancel a collection bid by transferring all lamports from its trade state account to the fee payer and setting the trade state account data to zero so it can be garbage collected.
+    pub fn cancel_collection_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelCollectionBuy<'info>>,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        cancel::cancel_collection_buy(ctx, buyer_price, token_size)
+    }
+
     /// Deposit `amount` into the escrow payment account for your specific wallet.
     pub fn deposit<'info>(
         ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
@@ -450,6 +519,25 @@ pub mod auction_house {
         )
     }
 
+    /// Execute a sale between a collection bid and the listing of any token verified as part of that collection.
+    pub fn execute_collection_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+        escrow_payment_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        execute_sale::execute_collection_sale(
+            ctx,
+            escrow_payment_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
     pub fn execute_partial_sale<'info>(
         ctx: Context<'_, '_, '_, 'info, ExecutePartialSale<'info>>,
         escrow_payment_bump: u8,
@@ -527,6 +615,28 @@ pub mod auction_house {
             program_as_signer_bump,
             buyer_price,
             token_size,
+            None,
+        )
+    }
+
+    /// Create a listing like `sell` which can no longer be executed once the `expiry` unix timestamp passes.
+    pub fn sell_with_expiry<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        trade_state_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        sell::sell(
+            ctx,
+            trade_state_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            Some(expiry),
         )
     }
 
@@ -546,6 +656,13 @@ pub mod auction_house {
         )
     }
 
+    /// Close a trade state whose expiry has passed, refunding its rent to the account that paid for it. Can be called by anyone.
+    pub fn close_expired_trade_state<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseExpiredTradeState<'info>>,
+    ) -> Result<()> {
+        expire::close_expired_trade_state(ctx)
+    }
+
     /// Withdraw `amount` from the escrow payment account for your specific wallet.
     pub fn withdraw<'info>(
         ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
@@ -96,6 +96,30 @@ pub fn find_public_bid_trade_state_address(
     )
 }
 
+/// Return collection bid trade state `Pubkey` address and bump seed.
+pub fn find_collection_bid_trade_state_address(
+    wallet: &Pubkey,
+    auction_house: &Pubkey,
+    treasury_mint: &Pubkey,
+    collection_mint: &Pubkey,
+    price: u64,
+    token_size: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            wallet.as_ref(),
+            auction_house.as_ref(),
+            COLLECTION.as_bytes(),
+            treasury_mint.as_ref(),
+            collection_mint.as_ref(),
+            &price.to_le_bytes(),
+            &token_size.to_le_bytes(),
+        ],
+        &id(),
+    )
+}
+
 /// Return bid receipt `Pubkey` address and bump seed.
 pub fn find_bid_receipt_address(trade_state: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
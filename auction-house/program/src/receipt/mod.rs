@@ -3,7 +3,10 @@ use crate::{
     constants::*,
     errors::AuctionHouseError,
     id,
-    instruction::{Buy, ExecuteSale, Sell},
+    instruction::{
+        AuctioneerBuy, AuctioneerSell, Buy, BuyWithExpiry, CollectionBuy, ExecuteSale, Sell,
+        SellWithExpiry,
+    },
     utils::*,
 };
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
@@ -22,7 +25,21 @@ pub const BID_RECEIPT_SIZE: usize = 8 + //key
 1 + // bump
 1 + // trade_state_bump
 8 + // created_at
-1 + 8; // canceled_at
+1 + 8 + // canceled_at
+1 + 8 + // expiry
+1 + 32 + 1 + 32; // target
+
+/// What a bid can be matched against.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum BidTarget {
+    /// The token decorated by the receipt `metadata`.
+    Token,
+    /// Any token verified as part of the collection, with `creator` as a verified creator if set.
+    Collection {
+        mint: Pubkey,
+        creator: Option<Pubkey>,
+    },
+}
 
 /// Receipt for a bid transaction.
 #[account]
@@ -40,6 +57,8 @@ pub struct BidReceipt {
     pub trade_state_bump: u8,
     pub created_at: i64,
     pub canceled_at: Option<i64>,
+    pub expiry: Option<i64>,
+    pub target: BidTarget,
 }
 
 pub const LISTING_RECEIPT_SIZE: usize = 8 + //key
@@ -54,7 +73,8 @@ pub const LISTING_RECEIPT_SIZE: usize = 8 + //key
 1 + // bump
 1 + // trade_state_bump
 8 + // created_at
-1 + 8; // canceled_at;
+1 + 8 + // canceled_at
+1 + 8; // expiry
 
 /// Receipt for a listing transaction.
 #[account]
@@ -71,6 +91,7 @@ pub struct ListingReceipt {
     pub trade_state_bump: u8,
     pub created_at: i64,
     pub canceled_at: Option<i64>,
+    pub expiry: Option<i64>,
 }
 
 pub const PURCHASE_RECEIPT_SIZE: usize = 8 + //key
@@ -98,6 +119,33 @@ pub struct PurchaseReceipt {
     pub created_at: i64,
 }
 
+/// Receipts printed before `expiry` and `target` were added are shorter than their current size,
+/// the missing tail is read as zeroes so they deserialize with no expiry and a token target.
+fn load_receipt<T: AccountDeserialize>(data: &[u8], size: usize) -> Result<T> {
+    let mut padded = data.to_vec();
+    if padded.len() < size {
+        padded.resize(size, 0);
+    }
+    T::try_deserialize(&mut padded.as_slice())
+}
+
+/// Writes a receipt back into its account, which may still have the shorter size of a receipt
+/// printed before `expiry` and `target` were added. Only a zeroed tail can be dropped in that case.
+fn store_receipt<T: AccountSerialize>(receipt: &T, data: &mut [u8]) -> Result<()> {
+    let mut serialized = Vec::with_capacity(data.len());
+    receipt.try_serialize(&mut serialized)?;
+
+    if serialized.len() > data.len() {
+        if serialized[data.len()..].iter().any(|byte| *byte != 0) {
+            return Err(ErrorCode::AccountDidNotSerialize.into());
+        }
+        serialized.truncate(data.len());
+    }
+
+    data[..serialized.len()].copy_from_slice(&serialized);
+    Ok(())
+}
+
 /// Accounts for the [`print_listing_receipt` hanlder](fn.print_listing_receipt.html).
 #[derive(Accounts)]
 #[instruction(receipt_bump: u8)]
@@ -143,10 +191,38 @@ pub fn print_listing_receipt<'info>(
     let seller_trade_state = &prev_instruction_accounts[6];
     let metadata = &prev_instruction_accounts[2];
 
-    assert_program_listing_instruction(&prev_instruction.data[..8])?;
+    let listing_type = assert_program_listing_instruction(&prev_instruction.data[..8])?;
 
     let mut buffer = &prev_instruction.data[8..];
-    let sell_data = Sell::deserialize(&mut buffer)?;
+    let (trade_state_bump, buyer_price, token_size, expiry) = match listing_type {
+        ListingType::Sell => {
+            let sell_data = Sell::deserialize(&mut buffer)?;
+            (
+                sell_data.trade_state_bump,
+                sell_data.buyer_price,
+                sell_data.token_size,
+                None,
+            )
+        }
+        ListingType::SellWithExpiry => {
+            let sell_data = SellWithExpiry::deserialize(&mut buffer)?;
+            (
+                sell_data.trade_state_bump,
+                sell_data.buyer_price,
+                sell_data.token_size,
+                Some(sell_data.expiry),
+            )
+        }
+        ListingType::AuctioneerSell => {
+            let sell_data = AuctioneerSell::deserialize(&mut buffer)?;
+            (
+                sell_data.trade_state_bump,
+                u64::MAX,
+                sell_data.token_size,
+                None,
+            )
+        }
+    };
 
     assert_keys_equal(prev_instruction.program_id, id())?;
 
@@ -187,15 +263,16 @@ pub fn print_listing_receipt<'info>(
         seller: wallet.pubkey,
         metadata: metadata.pubkey,
         purchase_receipt: None,
-        price: sell_data.buyer_price,
-        token_size: sell_data.token_size,
+        price: buyer_price,
+        token_size,
         bump: receipt_bump,
-        trade_state_bump: sell_data.trade_state_bump,
+        trade_state_bump,
         created_at: clock.unix_timestamp,
         canceled_at: None,
+        expiry,
     };
 
-    receipt.try_serialize(&mut *receipt_account.try_borrow_mut_data()?)?;
+    store_receipt(&receipt, &mut receipt_account.try_borrow_mut_data()?)?;
 
     Ok(())
 }
@@ -227,9 +304,12 @@ pub fn cancel_listing_receipt<'info>(
     let prev_instruction = get_instruction_relative(-1, instruction_account)?;
     let prev_instruction_accounts = prev_instruction.accounts;
 
-    let trade_state = &prev_instruction_accounts[6];
-
-    assert_program_cancel_instruction(&prev_instruction.data[..8])?;
+    let trade_state = match assert_program_cancel_instruction(&prev_instruction.data[..8])? {
+        CancelType::Cancel | CancelType::AuctioneerCancel => &prev_instruction_accounts[6],
+        CancelType::CancelCollectionBuy => {
+            return Err(AuctionHouseError::InstructionMismatch.into())
+        }
+    };
 
     if receipt_info.data_is_empty() {
         return Err(AuctionHouseError::ReceiptIsEmpty.into());
@@ -242,13 +322,12 @@ pub fn cancel_listing_receipt<'info>(
     )?;
 
     let mut receipt_data = receipt_info.try_borrow_mut_data()?;
-    let mut receipt_data_slice: &[u8] = &receipt_data;
 
-    let mut receipt = ListingReceipt::try_deserialize(&mut receipt_data_slice)?;
+    let mut receipt: ListingReceipt = load_receipt(&receipt_data, LISTING_RECEIPT_SIZE)?;
 
     receipt.canceled_at = Some(clock.unix_timestamp);
 
-    receipt.try_serialize(&mut *receipt_data)?;
+    store_receipt(&receipt, &mut receipt_data)?;
 
     Ok(())
 }
@@ -301,16 +380,64 @@ pub fn print_bid_receipt<'info>(
     let buyer_trade_state = &prev_instruction_accounts[10];
     let metadata = &prev_instruction_accounts[5];
 
-    let mut buffer = &prev_instruction.data[8..];
-    let buy_data = Buy::deserialize(&mut buffer)?;
-
     let bid_type = assert_program_bid_instruction(&prev_instruction.data[..8])?;
 
+    let mut buffer = &prev_instruction.data[8..];
+    let (trade_state_bump, buyer_price, token_size, expiry, target) = match bid_type {
+        BidType::PrivateSale | BidType::PublicSale => {
+            let buy_data = Buy::deserialize(&mut buffer)?;
+            (
+                buy_data.trade_state_bump,
+                buy_data.buyer_price,
+                buy_data.token_size,
+                None,
+                BidTarget::Token,
+            )
+        }
+        BidType::PrivateSaleWithExpiry | BidType::PublicSaleWithExpiry => {
+            let buy_data = BuyWithExpiry::deserialize(&mut buffer)?;
+            (
+                buy_data.trade_state_bump,
+                buy_data.buyer_price,
+                buy_data.token_size,
+                Some(buy_data.expiry),
+                BidTarget::Token,
+            )
+        }
+        BidType::AuctioneerPrivateSale | BidType::AuctioneerPublicSale => {
+            let buy_data = AuctioneerBuy::deserialize(&mut buffer)?;
+            (
+                buy_data.trade_state_bump,
+                buy_data.buyer_price,
+                buy_data.token_size,
+                None,
+                BidTarget::Token,
+            )
+        }
+        // The collection mint takes the place of the token account in the collection_buy accounts.
+        BidType::CollectionSale => {
+            let buy_data = CollectionBuy::deserialize(&mut buffer)?;
+            (
+                buy_data.trade_state_bump,
+                buy_data.buyer_price,
+                buy_data.token_size,
+                None,
+                BidTarget::Collection {
+                    mint: token_account.pubkey,
+                    creator: buy_data.creator,
+                },
+            )
+        }
+    };
+
     let token_account = match bid_type {
         BidType::PrivateSale => Some(token_account.pubkey),
+        BidType::PrivateSaleWithExpiry => Some(token_account.pubkey),
         BidType::AuctioneerPrivateSale => Some(token_account.pubkey),
         BidType::PublicSale => None,
+        BidType::PublicSaleWithExpiry => None,
         BidType::AuctioneerPublicSale => None,
+        BidType::CollectionSale => None,
     };
 
     assert_derivation(
@@ -353,15 +480,17 @@ pub fn print_bid_receipt<'info>(
         buyer: wallet.pubkey,
         metadata: metadata.pubkey,
         purchase_receipt: None,
-        price: buy_data.buyer_price,
-        token_size: buy_data.token_size,
+        price: buyer_price,
+        token_size,
         bump: receipt_bump,
-        trade_state_bump: buy_data.trade_state_bump,
+        trade_state_bump,
         created_at: clock.unix_timestamp,
         canceled_at: None,
+        expiry,
+        target,
     };
 
-    receipt.try_serialize(&mut *receipt_account.try_borrow_mut_data()?)?;
+    store_receipt(&receipt, &mut receipt_account.try_borrow_mut_data()?)?;
 
     Ok(())
 }
@@ -393,9 +522,10 @@ pub fn cancel_bid_receipt<'info>(
     let prev_instruction = get_instruction_relative(-1, instruction_account)?;
     let prev_instruction_accounts = prev_instruction.accounts;
 
-    let trade_state = &prev_instruction_accounts[6];
-
-    assert_program_cancel_instruction(&prev_instruction.data[..8])?;
+    let trade_state = match assert_program_cancel_instruction(&prev_instruction.data[..8])? {
+        CancelType::Cancel | CancelType::AuctioneerCancel => &prev_instruction_accounts[6],
+        CancelType::CancelCollectionBuy => &prev_instruction_accounts[5],
+    };
 
     if receipt_info.data_is_empty() {
         return Err(AuctionHouseError::ReceiptIsEmpty.into());
@@ -408,13 +538,12 @@ pub fn cancel_bid_receipt<'info>(
     )?;
 
     let mut receipt_data = receipt_info.try_borrow_mut_data()?;
-    let mut receipt_data_slice: &[u8] = &receipt_data;
 
-    let mut receipt = BidReceipt::try_deserialize(&mut receipt_data_slice)?;
+    let mut receipt: BidReceipt = load_receipt(&receipt_data, BID_RECEIPT_SIZE)?;
 
     receipt.canceled_at = Some(clock.unix_timestamp);
 
-    receipt.try_serialize(&mut *receipt_data)?;
+    store_receipt(&receipt, &mut receipt_data)?;
 
     Ok(())
 }
@@ -553,22 +682,21 @@ pub fn print_purchase_receipt<'info>(
     purchase.try_serialize(&mut *purchase_receipt_account.try_borrow_mut_data()?)?;
 
     let mut listing_receipt_data = listing_receipt_info.try_borrow_mut_data()?;
-    let mut listing_receipt_data_slice: &[u8] = &listing_receipt_data;
 
-    let mut listing_receipt = ListingReceipt::try_deserialize(&mut listing_receipt_data_slice)?;
+    let mut listing_receipt: ListingReceipt =
+        load_receipt(&listing_receipt_data, LISTING_RECEIPT_SIZE)?;
 
     listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
 
-    listing_receipt.try_serialize(&mut *listing_receipt_data)?;
+    store_receipt(&listing_receipt, &mut listing_receipt_data)?;
 
     let mut bid_receipt_data = bid_receipt_account.try_borrow_mut_data()?;
-    let mut bid_receipt_slice: &[u8] = &bid_receipt_data;
 
-    let mut bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_slice)?;
+    let mut bid_receipt: BidReceipt = load_receipt(&bid_receipt_data, BID_RECEIPT_SIZE)?;
 
     bid_receipt.purchase_receipt = Some(purchase_receipt_account.key());
 
-    bid_receipt.try_serialize(&mut *bid_receipt_data)?;
+    store_receipt(&bid_receipt, &mut bid_receipt_data)?;
 
     Ok(())
 }
@@ -240,6 +240,7 @@ pub fn sell<'info>(
     program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
@@ -276,6 +277,7 @@ pub fn sell<'info>(
         program_as_signer_bump,
         buyer_price,
         token_size,
+        expiry,
     )
 }
 
@@ -332,6 +334,7 @@ pub fn auctioneer_sell<'info>(
         program_as_signer_bump,
         u64::MAX,
         token_size,
+        None,
     )
 }
 
@@ -344,6 +347,7 @@ fn sell_logic<'info>(
     _program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
     let wallet = &accounts.wallet;
     let token_account = &accounts.token_account;
@@ -400,6 +404,8 @@ fn sell_logic<'info>(
         return Err(AuctionHouseError::InvalidTokenAmount.into());
     }
 
+    assert_valid_expiry(seller_trade_state, expiry)?;
+
     if wallet.is_signer {
         invoke(
             &approve(
@@ -435,16 +441,24 @@ fn sell_logic<'info>(
             &token_size.to_le_bytes(),
             &[trade_state_bump],
         ];
+        let ts_size = if expiry.is_some() {
+            EXPIRING_TRADE_STATE_SIZE
+        } else {
+            TRADE_STATE_SIZE
+        };
         create_or_allocate_account_raw(
             *program_id,
             &ts_info,
             &rent.to_account_info(),
             system_program,
             &fee_payer,
-            TRADE_STATE_SIZE,
+            ts_size,
             fee_seeds,
             &ts_seeds,
         )?;
+        if let Some(expiry) = expiry {
+            set_trade_state_expiry(&ts_info, expiry, fee_payer.key)?;
+        }
     }
 
     let data = &mut ts_info.data.borrow_mut();
@@ -15,7 +15,11 @@ use anchor_lang::{
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use mpl_token_metadata::{
+    pda::find_collection_royalties_account,
+    state::{Metadata, TokenMetadataAccount},
+    utils::resolve_royalties,
+};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
 use std::{convert::TryInto, slice::Iter};
@@ -182,13 +186,17 @@ pub fn assert_keys_equal_err(key1: Pubkey, key2: Pubkey) -> Result<()> {
 pub enum BidType {
     PublicSale,
     PrivateSale,
+    PublicSaleWithExpiry,
+    PrivateSaleWithExpiry,
     AuctioneerPublicSale,
     AuctioneerPrivateSale,
+    CollectionSale,
 }
 
 #[derive(Debug, Clone)]
 pub enum ListingType {
     Sell,
+    SellWithExpiry,
     AuctioneerSell,
 }
 
@@ -196,20 +204,25 @@ pub enum ListingType {
 pub enum PurchaseType {
     ExecuteSale,
     AuctioneerExecuteSale,
+    ExecuteCollectionSale,
 }
 
 #[derive(Debug, Clone)]
 pub enum CancelType {
     Cancel,
     AuctioneerCancel,
+    CancelCollectionBuy,
 }
 
 pub fn assert_program_bid_instruction(sighash: &[u8]) -> Result<BidType> {
     match sighash {
         [169, 84, 218, 35, 42, 206, 16, 171] => Ok(BidType::PublicSale),
         [102, 6, 61, 18, 1, 218, 235, 234] => Ok(BidType::PrivateSale),
+        [34, 122, 62, 62, 22, 175, 108, 82] => Ok(BidType::PublicSaleWithExpiry),
+        [147, 201, 118, 124, 1, 27, 171, 103] => Ok(BidType::PrivateSaleWithExpiry),
         [221, 239, 99, 240, 86, 46, 213, 126] => Ok(BidType::AuctioneerPublicSale),
         [17, 106, 133, 46, 229, 48, 45, 208] => Ok(BidType::AuctioneerPrivateSale),
+        [53, 107, 148, 41, 184, 45, 177, 113] => Ok(BidType::CollectionSale),
         _ => Err(AuctionHouseError::InstructionMismatch.into()),
     }
 }
@@ -217,6 +230,7 @@ pub fn assert_program_bid_instruction(sighash: &[u8]) -> Result<BidType> {
 pub fn assert_program_listing_instruction(sighash: &[u8]) -> Result<ListingType> {
     match sighash {
         [51, 230, 133, 164, 1, 127, 131, 173] => Ok(ListingType::Sell),
+        [252, 109, 255, 1, 141, 145, 127, 153] => Ok(ListingType::SellWithExpiry),
         [251, 60, 142, 195, 121, 203, 26, 183] => Ok(ListingType::AuctioneerSell),
         _ => Err(AuctionHouseError::InstructionMismatch.into()),
     }
@@ -226,6 +240,7 @@ pub fn assert_program_purchase_instruction(sighash: &[u8]) -> Result<PurchaseTyp
     match sighash {
         [37, 74, 217, 157, 79, 49, 35, 6] => Ok(PurchaseType::ExecuteSale),
         [68, 125, 32, 65, 251, 43, 35, 53] => Ok(PurchaseType::AuctioneerExecuteSale),
+        [213, 13, 253, 255, 139, 53, 120, 16] => Ok(PurchaseType::ExecuteCollectionSale),
         _ => Err(AuctionHouseError::InstructionMismatch.into()),
     }
 }
@@ -234,6 +249,7 @@ pub fn assert_program_cancel_instruction(sighash: &[u8]) -> Result<CancelType> {
     match sighash {
         [232, 219, 223, 41, 219, 236, 220, 190] => Ok(CancelType::Cancel),
         [197, 97, 152, 196, 115, 204, 64, 215] => Ok(CancelType::AuctioneerCancel),
+        [90, 118, 170, 66, 37, 219, 106, 231] => Ok(CancelType::CancelCollectionBuy),
         _ => Err(AuctionHouseError::InstructionMismatch.into()),
     }
 }
@@ -380,7 +396,22 @@ pub fn pay_creator_fees<'a>(
     is_native: bool,
 ) -> Result<u64> {
     let metadata = Metadata::from_account_info(metadata_info)?;
-    let fees = metadata.data.seller_fee_basis_points;
+    // Verified collection items without creators may inherit the collection default royalties.
+    // Their collection royalties account, uninitialized when the collection has none, is the
+    // first remaining account ahead of the creator accounts. Sales built before collection
+    // royalties don't pass any account for these items.
+    let collection_royalties_info = match &metadata.collection {
+        Some(collection) if collection.verified && metadata.data.creators.is_none() => {
+            let (collection_royalties, _) = find_collection_royalties_account(&collection.key);
+            match remaining_accounts.next() {
+                Some(info) if *info.key == collection_royalties => Some(info),
+                Some(_) => return Err(AuctionHouseError::InvalidCollectionRoyalties.into()),
+                None => None,
+            }
+        }
+        _ => None,
+    };
+    let (fees, creators) = resolve_royalties(&metadata, collection_royalties_info)?;
     let total_fee = (fees as u128)
         .checked_mul(size as u128)
         .ok_or(AuctionHouseError::NumericalOverflow)?
@@ -390,7 +421,7 @@ pub fn pay_creator_fees<'a>(
     let remaining_size = size
         .checked_sub(total_fee)
         .ok_or(AuctionHouseError::NumericalOverflow)?;
-    match metadata.data.creators {
+    match creators {
         Some(creators) => {
             for creator in creators {
                 let pct = creator.share as u128;
@@ -617,6 +648,151 @@ pub fn assert_valid_trade_state(
     }
 }
 
+pub fn assert_valid_collection_trade_state(
+    wallet: &Pubkey,
+    auction_house: &Account<AuctionHouse>,
+    buyer_price: u64,
+    token_size: u64,
+    trade_state: &AccountInfo,
+    collection_mint: &Pubkey,
+    ts_bump: u8,
+) -> Result<u8> {
+    let ah_pubkey = auction_house.key();
+    let canonical_bump = assert_derivation(
+        &crate::id(),
+        trade_state,
+        &[
+            PREFIX.as_bytes(),
+            wallet.as_ref(),
+            ah_pubkey.as_ref(),
+            COLLECTION.as_bytes(),
+            auction_house.treasury_mint.as_ref(),
+            collection_mint.as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes(),
+        ],
+    )?;
+
+    if canonical_bump != ts_bump {
+        return Err(AuctionHouseError::DerivedKeyInvalid.into());
+    }
+    Ok(canonical_bump)
+}
+
+/// Cheap method to grab the creator a collection bid trade state requires, if any.
+pub fn get_collection_bid_creator(trade_state: &AccountInfo) -> Result<Option<Pubkey>> {
+    // Collection trade state layout:   bump(1), creator(32)
+    if trade_state.data_len() != COLLECTION_TRADE_STATE_SIZE {
+        return Err(AuctionHouseError::BuyerTradeStateNotValid.into());
+    }
+    let data = trade_state.try_borrow_data()?;
+    let creator = Pubkey::new_from_array(*array_ref![data, TRADE_STATE_SIZE, PUBKEY_BYTES]);
+    if creator == Pubkey::default() {
+        Ok(None)
+    } else {
+        Ok(Some(creator))
+    }
+}
+
+/// Checks that a collection bid trade state was placed by `wallet` on the verified collection of
+/// `metadata`, and that `metadata` has the verified creator the bid requires, if any.
+pub fn assert_valid_collection_bid(
+    wallet: &Pubkey,
+    auction_house: &Account<AuctionHouse>,
+    buyer_price: u64,
+    token_size: u64,
+    trade_state: &AccountInfo,
+    metadata: &AccountInfo,
+    ts_bump: u8,
+) -> Result<()> {
+    let metadata = Metadata::from_account_info(metadata)?;
+    let collection = match metadata.collection {
+        Some(collection) if collection.verified => collection,
+        _ => return Err(AuctionHouseError::CollectionNotVerified.into()),
+    };
+
+    assert_valid_collection_trade_state(
+        wallet,
+        auction_house,
+        buyer_price,
+        token_size,
+        trade_state,
+        &collection.key,
+        ts_bump,
+    )
+    .map_err(|_| AuctionHouseError::CollectionNotVerified)?;
+
+    if let Some(creator) = get_collection_bid_creator(trade_state)? {
+        let has_creator = match metadata.data.creators {
+            Some(creators) => creators.iter().any(|c| c.verified && c.address == creator),
+            None => false,
+        };
+        if !has_creator {
+            return Err(AuctionHouseError::CollectionBidCreatorMismatch.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Cheap method to grab the expiry and rent payer of a trade state created with an expiry.
+/// Returns `None` for trade states created without one.
+pub fn get_trade_state_expiry(trade_state: &AccountInfo) -> Result<Option<(i64, Pubkey)>> {
+    // Expiring trade state layout:   bump(1), expiry(8), rent payer(32)
+    if trade_state.data_len() != EXPIRING_TRADE_STATE_SIZE {
+        return Ok(None);
+    }
+    let data = trade_state.try_borrow_data()?;
+    if data[0] == 0 {
+        return Ok(None);
+    }
+    let expiry = i64::from_le_bytes(*array_ref![data, TRADE_STATE_SIZE, 8]);
+    let payer = Pubkey::new_from_array(*array_ref![data, TRADE_STATE_SIZE + 8, PUBKEY_BYTES]);
+    Ok(Some((expiry, payer)))
+}
+
+/// Records the expiry and rent payer after the bump of a newly allocated expiring trade state.
+pub fn set_trade_state_expiry(
+    trade_state: &AccountInfo,
+    expiry: i64,
+    payer: &Pubkey,
+) -> Result<()> {
+    let mut data = trade_state.try_borrow_mut_data()?;
+    data[TRADE_STATE_SIZE..TRADE_STATE_SIZE + 8].copy_from_slice(&expiry.to_le_bytes());
+    data[TRADE_STATE_SIZE + 8..EXPIRING_TRADE_STATE_SIZE].copy_from_slice(payer.as_ref());
+    Ok(())
+}
+
+/// Checks the expiry requested for a bid or listing against its trade state.
+/// A new trade state needs an expiry in the future, an existing one must still be live and
+/// have been created with the same expiry.
+pub fn assert_valid_expiry(trade_state: &AccountInfo, expiry: Option<i64>) -> Result<()> {
+    if trade_state.data_is_empty() {
+        if let Some(expiry) = expiry {
+            if expiry <= Clock::get()?.unix_timestamp {
+                return Err(AuctionHouseError::InvalidExpiry.into());
+            }
+        }
+        return Ok(());
+    }
+
+    if get_trade_state_expiry(trade_state)?.map(|(expiry, _)| expiry) != expiry {
+        return Err(AuctionHouseError::TradeStateExpiryMismatch.into());
+    }
+
+    assert_trade_state_not_expired(trade_state)
+}
+
+/// Fails once the expiry of the trade state, if it has one, has passed.
+pub fn assert_trade_state_not_expired(trade_state: &AccountInfo) -> Result<()> {
+    if let Some((expiry, _)) = get_trade_state_expiry(trade_state)? {
+        if Clock::get()?.unix_timestamp > expiry {
+            return Err(AuctionHouseError::TradeStateExpired.into());
+        }
+    }
+    Ok(())
+}
+
 // This function verifies that there are enough funds in `account` such that `amount` can be
 // withdrawn.  If there are not sufficent funds it returns an error.  If there are sufficient
 // funds, it returns any additional amount needed to keep the account above the rent exempt
@@ -0,0 +1,357 @@
+#![cfg(feature = "test-bpf")]
+pub mod common;
+pub mod utils;
+
+use common::*;
+use utils::setup_functions::*;
+
+use mpl_auction_house::constants::EXPIRING_TRADE_STATE_SIZE;
+
+async fn now(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+fn close_expired_trade_state(
+    context: &mut ProgramTestContext,
+    trade_state: Pubkey,
+    payer: Pubkey,
+    cranker: &Keypair,
+) -> Transaction {
+    let instruction = Instruction {
+        program_id: mpl_auction_house::id(),
+        data: mpl_auction_house::instruction::CloseExpiredTradeState {}.data(),
+        accounts: mpl_auction_house::accounts::CloseExpiredTradeState { trade_state, payer }
+            .to_account_metas(None),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&cranker.pubkey()),
+        &[cranker],
+        context.last_blockhash,
+    )
+}
+
+async fn create_test_metadata(context: &mut ProgramTestContext) -> Metadata {
+    let test_metadata = Metadata::new();
+    airdrop(context, &test_metadata.token.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            1,
+        )
+        .await
+        .unwrap();
+    test_metadata
+}
+
+#[tokio::test]
+async fn expired_bid_cannot_be_executed() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, authority) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context).await;
+
+    let ((sell_acc, _), sell_tx) = sell(&mut context, &ahkey, &ah, &test_metadata, ONE_SOL, 1);
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    let expiry = now(&mut context).await + 100;
+    let ((bid_acc, print_receipt_acc), buy_tx) = buy_with_expiry(
+        &mut context,
+        &ahkey,
+        &ah,
+        &test_metadata,
+        &test_metadata.token.pubkey(),
+        &buyer,
+        ONE_SOL,
+        1,
+        Some(expiry),
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    let trade_state = context
+        .banks_client
+        .get_account(bid_acc.buyer_trade_state)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trade_state.data.len(), EXPIRING_TRADE_STATE_SIZE);
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(print_receipt_acc.receipt)
+        .await
+        .unwrap()
+        .unwrap();
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(bid_receipt.expiry, Some(expiry));
+
+    advance_clock(&mut context, 200).await;
+
+    airdrop(&mut context, &ah.auction_house_fee_account, TEN_SOL)
+        .await
+        .unwrap();
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &ahkey,
+        &ah,
+        &authority,
+        &test_metadata,
+        &buyer.pubkey(),
+        &test_metadata.token.pubkey(),
+        &sell_acc.token_account,
+        &sell_acc.seller_trade_state,
+        &bid_acc.buyer_trade_state,
+        1,
+        ONE_SOL,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+    assert_error!(error, TRADE_STATE_EXPIRED);
+}
+
+#[tokio::test]
+async fn close_expired_listing_refunds_payer() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context).await;
+
+    let expiry = now(&mut context).await + 100;
+    let ((sell_acc, print_receipt_acc), sell_tx) = sell_with_expiry(
+        &mut context,
+        &ahkey,
+        &ah,
+        &test_metadata,
+        ONE_SOL,
+        1,
+        Some(expiry),
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let listing_receipt_account = context
+        .banks_client
+        .get_account(print_receipt_acc.receipt)
+        .await
+        .unwrap()
+        .unwrap();
+    let listing_receipt =
+        ListingReceipt::try_deserialize(&mut listing_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(listing_receipt.expiry, Some(expiry));
+
+    let cranker = Keypair::new();
+    airdrop(&mut context, &cranker.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let early_tx = close_expired_trade_state(
+        &mut context,
+        sell_acc.seller_trade_state,
+        test_metadata.token.pubkey(),
+        &cranker,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(early_tx)
+        .await
+        .unwrap_err();
+    assert_error!(error, TRADE_STATE_NOT_EXPIRED);
+
+    advance_clock(&mut context, 200).await;
+
+    let trade_state_lamports = context
+        .banks_client
+        .get_account(sell_acc.seller_trade_state)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    let seller_before = context
+        .banks_client
+        .get_account(test_metadata.token.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    // A different cranker keeps the transaction from being deduplicated with the failed one.
+    let cranker = Keypair::new();
+    airdrop(&mut context, &cranker.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let crank_tx = close_expired_trade_state(
+        &mut context,
+        sell_acc.seller_trade_state,
+        test_metadata.token.pubkey(),
+        &cranker,
+    );
+    context
+        .banks_client
+        .process_transaction(crank_tx)
+        .await
+        .unwrap();
+
+    let seller_after = context
+        .banks_client
+        .get_account(test_metadata.token.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert_eq!(seller_after, seller_before + trade_state_lamports);
+
+    let trade_state = context
+        .banks_client
+        .get_account(sell_acc.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(trade_state.is_none());
+}
+
+#[tokio::test]
+async fn close_expired_trade_state_wrong_payer_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context).await;
+
+    let expiry = now(&mut context).await + 100;
+    let ((sell_acc, _), sell_tx) = sell_with_expiry(
+        &mut context,
+        &ahkey,
+        &ah,
+        &test_metadata,
+        ONE_SOL,
+        1,
+        Some(expiry),
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    advance_clock(&mut context, 200).await;
+
+    let cranker = Keypair::new();
+    airdrop(&mut context, &cranker.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let crank_tx = close_expired_trade_state(
+        &mut context,
+        sell_acc.seller_trade_state,
+        cranker.pubkey(),
+        &cranker,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(crank_tx)
+        .await
+        .unwrap_err();
+    assert_error!(error, PUBLIC_KEY_MISMATCH);
+}
+
+#[tokio::test]
+async fn close_trade_state_without_expiry_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context).await;
+
+    let ((sell_acc, _), sell_tx) = sell(&mut context, &ahkey, &ah, &test_metadata, ONE_SOL, 1);
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let cranker = Keypair::new();
+    airdrop(&mut context, &cranker.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let crank_tx = close_expired_trade_state(
+        &mut context,
+        sell_acc.seller_trade_state,
+        test_metadata.token.pubkey(),
+        &cranker,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(crank_tx)
+        .await
+        .unwrap_err();
+    assert_error!(error, TRADE_STATE_HAS_NO_EXPIRY);
+}
+
+#[tokio::test]
+async fn bid_with_past_expiry_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context).await;
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    let expiry = now(&mut context).await;
+    let (_, buy_tx) = buy_with_expiry(
+        &mut context,
+        &ahkey,
+        &ah,
+        &test_metadata,
+        &test_metadata.token.pubkey(),
+        &buyer,
+        ONE_SOL,
+        1,
+        Some(expiry),
+    );
+    let error = context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap_err();
+    assert_error!(error, INVALID_EXPIRY);
+}
@@ -0,0 +1,410 @@
+#![cfg(feature = "test-bpf")]
+pub mod common;
+pub mod utils;
+
+use common::*;
+use utils::setup_functions::*;
+
+use mpl_auction_house::{
+    constants::COLLECTION_TRADE_STATE_SIZE,
+    receipt::{BidTarget, PurchaseReceipt},
+};
+use mpl_testing_utils::{solana::clone_keypair, utils::MasterEditionV2};
+use mpl_token_metadata::{
+    instruction::update_metadata_accounts_v2,
+    state::{Collection, Creator, DataV2},
+};
+use solana_program::{instruction::AccountMeta, program_pack::Pack};
+use spl_token::state::Account;
+use std::result::Result as StdResult;
+
+async fn create_collection(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+    let collection = Metadata::new();
+    airdrop(context, &collection.token.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    collection
+        .create(
+            context,
+            "Collection".to_string(),
+            "COL".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            1,
+        )
+        .await
+        .unwrap();
+    let master_edition = MasterEditionV2::new(&collection);
+    master_edition.create(context, Some(0)).await.unwrap();
+    (collection, master_edition)
+}
+
+/// Create an item in `collection`, optionally with the test payer as its only, verified, creator.
+async fn create_collection_item(
+    context: &mut ProgramTestContext,
+    collection: &Metadata,
+    master_edition: &MasterEditionV2,
+    verify: bool,
+    with_creator: bool,
+) -> Metadata {
+    let item = Metadata::new();
+    airdrop(context, &item.token.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    item.create(
+        context,
+        "Item".to_string(),
+        "ITM".to_string(),
+        "uri".to_string(),
+        None,
+        10,
+        true,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let update_tx = Transaction::new_signed_with_payer(
+        &[update_metadata_accounts_v2(
+            mpl_token_metadata::id(),
+            item.pubkey,
+            context.payer.pubkey(),
+            None,
+            Some(DataV2 {
+                name: "Item".to_string(),
+                symbol: "ITM".to_string(),
+                uri: "uri".to_string(),
+                creators: if with_creator {
+                    Some(vec![Creator {
+                        address: context.payer.pubkey(),
+                        verified: true,
+                        share: 100,
+                    }])
+                } else {
+                    None
+                },
+                seller_fee_basis_points: 10,
+                collection: Some(Collection {
+                    verified: false,
+                    key: collection.mint.pubkey(),
+                }),
+                uses: None,
+            }),
+            None,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(update_tx)
+        .await
+        .unwrap();
+
+    if verify {
+        let collection_authority = clone_keypair(&context.payer);
+        item.verify_collection(
+            context,
+            collection.pubkey,
+            collection_authority,
+            collection.mint.pubkey(),
+            master_edition.pubkey,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    item
+}
+
+async fn collection_sale(
+    context: &mut ProgramTestContext,
+    collection: &Metadata,
+    item: &Metadata,
+    creator: Option<Pubkey>,
+    remaining_accounts: Vec<AccountMeta>,
+) -> StdResult<(), BanksClientError> {
+    let (ah, ahkey, authority) = existing_auction_house_test_context(context).await.unwrap();
+
+    let ((sell_acc, _), sell_tx) = sell(context, &ahkey, &ah, item, ONE_SOL, 1);
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let buyer = Keypair::new();
+    airdrop(context, &buyer.pubkey(), TEN_SOL).await.unwrap();
+    let ((bid_acc, _), buy_tx) =
+        collection_buy(context, &ahkey, &ah, collection, &buyer, ONE_SOL, creator);
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    airdrop(context, &ah.auction_house_fee_account, TEN_SOL)
+        .await
+        .unwrap();
+    let (_, execute_sale_tx) = execute_collection_sale(
+        context,
+        &ahkey,
+        &ah,
+        &authority,
+        item,
+        &buyer.pubkey(),
+        &item.token.pubkey(),
+        &sell_acc.token_account,
+        &sell_acc.seller_trade_state,
+        &bid_acc.buyer_trade_state,
+        1,
+        ONE_SOL,
+        remaining_accounts,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+}
+
+#[tokio::test]
+async fn execute_collection_sale_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, authority) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let item = create_collection_item(&mut context, &collection, &master_edition, true, true).await;
+
+    let ((sell_acc, _), sell_tx) = sell(&mut context, &ahkey, &ah, &item, ONE_SOL, 1);
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    let creator = Some(context.payer.pubkey());
+    let ((bid_acc, print_receipt_acc), buy_tx) = collection_buy(
+        &mut context,
+        &ahkey,
+        &ah,
+        &collection,
+        &buyer,
+        ONE_SOL,
+        creator,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    let trade_state = context
+        .banks_client
+        .get_account(bid_acc.buyer_trade_state)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trade_state.data.len(), COLLECTION_TRADE_STATE_SIZE);
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(print_receipt_acc.receipt)
+        .await
+        .unwrap()
+        .unwrap();
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(bid_receipt.metadata, collection.pubkey);
+    assert_eq!(bid_receipt.token_account, None);
+    assert_eq!(
+        bid_receipt.target,
+        BidTarget::Collection {
+            mint: collection.mint.pubkey(),
+            creator,
+        }
+    );
+
+    airdrop(&mut context, &ah.auction_house_fee_account, TEN_SOL)
+        .await
+        .unwrap();
+    let ((execute_sale_acc, purchase_receipt_acc), execute_sale_tx) = execute_collection_sale(
+        &mut context,
+        &ahkey,
+        &ah,
+        &authority,
+        &item,
+        &buyer.pubkey(),
+        &item.token.pubkey(),
+        &sell_acc.token_account,
+        &sell_acc.seller_trade_state,
+        &bid_acc.buyer_trade_state,
+        1,
+        ONE_SOL,
+        vec![],
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap();
+
+    let buyer_token_account = Account::unpack_from_slice(
+        context
+            .banks_client
+            .get_account(execute_sale_acc.buyer_receipt_token_account)
+            .await
+            .unwrap()
+            .unwrap()
+            .data
+            .as_slice(),
+    )
+    .unwrap();
+    assert_eq!(buyer_token_account.amount, 1);
+
+    let purchase_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_acc.purchase_receipt)
+        .await
+        .unwrap()
+        .unwrap();
+    let purchase_receipt =
+        PurchaseReceipt::try_deserialize(&mut purchase_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(purchase_receipt.buyer, buyer.pubkey());
+    assert_eq!(purchase_receipt.metadata, item.pubkey);
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(print_receipt_acc.receipt)
+        .await
+        .unwrap()
+        .unwrap();
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(
+        bid_receipt.purchase_receipt,
+        Some(purchase_receipt_acc.purchase_receipt)
+    );
+}
+
+#[tokio::test]
+async fn execute_collection_sale_unverified_item_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let item =
+        create_collection_item(&mut context, &collection, &master_edition, false, true).await;
+
+    let error = collection_sale(&mut context, &collection, &item, None, vec![])
+        .await
+        .unwrap_err();
+    assert_error!(error, COLLECTION_NOT_VERIFIED);
+}
+
+#[tokio::test]
+async fn execute_collection_sale_other_collection_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let (other_collection, _) = create_collection(&mut context).await;
+    let item = create_collection_item(&mut context, &collection, &master_edition, true, true).await;
+
+    let error = collection_sale(&mut context, &other_collection, &item, None, vec![])
+        .await
+        .unwrap_err();
+    assert_error!(error, COLLECTION_NOT_VERIFIED);
+}
+
+#[tokio::test]
+async fn execute_collection_sale_creator_mismatch_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let item = create_collection_item(&mut context, &collection, &master_edition, true, true).await;
+
+    let creator = Keypair::new().pubkey();
+    let error = collection_sale(&mut context, &collection, &item, Some(creator), vec![])
+        .await
+        .unwrap_err();
+    assert_error!(error, COLLECTION_BID_CREATOR_MISMATCH);
+}
+
+#[tokio::test]
+async fn execute_collection_sale_without_collection_royalties_account() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let item =
+        create_collection_item(&mut context, &collection, &master_edition, true, false).await;
+
+    // Sales built before collection royalties don't pass the account.
+    collection_sale(&mut context, &collection, &item, None, vec![])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn execute_collection_sale_wrong_collection_royalties_fails() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (collection, master_edition) = create_collection(&mut context).await;
+    let item =
+        create_collection_item(&mut context, &collection, &master_edition, true, false).await;
+
+    let wrong_account = AccountMeta::new_readonly(Keypair::new().pubkey(), false);
+    let error = collection_sale(&mut context, &collection, &item, None, vec![wrong_account])
+        .await
+        .unwrap_err();
+    assert_error!(error, INVALID_COLLECTION_ROYALTIES);
+}
+
+#[tokio::test]
+async fn cancel_collection_buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let (collection, _) = create_collection(&mut context).await;
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), TEN_SOL)
+        .await
+        .unwrap();
+    let ((bid_acc, _), buy_tx) = collection_buy(
+        &mut context,
+        &ahkey,
+        &ah,
+        &collection,
+        &buyer,
+        ONE_SOL,
+        None,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    let (_, cancel_tx) = cancel_collection_buy(
+        &mut context,
+        &ahkey,
+        &ah,
+        &collection.mint.pubkey(),
+        &buyer,
+        &bid_acc.buyer_trade_state,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .unwrap();
+
+    let trade_state = context
+        .banks_client
+        .get_account(bid_acc.buyer_trade_state)
+        .await
+        .unwrap();
+    assert!(trade_state.is_none());
+}
@@ -23,6 +23,7 @@ pub use std::assert_eq;
 pub const HAS_ONE_CONSTRAINT_VIOLATION: u32 = 2001;
 pub const INVALID_SEEDS: u32 = 2006;
 pub const ACCOUNT_NOT_INITIALIZED: u32 = 3012;
+pub const PUBLIC_KEY_MISMATCH: u32 = 6000;
 pub const MISSING_AUCTIONEER_SCOPE: u32 = 6029;
 pub const NO_AUCTIONEER_PROGRAM_SET: u32 = 6031;
 pub const TOO_MANY_SCOPES: u32 = 6032;
@@ -34,6 +35,13 @@ pub const PARTIAL_BUY_PRICE_MISMATCH: u32 = 6040;
 pub const MISSING_ELEMENTS_NEEDED_FOR_PARTIAL_BUY: u32 = 6038;
 pub const AUCTIONEER_ALREADY_DELEGATED: u32 = 6041;
 pub const INSUFFICIENT_FUNDS: u32 = 6043;
+pub const TRADE_STATE_EXPIRED: u32 = 6044;
+pub const TRADE_STATE_NOT_EXPIRED: u32 = 6045;
+pub const INVALID_EXPIRY: u32 = 6046;
+pub const TRADE_STATE_HAS_NO_EXPIRY: u32 = 6048;
+pub const COLLECTION_NOT_VERIFIED: u32 = 6049;
+pub const COLLECTION_BID_CREATOR_MISMATCH: u32 = 6050;
+pub const INVALID_COLLECTION_ROYALTIES: u32 = 6052;
 
 pub const TEN_SOL: u64 = 10_000_000_000;
 pub const ONE_SOL: u64 = 1_000_000_000;
@@ -10,7 +10,8 @@ use mpl_auction_house::{
     pda::{
         find_auction_house_address, find_auction_house_fee_account_address,
         find_auction_house_treasury_address, find_auctioneer_pda,
-        find_auctioneer_trade_state_address, find_bid_receipt_address, find_escrow_payment_address,
+        find_auctioneer_trade_state_address, find_bid_receipt_address,
+        find_collection_bid_trade_state_address, find_escrow_payment_address,
         find_listing_receipt_address, find_program_as_signer_address,
         find_public_bid_trade_state_address, find_purchase_receipt_address,
         find_trade_state_address,
@@ -23,7 +24,10 @@ use std::result::Result as StdResult;
 
 use mpl_token_metadata::pda::find_metadata_account;
 use solana_program_test::*;
-use solana_sdk::{instruction::Instruction, transaction::Transaction};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    transaction::Transaction,
+};
 use spl_associated_token_account::get_associated_token_address;
 
 pub fn auction_house_program_test() -> ProgramTest {
@@ -233,6 +237,36 @@ pub fn buy(
         mpl_auction_house::accounts::PrintBidReceipt,
     ),
     Transaction,
+) {
+    buy_with_expiry(
+        context,
+        ahkey,
+        ah,
+        test_metadata,
+        owner,
+        buyer,
+        sale_price,
+        token_size,
+        None,
+    )
+}
+
+pub fn buy_with_expiry(
+    context: &mut ProgramTestContext,
+    ahkey: &Pubkey,
+    ah: &AuctionHouse,
+    test_metadata: &Metadata,
+    owner: &Pubkey,
+    buyer: &Keypair,
+    sale_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (
+    (
+        mpl_auction_house::accounts::Buy,
+        mpl_auction_house::accounts::PrintBidReceipt,
+    ),
+    Transaction,
 ) {
     let seller_token_account = get_associated_token_address(owner, &test_metadata.mint.pubkey());
     let trade_state = find_trade_state_address(
@@ -266,13 +300,23 @@ pub fn buy(
 
     let account_metas = accounts.to_account_metas(None);
 
-    let buy_ix = mpl_auction_house::instruction::Buy {
-        trade_state_bump: bts_bump,
-        escrow_payment_bump: escrow_bump,
-        token_size,
-        buyer_price: sale_price,
+    let data = match expiry {
+        Some(expiry) => mpl_auction_house::instruction::BuyWithExpiry {
+            trade_state_bump: bts_bump,
+            escrow_payment_bump: escrow_bump,
+            token_size,
+            buyer_price: sale_price,
+            expiry,
+        }
+        .data(),
+        None => mpl_auction_house::instruction::Buy {
+            trade_state_bump: bts_bump,
+            escrow_payment_bump: escrow_bump,
+            token_size,
+            buyer_price: sale_price,
+        }
+        .data(),
     };
-    let data = buy_ix.data();
 
     let instruction = Instruction {
         program_id: mpl_auction_house::id(),
@@ -547,6 +591,139 @@ pub fn auctioneer_public_buy(
     )
 }
 
+pub fn collection_buy(
+    context: &mut ProgramTestContext,
+    ahkey: &Pubkey,
+    ah: &AuctionHouse,
+    collection_metadata: &Metadata,
+    buyer: &Keypair,
+    sale_price: u64,
+    creator: Option<Pubkey>,
+) -> (
+    (
+        mpl_auction_house::accounts::CollectionBuy,
+        mpl_auction_house::accounts::PrintBidReceipt,
+    ),
+    Transaction,
+) {
+    let (bts, bts_bump) = find_collection_bid_trade_state_address(
+        &buyer.pubkey(),
+        ahkey,
+        &ah.treasury_mint,
+        &collection_metadata.mint.pubkey(),
+        sale_price,
+        1,
+    );
+    let (escrow, escrow_bump) = find_escrow_payment_address(ahkey, &buyer.pubkey());
+
+    let accounts = mpl_auction_house::accounts::CollectionBuy {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        treasury_mint: ah.treasury_mint,
+        collection_mint: collection_metadata.mint.pubkey(),
+        collection_metadata: collection_metadata.pubkey,
+        escrow_payment_account: escrow,
+        authority: ah.authority,
+        auction_house: *ahkey,
+        auction_house_fee_account: ah.auction_house_fee_account,
+        buyer_trade_state: bts,
+        token_program: spl_token::id(),
+        system_program: solana_program::system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+    let account_metas = accounts.to_account_metas(None);
+
+    let buy_ix = mpl_auction_house::instruction::CollectionBuy {
+        trade_state_bump: bts_bump,
+        escrow_payment_bump: escrow_bump,
+        buyer_price: sale_price,
+        token_size: 1,
+        creator,
+    };
+    let data = buy_ix.data();
+
+    let instruction = Instruction {
+        program_id: mpl_auction_house::id(),
+        data,
+        accounts: account_metas,
+    };
+
+    let (bid_receipt, bid_receipt_bump) = find_bid_receipt_address(&bts);
+    let print_receipt_accounts = mpl_auction_house::accounts::PrintBidReceipt {
+        receipt: bid_receipt,
+        bookkeeper: buyer.pubkey(),
+        system_program: solana_program::system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let account_metas = print_receipt_accounts.to_account_metas(None);
+
+    let print_bid_receipt_ix = mpl_auction_house::instruction::PrintBidReceipt {
+        receipt_bump: bid_receipt_bump,
+    };
+    let data = print_bid_receipt_ix.data();
+
+    let print_bid_receipt_instruction = Instruction {
+        program_id: mpl_auction_house::id(),
+        data,
+        accounts: account_metas,
+    };
+
+    (
+        (accounts, print_receipt_accounts),
+        Transaction::new_signed_with_payer(
+            &[instruction, print_bid_receipt_instruction],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn cancel_collection_buy(
+    context: &mut ProgramTestContext,
+    ahkey: &Pubkey,
+    ah: &AuctionHouse,
+    collection_mint: &Pubkey,
+    buyer: &Keypair,
+    buyer_trade_state: &Pubkey,
+    sale_price: u64,
+) -> (
+    mpl_auction_house::accounts::CancelCollectionBuy,
+    Transaction,
+) {
+    let accounts = mpl_auction_house::accounts::CancelCollectionBuy {
+        wallet: buyer.pubkey(),
+        collection_mint: *collection_mint,
+        authority: ah.authority,
+        auction_house: *ahkey,
+        auction_house_fee_account: ah.auction_house_fee_account,
+        trade_state: *buyer_trade_state,
+    };
+
+    let instruction = Instruction {
+        program_id: mpl_auction_house::id(),
+        data: mpl_auction_house::instruction::CancelCollectionBuy {
+            buyer_price: sale_price,
+            token_size: 1,
+        }
+        .data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
 pub fn execute_sale(
     context: &mut ProgramTestContext,
     ahkey: &Pubkey,
@@ -654,6 +831,115 @@ pub fn execute_sale(
     ((execute_sale_accounts, print_purchase_receipt_accounts), tx)
 }
 
+pub fn execute_collection_sale(
+    context: &mut ProgramTestContext,
+    ahkey: &Pubkey,
+    ah: &AuctionHouse,
+    authority: &Keypair,
+    test_metadata: &Metadata,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    token_account: &Pubkey,
+    seller_trade_state: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    token_size: u64,
+    buyer_price: u64,
+    remaining_accounts: Vec<AccountMeta>,
+) -> (
+    (
+        mpl_auction_house::accounts::ExecuteSale,
+        mpl_auction_house::accounts::PrintPurchaseReceipt,
+    ),
+    Transaction,
+) {
+    let program_id = mpl_auction_house::id();
+    let buyer_token_account = get_associated_token_address(buyer, &test_metadata.mint.pubkey());
+
+    let (program_as_signer, pas_bump) = find_program_as_signer_address();
+
+    let (free_trade_state, free_sts_bump) = find_trade_state_address(
+        seller,
+        ahkey,
+        token_account,
+        &ah.treasury_mint,
+        &test_metadata.mint.pubkey(),
+        0,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_bump) = find_escrow_payment_address(ahkey, buyer);
+    let (purchase_receipt, purchase_receipt_bump) =
+        find_purchase_receipt_address(seller_trade_state, buyer_trade_state);
+    let (listing_receipt, _listing_receipt_bump) = find_listing_receipt_address(seller_trade_state);
+    let (bid_receipt, _public_bid_receipt_bump) = find_bid_receipt_address(buyer_trade_state);
+    let execute_sale_accounts = mpl_auction_house::accounts::ExecuteSale {
+        buyer: *buyer,
+        seller: *seller,
+        auction_house: *ahkey,
+        token_account: *token_account,
+        token_mint: test_metadata.mint.pubkey(),
+        treasury_mint: ah.treasury_mint,
+        metadata: test_metadata.pubkey,
+        authority: ah.authority,
+        seller_trade_state: *seller_trade_state,
+        buyer_trade_state: *buyer_trade_state,
+        free_trade_state,
+        seller_payment_receipt_account: *seller,
+        buyer_receipt_token_account: buyer_token_account,
+        escrow_payment_account,
+        auction_house_fee_account: ah.auction_house_fee_account,
+        auction_house_treasury: ah.auction_house_treasury,
+        program_as_signer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        ata_program: spl_associated_token_account::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut execute_sale_account_metas = execute_sale_accounts.to_account_metas(None);
+    execute_sale_account_metas.extend(remaining_accounts);
+
+    let execute_sale_instruction = Instruction {
+        program_id,
+        data: mpl_auction_house::instruction::ExecuteCollectionSale {
+            escrow_payment_bump: escrow_bump,
+            free_trade_state_bump: free_sts_bump,
+            program_as_signer_bump: pas_bump,
+            token_size,
+            buyer_price,
+        }
+        .data(),
+        accounts: execute_sale_account_metas,
+    };
+
+    let print_purchase_receipt_accounts = mpl_auction_house::accounts::PrintPurchaseReceipt {
+        purchase_receipt,
+        listing_receipt,
+        bid_receipt,
+        bookkeeper: authority.pubkey(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let print_purchase_receipt_instruction = Instruction {
+        program_id,
+        data: mpl_auction_house::instruction::PrintPurchaseReceipt {
+            purchase_receipt_bump,
+        }
+        .data(),
+        accounts: print_purchase_receipt_accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_instruction, print_purchase_receipt_instruction],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    ((execute_sale_accounts, print_purchase_receipt_accounts), tx)
+}
+
 pub fn auctioneer_execute_sale(
     context: &mut ProgramTestContext,
     ahkey: &Pubkey,
@@ -921,6 +1207,32 @@ pub fn sell(
         mpl_auction_house::accounts::PrintListingReceipt,
     ),
     Transaction,
+) {
+    sell_with_expiry(
+        context,
+        ahkey,
+        ah,
+        test_metadata,
+        sale_price,
+        token_size,
+        None,
+    )
+}
+
+pub fn sell_with_expiry(
+    context: &mut ProgramTestContext,
+    ahkey: &Pubkey,
+    ah: &AuctionHouse,
+    test_metadata: &Metadata,
+    sale_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (
+    (
+        mpl_auction_house::accounts::Sell,
+        mpl_auction_house::accounts::PrintListingReceipt,
+    ),
+    Transaction,
 ) {
     let program_id = mpl_auction_house::id();
     let token =
@@ -963,14 +1275,25 @@ pub fn sell(
     };
     let account_metas = accounts.to_account_metas(None);
 
-    let data = mpl_auction_house::instruction::Sell {
-        trade_state_bump: sts_bump,
-        free_trade_state_bump: free_sts_bump,
-        program_as_signer_bump: pas_bump,
-        token_size,
-        buyer_price: sale_price,
-    }
-    .data();
+    let data = match expiry {
+        Some(expiry) => mpl_auction_house::instruction::SellWithExpiry {
+            trade_state_bump: sts_bump,
+            free_trade_state_bump: free_sts_bump,
+            program_as_signer_bump: pas_bump,
+            token_size,
+            buyer_price: sale_price,
+            expiry,
+        }
+        .data(),
+        None => mpl_auction_house::instruction::Sell {
+            trade_state_bump: sts_bump,
+            free_trade_state_bump: free_sts_bump,
+            program_as_signer_bump: pas_bump,
+            token_size,
+            buyer_price: sale_price,
+        }
+        .data(),
+    };
 
     let instruction = Instruction {
         program_id,
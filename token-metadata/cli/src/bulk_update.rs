@@ -0,0 +1,760 @@
+use {
+    crate::utils::{flag_arg, keypair_or_payer, try_send, value_of},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        instruction::update_metadata_accounts_v2,
+        pda::{find_collection_validation_profile_account, find_metadata_account},
+        state::{Creator, DataV2, Metadata},
+        utils::unpuffed_string,
+    },
+    serde::Deserialize,
+    solana_client::rpc_client::RpcClient,
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_program,
+        transaction::Transaction,
+    },
+    std::{
+        collections::HashSet,
+        fs::{self, OpenOptions},
+        io::Write,
+        path::Path,
+        str::FromStr,
+    },
+};
+
+/// Maximum number of accounts fetched per `getMultipleAccounts` request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Default number of updates packed in one transaction, when they fit.
+const DEFAULT_BATCH_SIZE: usize = 8;
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![SubCommand::with_name("bulk_update")
+        .about("Update the metadata of many mints from a CSV or JSON manifest")
+        .arg(
+            Arg::with_name("manifest")
+                .long("manifest")
+                .value_name("FILE")
+                .required(true)
+                .takes_value(true)
+                .help(
+                    "Manifest of mint to name, uri, creators and primary_sale_happened changes. \
+                     CSV files need a header row, creators are written as address:share \
+                     pairs separated by semicolons and empty cells are left unchanged.",
+                ),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .possible_values(&["csv", "json"])
+                .help("Format of the manifest, defaults to its file extension"),
+        )
+        .arg(
+            Arg::with_name("checkpoint")
+                .long("checkpoint")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "File recording the mints already processed, so a failed run can be resumed. \
+                     Defaults to the manifest path with a .checkpoint extension",
+                ),
+        )
+        .arg(
+            Arg::with_name("batch_size")
+                .long("batch_size")
+                .value_name("COUNT")
+                .takes_value(true)
+                .help("Maximum number of updates packed in one transaction [default: 8]"),
+        )
+        .arg(flag_arg(
+            "dry_run",
+            "Only diff the manifest against the chain, without sending transactions",
+        ))]
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ManifestEntry {
+    mint: Pubkey,
+    name: Option<String>,
+    uri: Option<String>,
+    creators: Option<Vec<(Pubkey, u8)>>,
+    primary_sale_happened: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct JsonEntry {
+    mint: String,
+    name: Option<String>,
+    uri: Option<String>,
+    creators: Option<Vec<JsonCreator>>,
+    primary_sale_happened: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct JsonCreator {
+    address: String,
+    share: u8,
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value.trim()).map_err(|_| format!("invalid pubkey {}", value))
+}
+
+fn parse_json_manifest(contents: &str) -> Result<Vec<ManifestEntry>, String> {
+    let entries: Vec<JsonEntry> = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let creators = match entry.creators {
+                Some(creators) => Some(
+                    creators
+                        .iter()
+                        .map(|c| Ok((parse_pubkey(&c.address)?, c.share)))
+                        .collect::<Result<Vec<_>, String>>()?,
+                ),
+                None => None,
+            };
+            Ok(ManifestEntry {
+                mint: parse_pubkey(&entry.mint)?,
+                name: entry.name,
+                uri: entry.uri,
+                creators,
+                primary_sale_happened: entry.primary_sale_happened,
+            })
+        })
+        .collect()
+}
+
+/// Splits a CSV line into its cells, honouring double quoted cells and `""` escapes.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+fn parse_csv_creators(value: &str) -> Result<Vec<(Pubkey, u8)>, String> {
+    value
+        .split(';')
+        .filter(|creator| !creator.trim().is_empty())
+        .map(|creator| {
+            let (address, share) = creator
+                .split_once(':')
+                .ok_or_else(|| format!("creator {} is not an address:share pair", creator))?;
+            let share = share
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("invalid share {}", share))?;
+            Ok((parse_pubkey(address)?, share))
+        })
+        .collect()
+}
+
+fn parse_csv_manifest(contents: &str) -> Result<Vec<ManifestEntry>, String> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let header: Vec<String> = match lines.next() {
+        Some((_, line)) => split_csv_line(line)
+            .iter()
+            .map(|cell| cell.trim().to_owned())
+            .collect(),
+        None => return Ok(vec![]),
+    };
+    for column in &header {
+        if !["mint", "name", "uri", "creators", "primary_sale_happened"].contains(&column.as_str())
+        {
+            return Err(format!("unknown column {}", column));
+        }
+    }
+    if !header.iter().any(|column| column == "mint") {
+        return Err("missing mint column".to_owned());
+    }
+
+    lines
+        .map(|(i, line)| {
+            let cells = split_csv_line(line);
+            if cells.len() != header.len() {
+                return Err(format!(
+                    "line {} has {} cells, expected {}",
+                    i + 1,
+                    cells.len(),
+                    header.len()
+                ));
+            }
+            let cell = |column: &str| {
+                header
+                    .iter()
+                    .position(|c| c == column)
+                    .map(|position| cells[position].clone())
+                    .filter(|value| !value.is_empty())
+            };
+            let primary_sale_happened = match cell("primary_sale_happened") {
+                Some(value) => Some(
+                    value
+                        .trim()
+                        .parse::<bool>()
+                        .map_err(|_| format!("line {}: invalid bool {}", i + 1, value))?,
+                ),
+                None => None,
+            };
+            Ok(ManifestEntry {
+                mint: parse_pubkey(&cell("mint").unwrap_or_default())?,
+                name: cell("name"),
+                uri: cell("uri"),
+                creators: cell("creators")
+                    .map(|value| parse_csv_creators(&value))
+                    .transpose()?,
+                primary_sale_happened,
+            })
+        })
+        .collect()
+}
+
+/// Builds the update bringing `metadata` in line with `entry`, or `None` if it already is.
+/// `payer` funds the growth of metadata accounts resized to fit their content.
+fn diff(
+    entry: &ManifestEntry,
+    metadata_key: Pubkey,
+    metadata: &Metadata,
+    update_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Option<Instruction>, String> {
+    if metadata.update_authority != *update_authority {
+        return Err(format!("update authority is {}", metadata.update_authority));
+    }
+
+    let name = unpuffed_string(&metadata.data.name);
+    let uri = unpuffed_string(&metadata.data.uri);
+    let on_chain_creators: Vec<(Pubkey, u8)> = metadata
+        .data
+        .creators
+        .iter()
+        .flatten()
+        .map(|creator| (creator.address, creator.share))
+        .collect();
+
+    let new_name = entry.name.clone().unwrap_or_else(|| name.clone());
+    let new_uri = entry.uri.clone().unwrap_or_else(|| uri.clone());
+    let new_creators = entry
+        .creators
+        .clone()
+        .unwrap_or_else(|| on_chain_creators.clone());
+    let renamed = new_name != name || new_uri != uri;
+    let data_changed = renamed || new_creators != on_chain_creators;
+
+    let primary_sale_happened = match entry.primary_sale_happened {
+        Some(val) if val != metadata.primary_sale_happened => {
+            if !val {
+                return Err("primary_sale_happened can only be flipped to true".to_owned());
+            }
+            Some(val)
+        }
+        _ => None,
+    };
+
+    if !data_changed && primary_sale_happened.is_none() {
+        return Ok(None);
+    }
+    if data_changed && !metadata.is_mutable {
+        return Err("metadata is immutable".to_owned());
+    }
+
+    let data = if data_changed {
+        // Creators that stay in the list keep their verification.
+        let creators: Vec<Creator> = new_creators
+            .iter()
+            .map(|(address, share)| Creator {
+                address: *address,
+                verified: metadata
+                    .data
+                    .creators
+                    .iter()
+                    .flatten()
+                    .any(|creator| creator.address == *address && creator.verified),
+                share: *share,
+            })
+            .collect();
+
+        Some(DataV2 {
+            name: new_name,
+            symbol: unpuffed_string(&metadata.data.symbol),
+            uri: new_uri,
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+            creators: if creators.is_empty() {
+                None
+            } else {
+                Some(creators)
+            },
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        })
+    } else {
+        None
+    };
+
+    let mut instruction = update_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        metadata_key,
+        *update_authority,
+        None,
+        data,
+        primary_sale_happened,
+        None,
+    );
+    instruction.accounts.push(AccountMeta::new(*payer, true));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(system_program::id(), false));
+    // Renaming a verified collection item is checked against the collection validation profile.
+    if let Some(collection) = metadata.collection.as_ref().filter(|c| c.verified) {
+        if renamed {
+            instruction.accounts.push(AccountMeta::new_readonly(
+                find_collection_validation_profile_account(&collection.key).0,
+                false,
+            ));
+        }
+    }
+
+    Ok(Some(instruction))
+}
+
+fn fits_in_transaction(instructions: &[Instruction], payer: &Pubkey) -> bool {
+    let transaction = Transaction::new_with_payer(instructions, Some(payer));
+    bincode::serialized_size(&transaction).unwrap() as usize <= PACKET_DATA_SIZE
+}
+
+enum Outcome {
+    Updated,
+    Unchanged,
+    Checkpointed,
+    Pending,
+    Failed(String),
+}
+
+struct BulkUpdate<'a> {
+    client: &'a RpcClient,
+    payer: &'a Keypair,
+    update_authority: &'a Keypair,
+    checkpoint: Option<fs::File>,
+    outcomes: Vec<(Pubkey, Outcome)>,
+}
+
+impl<'a> BulkUpdate<'a> {
+    fn record(&mut self, mint: Pubkey, outcome: Outcome) {
+        if matches!(outcome, Outcome::Updated | Outcome::Unchanged) {
+            if let Some(checkpoint) = self.checkpoint.as_mut() {
+                writeln!(checkpoint, "{}", mint).unwrap();
+                checkpoint.flush().unwrap();
+            }
+        }
+        self.outcomes.push((mint, outcome));
+    }
+
+    /// Sends the batch in one transaction. If it fails, each update is retried on its own so
+    /// that a single bad mint doesn't fail the rest of the batch.
+    fn send_batch(&mut self, batch: Vec<(Pubkey, Instruction)>) {
+        if batch.is_empty() {
+            return;
+        }
+        let instructions: Vec<Instruction> = batch.iter().map(|(_, ix)| ix.clone()).collect();
+
+        match try_send(
+            self.client,
+            self.payer,
+            &instructions,
+            &[self.update_authority],
+        ) {
+            Ok(_) => {
+                for (mint, _) in batch {
+                    self.record(mint, Outcome::Updated);
+                }
+            }
+            Err(err) if batch.len() == 1 => {
+                self.record(batch[0].0, Outcome::Failed(err.to_string()));
+            }
+            Err(_) => {
+                for update in batch {
+                    self.send_batch(vec![update]);
+                }
+            }
+        }
+    }
+}
+
+fn read_checkpoint(path: &Path) -> HashSet<Pubkey> {
+    match fs::read_to_string(path) {
+        Ok(contents) => contents
+            .lines()
+            .filter_map(|line| Pubkey::from_str(line.trim()).ok())
+            .collect(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+fn print_report(outcomes: &[(Pubkey, Outcome)]) {
+    println!("{:<44}  {:<12}  DETAIL", "MINT", "RESULT");
+    for (mint, outcome) in outcomes {
+        let (result, detail) = match outcome {
+            Outcome::Updated => ("updated", ""),
+            Outcome::Unchanged => ("unchanged", ""),
+            Outcome::Checkpointed => ("skipped", "already in checkpoint"),
+            Outcome::Pending => ("pending", "dry run"),
+            Outcome::Failed(err) => ("failed", err.as_str()),
+        };
+        println!("{:<44}  {:<12}  {}", mint.to_string(), result, detail);
+    }
+
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+    println!(
+        "\n{} updated, {} unchanged, {} skipped, {} pending, {} failed",
+        count(|o| matches!(o, Outcome::Updated)),
+        count(|o| matches!(o, Outcome::Unchanged)),
+        count(|o| matches!(o, Outcome::Checkpointed)),
+        count(|o| matches!(o, Outcome::Pending)),
+        count(|o| matches!(o, Outcome::Failed(_))),
+    );
+}
+
+pub fn bulk_update(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let update_authority = keypair_or_payer(app_matches, "update_authority");
+    let manifest_path = Path::new(app_matches.value_of("manifest").unwrap());
+    let batch_size = value_of::<usize>(app_matches, "batch_size").unwrap_or(DEFAULT_BATCH_SIZE);
+    let dry_run = app_matches.is_present("dry_run");
+
+    let format = app_matches
+        .value_of("format")
+        .map(|format| format.to_owned())
+        .or_else(|| {
+            manifest_path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+        });
+    let contents = fs::read_to_string(manifest_path).unwrap();
+    let entries = match format.as_deref() {
+        Some("csv") => parse_csv_manifest(&contents),
+        Some("json") => parse_json_manifest(&contents),
+        _ => Err("pass --format to tell whether it is csv or json".to_owned()),
+    }
+    .unwrap_or_else(|err| panic!("Invalid manifest: {}", err));
+
+    let checkpoint_path = app_matches
+        .value_of("checkpoint")
+        .map(|path| path.into())
+        .unwrap_or_else(|| manifest_path.with_extension("checkpoint"));
+    let done = read_checkpoint(&checkpoint_path);
+    let checkpoint = if dry_run {
+        None
+    } else {
+        Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&checkpoint_path)
+                .unwrap(),
+        )
+    };
+
+    let mut run = BulkUpdate {
+        client: &client,
+        payer: &payer,
+        update_authority: &update_authority,
+        checkpoint,
+        outcomes: vec![],
+    };
+    let mut batch: Vec<(Pubkey, Instruction)> = vec![];
+
+    for chunk in entries.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let metadata_keys: Vec<Pubkey> = chunk
+            .iter()
+            .map(|entry| find_metadata_account(&entry.mint).0)
+            .collect();
+        let accounts = client.get_multiple_accounts(&metadata_keys).unwrap();
+
+        for ((entry, metadata_key), account) in chunk.iter().zip(metadata_keys).zip(accounts) {
+            if done.contains(&entry.mint) {
+                run.record(entry.mint, Outcome::Checkpointed);
+                continue;
+            }
+            let metadata: Metadata = match account
+                .as_ref()
+                .and_then(|account| try_from_slice_unchecked(&account.data).ok())
+            {
+                Some(metadata) => metadata,
+                None => {
+                    run.record(
+                        entry.mint,
+                        Outcome::Failed("metadata account not found".to_owned()),
+                    );
+                    continue;
+                }
+            };
+
+            match diff(
+                entry,
+                metadata_key,
+                &metadata,
+                &update_authority.pubkey(),
+                &payer.pubkey(),
+            ) {
+                Ok(None) => run.record(entry.mint, Outcome::Unchanged),
+                Ok(Some(_)) if dry_run => run.record(entry.mint, Outcome::Pending),
+                Ok(Some(instruction)) => {
+                    batch.push((entry.mint, instruction));
+                    let instructions: Vec<Instruction> =
+                        batch.iter().map(|(_, ix)| ix.clone()).collect();
+                    if !fits_in_transaction(&instructions, &payer.pubkey()) {
+                        let last = batch.pop().unwrap();
+                        run.send_batch(std::mem::take(&mut batch));
+                        batch.push(last);
+                    }
+                    if batch.len() >= batch_size {
+                        run.send_batch(std::mem::take(&mut batch));
+                    }
+                }
+                Err(err) => run.record(entry.mint, Outcome::Failed(err)),
+            }
+        }
+    }
+    run.send_batch(batch);
+
+    print_report(&run.outcomes);
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        borsh::BorshDeserialize,
+        mpl_token_metadata::{
+            instruction::{MetadataInstruction, UpdateMetadataAccountArgsV2},
+            state::Data,
+            utils::puff_out_data_fields,
+        },
+    };
+
+    fn metadata(update_authority: &Pubkey, creators: Option<Vec<Creator>>) -> Metadata {
+        let mut metadata = Metadata {
+            update_authority: *update_authority,
+            mint: Pubkey::new_unique(),
+            data: Data {
+                name: "Item".to_owned(),
+                symbol: "ITM".to_owned(),
+                uri: "https://example.com/item.json".to_owned(),
+                seller_fee_basis_points: 500,
+                creators,
+            },
+            is_mutable: true,
+            ..Metadata::default()
+        };
+        // Strings are puffed out on chain.
+        puff_out_data_fields(&mut metadata);
+        metadata
+    }
+
+    fn entry(mint: &Pubkey) -> ManifestEntry {
+        ManifestEntry {
+            mint: *mint,
+            name: None,
+            uri: None,
+            creators: None,
+            primary_sale_happened: None,
+        }
+    }
+
+    fn update_args(instruction: &Instruction) -> UpdateMetadataAccountArgsV2 {
+        match MetadataInstruction::try_from_slice(&instruction.data).unwrap() {
+            MetadataInstruction::UpdateMetadataAccountV2(args) => args,
+            _ => panic!("not an update instruction"),
+        }
+    }
+
+    #[test]
+    fn csv_cells_may_be_quoted() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let manifest = format!(
+            "mint,name,creators,primary_sale_happened\n{},\"Item, \"\"one\"\"\",{}:100,true\n",
+            mint, creator
+        );
+
+        let entries = parse_csv_manifest(&manifest).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![ManifestEntry {
+                mint,
+                name: Some("Item, \"one\"".to_owned()),
+                uri: None,
+                creators: Some(vec![(creator, 100)]),
+                primary_sale_happened: Some(true),
+            }]
+        );
+    }
+
+    #[test]
+    fn reject_malformed_csv_creators() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        for creators in [
+            creator.to_string(),
+            format!("{}:lots", creator),
+            "not-a-pubkey:100".to_owned(),
+        ] {
+            let manifest = format!("mint,creators\n{},{}\n", mint, creators);
+            assert!(parse_csv_manifest(&manifest).is_err(), "{}", creators);
+        }
+
+        let manifest = format!("mint,name\n{},Item,extra\n", mint);
+        assert_eq!(
+            parse_csv_manifest(&manifest).unwrap_err(),
+            "line 2 has 3 cells, expected 2"
+        );
+    }
+
+    #[test]
+    fn skip_entries_matching_the_chain() {
+        let update_authority = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let metadata = metadata(
+            &update_authority,
+            Some(vec![Creator {
+                address: creator,
+                verified: true,
+                share: 100,
+            }]),
+        );
+
+        let entry = ManifestEntry {
+            name: Some("Item".to_owned()),
+            uri: Some("https://example.com/item.json".to_owned()),
+            creators: Some(vec![(creator, 100)]),
+            primary_sale_happened: Some(false),
+            ..entry(&metadata.mint)
+        };
+
+        let update = diff(
+            &entry,
+            Pubkey::new_unique(),
+            &metadata,
+            &update_authority,
+            &Pubkey::new_unique(),
+        )
+        .unwrap();
+        assert!(update.is_none());
+    }
+
+    #[test]
+    fn reject_unsetting_primary_sale_happened() {
+        let update_authority = Pubkey::new_unique();
+        let mut metadata = metadata(&update_authority, None);
+        metadata.primary_sale_happened = true;
+
+        let entry = ManifestEntry {
+            primary_sale_happened: Some(false),
+            ..entry(&metadata.mint)
+        };
+
+        let err = diff(
+            &entry,
+            Pubkey::new_unique(),
+            &metadata,
+            &update_authority,
+            &Pubkey::new_unique(),
+        )
+        .unwrap_err();
+        assert_eq!(err, "primary_sale_happened can only be flipped to true");
+    }
+
+    #[test]
+    fn keep_verification_of_remaining_creators() {
+        let update_authority = Pubkey::new_unique();
+        let kept = Pubkey::new_unique();
+        let added = Pubkey::new_unique();
+        let metadata = metadata(
+            &update_authority,
+            Some(vec![
+                Creator {
+                    address: kept,
+                    verified: true,
+                    share: 50,
+                },
+                Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 50,
+                },
+            ]),
+        );
+
+        let entry = ManifestEntry {
+            creators: Some(vec![(kept, 60), (added, 40)]),
+            ..entry(&metadata.mint)
+        };
+
+        let update = diff(
+            &entry,
+            Pubkey::new_unique(),
+            &metadata,
+            &update_authority,
+            &Pubkey::new_unique(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let data = update_args(&update).data.unwrap();
+        assert_eq!(data.name, "Item");
+        assert_eq!(
+            data.creators,
+            Some(vec![
+                Creator {
+                    address: kept,
+                    verified: true,
+                    share: 60,
+                },
+                Creator {
+                    address: added,
+                    verified: false,
+                    share: 40,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn read_mints_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("{}.checkpoint", Pubkey::new_unique()));
+        assert!(read_checkpoint(&path).is_empty());
+
+        let done = Pubkey::new_unique();
+        fs::write(&path, format!("{}\nnot-a-mint\n\n", done)).unwrap();
+        let checkpoint = read_checkpoint(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(checkpoint, HashSet::from([done]));
+    }
+}
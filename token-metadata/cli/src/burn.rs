@@ -0,0 +1,83 @@
+use {
+    crate::utils::{
+        find_largest_token_account, find_token_account, get_metadata, pubkey_arg, send,
+    },
+    clap::{App, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        client::BurnNft,
+        instruction::burn_edition_nft,
+        pda::{find_edition_marker_account, find_master_edition_account},
+        state::Edition,
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        SubCommand::with_name("burn_nft")
+            .about("Burn an NFT you own along with its metadata and master edition")
+            .arg(pubkey_arg("mint", "Mint of the NFT")),
+        SubCommand::with_name("burn_edition")
+            .about("Burn a print edition you own along with its metadata and edition")
+            .arg(pubkey_arg("mint", "Mint of the print edition"))
+            .arg(pubkey_arg(
+                "master_mint",
+                "Mint of the master edition it was printed from",
+            )),
+    ]
+}
+
+pub fn burn_nft(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let (_, metadata) = get_metadata(&client, &mint);
+
+    // Verified items decrement the size of their collection.
+    let collection_mint = metadata
+        .collection
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key);
+
+    let instruction = BurnNft {
+        mint,
+        owner: payer.pubkey(),
+        token_account: find_token_account(&client, &payer.pubkey(), &mint),
+        collection_mint,
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!("Burned {} with signature {}", mint, signature);
+}
+
+pub fn burn_edition(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let master_mint = pubkey_of(app_matches, "master_mint").unwrap();
+    let (metadata_key, _) = get_metadata(&client, &mint);
+
+    let (print_edition_key, _) = find_master_edition_account(&mint);
+    let print_edition_account = client.get_account(&print_edition_key).unwrap();
+    let print_edition: Edition = try_from_slice_unchecked(&print_edition_account.data).unwrap();
+
+    let instruction = burn_edition_nft(
+        mpl_token_metadata::id(),
+        metadata_key,
+        payer.pubkey(),
+        mint,
+        master_mint,
+        find_token_account(&client, &payer.pubkey(), &mint),
+        find_largest_token_account(&client, &master_mint),
+        find_master_edition_account(&master_mint).0,
+        print_edition_key,
+        find_edition_marker_account(&master_mint, print_edition.edition).0,
+        spl_token::id(),
+    );
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Burned edition {} of {} with signature {}",
+        print_edition.edition, master_mint, signature
+    );
+}
@@ -0,0 +1,209 @@
+use {
+    crate::utils::{
+        amount_arg, flag_arg, get_metadata, keypair_or_payer, pubkey_arg, send, signer_arg,
+        value_of,
+    },
+    clap::{App, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        client::{
+            ApproveCollectionAuthority, RevokeCollectionAuthority, UnverifyCollection,
+            VerifyCollection,
+        },
+        instruction::set_collection_size,
+        pda::find_collection_authority_account,
+        state::COLLECTION_PERMISSION_ALL,
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        SubCommand::with_name("verify_collection_item")
+            .about("Verify an item as a member of its collection, sized or not")
+            .arg(pubkey_arg("mint", "Mint of the item"))
+            .arg(signer_arg(
+                "collection_authority",
+                "Filepath or URL to a keypair of the collection authority, defaults to you",
+            ))
+            .arg(flag_arg(
+                "delegated",
+                "The collection authority is delegated through a collection authority record",
+            )),
+        SubCommand::with_name("unverify_collection_item")
+            .about("Unverify an item as a member of its collection, sized or not")
+            .arg(pubkey_arg("mint", "Mint of the item"))
+            .arg(signer_arg(
+                "collection_authority",
+                "Filepath or URL to a keypair of the collection authority, defaults to you",
+            ))
+            .arg(flag_arg(
+                "delegated",
+                "The collection authority is delegated through a collection authority record",
+            )),
+        SubCommand::with_name("set_collection_size")
+            .about("Set the size of a collection parent, turning it into a sized collection")
+            .arg(pubkey_arg("mint", "Mint of the collection"))
+            .arg(amount_arg(
+                "size",
+                "Number of verified items in the collection",
+            ))
+            .arg(signer_arg(
+                "collection_authority",
+                "Filepath or URL to a keypair of the collection authority, defaults to you",
+            ))
+            .arg(flag_arg(
+                "delegated",
+                "The collection authority is delegated through a collection authority record",
+            )),
+        SubCommand::with_name("approve_collection_authority")
+            .about("Delegate the verification of items of a collection")
+            .arg(pubkey_arg("mint", "Mint of the collection"))
+            .arg(pubkey_arg("authority", "New collection authority"))
+            .arg(
+                amount_arg(
+                    "permissions",
+                    "Bitmask of the operations the authority may perform, defaults to all",
+                )
+                .required(false),
+            )
+            .arg(
+                amount_arg(
+                    "expiry",
+                    "Unix timestamp after which the authority can't be used",
+                )
+                .required(false),
+            ),
+        SubCommand::with_name("revoke_collection_authority")
+            .about("Revoke a delegated collection authority")
+            .arg(pubkey_arg("mint", "Mint of the collection"))
+            .arg(pubkey_arg("authority", "Collection authority to revoke")),
+    ]
+}
+
+fn collection_authority_args(app_matches: &ArgMatches) -> (Keypair, bool) {
+    (
+        keypair_or_payer(app_matches, "collection_authority"),
+        app_matches.is_present("delegated"),
+    )
+}
+
+pub fn verify_collection_item(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let (collection_authority, delegated) = collection_authority_args(app_matches);
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+
+    let (_, metadata) = get_metadata(&client, &mint);
+    let collection_mint = metadata.collection.expect("Item has no collection.").key;
+    let (_, collection_metadata) = get_metadata(&client, &collection_mint);
+
+    let instruction = VerifyCollection {
+        mint,
+        collection_mint,
+        collection_authority: collection_authority.pubkey(),
+        payer: payer.pubkey(),
+        sized: collection_metadata.collection_details.is_some(),
+        delegated,
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[&collection_authority]);
+
+    println!(
+        "Verified {} in collection {} with signature {}",
+        mint, collection_mint, signature
+    );
+}
+
+pub fn unverify_collection_item(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let (collection_authority, delegated) = collection_authority_args(app_matches);
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+
+    let (_, metadata) = get_metadata(&client, &mint);
+    let collection_mint = metadata.collection.expect("Item has no collection.").key;
+    let (_, collection_metadata) = get_metadata(&client, &collection_mint);
+
+    let instruction = UnverifyCollection {
+        mint,
+        collection_mint,
+        collection_authority: collection_authority.pubkey(),
+        payer: payer.pubkey(),
+        sized: collection_metadata.collection_details.is_some(),
+        delegated,
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[&collection_authority]);
+
+    println!(
+        "Unverified {} in collection {} with signature {}",
+        mint, collection_mint, signature
+    );
+}
+
+pub fn set_size(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let (collection_authority, delegated) = collection_authority_args(app_matches);
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let size = value_of::<u64>(app_matches, "size").unwrap();
+
+    let (metadata_key, _) = get_metadata(&client, &mint);
+    let collection_authority_record = if delegated {
+        Some(find_collection_authority_account(&mint, &collection_authority.pubkey()).0)
+    } else {
+        None
+    };
+
+    let instruction = set_collection_size(
+        mpl_token_metadata::id(),
+        metadata_key,
+        collection_authority.pubkey(),
+        mint,
+        collection_authority_record,
+        size,
+    );
+    let signature = send(&client, &payer, &[instruction], &[&collection_authority]);
+
+    println!(
+        "Set size of collection {} to {} with signature {}",
+        mint, size, signature
+    );
+}
+
+pub fn approve_collection_authority(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let update_authority = keypair_or_payer(app_matches, "update_authority");
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let authority = pubkey_of(app_matches, "authority").unwrap();
+
+    let instruction = ApproveCollectionAuthority {
+        mint,
+        new_collection_authority: authority,
+        update_authority: update_authority.pubkey(),
+        payer: payer.pubkey(),
+        permissions: value_of(app_matches, "permissions").unwrap_or(COLLECTION_PERMISSION_ALL),
+        expiry: value_of(app_matches, "expiry"),
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[&update_authority]);
+
+    println!(
+        "Approved {} as collection authority of {} with signature {}",
+        authority, mint, signature
+    );
+}
+
+pub fn revoke_collection_authority(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let update_authority = keypair_or_payer(app_matches, "update_authority");
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let authority = pubkey_of(app_matches, "authority").unwrap();
+
+    let instruction = RevokeCollectionAuthority {
+        mint,
+        delegate_authority: authority,
+        revoke_authority: update_authority.pubkey(),
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[&update_authority]);
+
+    println!(
+        "Revoked {} as collection authority of {} with signature {}",
+        authority, mint, signature
+    );
+}
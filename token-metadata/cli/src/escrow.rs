@@ -0,0 +1,141 @@
+use {
+    crate::utils::{
+        amount_arg, find_largest_token_account, find_token_account, flag_arg, pubkey_arg, send,
+        value_of,
+    },
+    clap::{App, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        escrow::{
+            close_escrow_account, create_escrow_account, pda::find_escrow_account,
+            transfer_out_of_escrow,
+        },
+        pda::{find_master_edition_account, find_metadata_account},
+        state::EscrowAuthority,
+    },
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const CREATOR_HELP: &str = "Use the escrow of which you are the creator authority, instead of the escrow of the token owner";
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        SubCommand::with_name("create_escrow")
+            .about("Create the token owned escrow of an NFT")
+            .arg(pubkey_arg("mint", "Mint of the NFT"))
+            .arg(flag_arg("creator", CREATOR_HELP)),
+        SubCommand::with_name("close_escrow")
+            .about("Close the token owned escrow of an NFT you own")
+            .arg(pubkey_arg("mint", "Mint of the NFT")),
+        SubCommand::with_name("transfer_out_of_escrow")
+            .about("Transfer tokens held by the escrow of an NFT to your wallet")
+            .arg(pubkey_arg("mint", "Mint of the NFT"))
+            .arg(pubkey_arg(
+                "attribute_mint",
+                "Mint of the tokens to transfer",
+            ))
+            .arg(amount_arg("amount", "Amount of tokens to transfer"))
+            .arg(flag_arg("creator", CREATOR_HELP)),
+    ]
+}
+
+fn escrow_authority(app_matches: &ArgMatches, payer: &Keypair) -> EscrowAuthority {
+    if app_matches.is_present("creator") {
+        EscrowAuthority::Creator(payer.pubkey())
+    } else {
+        EscrowAuthority::TokenOwner
+    }
+}
+
+pub fn create_escrow(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let authority = escrow_authority(app_matches, &payer);
+    let (escrow, _) = find_escrow_account(&mint, &authority);
+
+    // The token account can belong to anyone when a creator creates the escrow.
+    let token_account = match authority {
+        EscrowAuthority::TokenOwner => find_token_account(&client, &payer.pubkey(), &mint),
+        EscrowAuthority::Creator(_) => find_largest_token_account(&client, &mint),
+    };
+
+    let instruction = create_escrow_account(
+        mpl_token_metadata::id(),
+        escrow,
+        find_metadata_account(&mint).0,
+        mint,
+        token_account,
+        find_master_edition_account(&mint).0,
+        payer.pubkey(),
+        match authority {
+            EscrowAuthority::TokenOwner => None,
+            EscrowAuthority::Creator(creator) => Some(creator),
+        },
+    );
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Created escrow {} of {} with signature {}",
+        escrow, mint, signature
+    );
+}
+
+pub fn close_escrow(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let (escrow, _) = find_escrow_account(&mint, &EscrowAuthority::TokenOwner);
+
+    let instruction = close_escrow_account(
+        mpl_token_metadata::id(),
+        escrow,
+        find_metadata_account(&mint).0,
+        mint,
+        find_master_edition_account(&mint).0,
+        payer.pubkey(),
+        find_token_account(&client, &payer.pubkey(), &mint),
+    );
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Closed escrow {} of {} with signature {}",
+        escrow, mint, signature
+    );
+}
+
+pub fn transfer_out(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let attribute_mint = pubkey_of(app_matches, "attribute_mint").unwrap();
+    let amount = value_of(app_matches, "amount").unwrap();
+    let authority = escrow_authority(app_matches, &payer);
+    let (escrow, _) = find_escrow_account(&mint, &authority);
+
+    let escrow_token_account = match authority {
+        EscrowAuthority::TokenOwner => find_token_account(&client, &payer.pubkey(), &mint),
+        EscrowAuthority::Creator(_) => find_largest_token_account(&client, &mint),
+    };
+
+    let instruction = transfer_out_of_escrow(
+        mpl_token_metadata::id(),
+        escrow,
+        find_metadata_account(&mint).0,
+        payer.pubkey(),
+        attribute_mint,
+        get_associated_token_address(&escrow, &attribute_mint),
+        get_associated_token_address(&payer.pubkey(), &attribute_mint),
+        mint,
+        escrow_token_account,
+        match authority {
+            EscrowAuthority::TokenOwner => None,
+            EscrowAuthority::Creator(creator) => Some(creator),
+        },
+        None,
+        vec![],
+        amount,
+    );
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Transferred {} of {} out of escrow {} with signature {}",
+        amount, attribute_mint, escrow, signature
+    );
+}
@@ -1,3 +1,12 @@
+mod bulk_update;
+mod burn;
+mod collection;
+mod escrow;
+mod output;
+mod snapshot;
+mod uses;
+mod utils;
+
 use solana_client::rpc_request::TokenAccountsFilter;
 use solana_sdk::account::ReadableAccount;
 
@@ -198,6 +207,18 @@ fn show(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
     let (master_edition_key, _) = Pubkey::find_program_address(master_edition_seeds, &program_key);
     let master_edition_account_res = client.get_account(&master_edition_key);
 
+    if app_matches.value_of("output") == Some("json") {
+        let edition = master_edition_account_res
+            .ok()
+            .and_then(|account| output::edition_json(&master_edition_key, &account.data));
+        let json = serde_json::json!({
+            "metadata": output::metadata_json(&master_metadata_key, &master_metadata),
+            "edition": edition,
+        });
+        println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        return;
+    }
+
     println!("Metadata key: {:?}", master_metadata_key);
     println!("Metadata: {:#?}", master_metadata);
     println!("Update authority: {:?}", update_authority);
@@ -738,6 +759,15 @@ fn main() {
                         .takes_value(true)
                         .help("Metadata mint"),
                 )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("FORMAT")
+                        .takes_value(true)
+                        .possible_values(&["text", "json"])
+                        .default_value("text")
+                        .help("Print the accounts as text or as JSON"),
+                )
         ).subcommand(
             SubCommand::with_name("show_reservation_list")
                 .about("Show Reservation List")
@@ -816,7 +846,14 @@ fn main() {
 
         ).subcommand(
                 SubCommand::with_name("puff_unpuffed_metadata")
-                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC.")).get_matches();
+                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC."))
+        .subcommands(collection::subcommands())
+        .subcommands(uses::subcommands())
+        .subcommands(burn::subcommands())
+        .subcommands(escrow::subcommands())
+        .subcommands(bulk_update::subcommands())
+        .subcommands(snapshot::subcommands())
+        .get_matches();
 
     let client = RpcClient::new(
         app_matches
@@ -871,6 +908,51 @@ fn main() {
         ("puff_unpuffed_metadata", Some(arg_matches)) => {
             puff_unpuffed_metadata(arg_matches, payer, client);
         }
+        ("verify_collection_item", Some(arg_matches)) => {
+            collection::verify_collection_item(arg_matches, payer, client);
+        }
+        ("unverify_collection_item", Some(arg_matches)) => {
+            collection::unverify_collection_item(arg_matches, payer, client);
+        }
+        ("set_collection_size", Some(arg_matches)) => {
+            collection::set_size(arg_matches, payer, client);
+        }
+        ("approve_collection_authority", Some(arg_matches)) => {
+            collection::approve_collection_authority(arg_matches, payer, client);
+        }
+        ("revoke_collection_authority", Some(arg_matches)) => {
+            collection::revoke_collection_authority(arg_matches, payer, client);
+        }
+        ("approve_use_authority", Some(arg_matches)) => {
+            uses::approve_use_authority(arg_matches, payer, client);
+        }
+        ("revoke_use_authority", Some(arg_matches)) => {
+            uses::revoke_use_authority(arg_matches, payer, client);
+        }
+        ("utilize", Some(arg_matches)) => {
+            uses::utilize(arg_matches, payer, client);
+        }
+        ("burn_nft", Some(arg_matches)) => {
+            burn::burn_nft(arg_matches, payer, client);
+        }
+        ("burn_edition", Some(arg_matches)) => {
+            burn::burn_edition(arg_matches, payer, client);
+        }
+        ("create_escrow", Some(arg_matches)) => {
+            escrow::create_escrow(arg_matches, payer, client);
+        }
+        ("close_escrow", Some(arg_matches)) => {
+            escrow::close_escrow(arg_matches, payer, client);
+        }
+        ("transfer_out_of_escrow", Some(arg_matches)) => {
+            escrow::transfer_out(arg_matches, payer, client);
+        }
+        ("bulk_update", Some(arg_matches)) => {
+            bulk_update::bulk_update(arg_matches, payer, client);
+        }
+        ("snapshot", Some(arg_matches)) => {
+            snapshot::snapshot(arg_matches, payer, client);
+        }
         _ => unreachable!(),
     }
 }
@@ -0,0 +1,103 @@
+use {
+    mpl_token_metadata::{
+        state::{
+            CollectionDetails, Creator, Edition, Key, MasterEditionV1, MasterEditionV2, Metadata,
+        },
+        utils::unpuffed_string,
+    },
+    serde_json::{json, Value},
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::pubkey::Pubkey,
+};
+
+fn creator_json(creator: &Creator) -> Value {
+    json!({
+        "address": creator.address.to_string(),
+        "verified": creator.verified,
+        "share": creator.share,
+    })
+}
+
+/// Every field of `metadata`, with the padding of its strings stripped.
+pub fn metadata_json(metadata_key: &Pubkey, metadata: &Metadata) -> Value {
+    json!({
+        "key": metadata_key.to_string(),
+        "update_authority": metadata.update_authority.to_string(),
+        "mint": metadata.mint.to_string(),
+        "data": {
+            "name": unpuffed_string(&metadata.data.name),
+            "symbol": unpuffed_string(&metadata.data.symbol),
+            "uri": unpuffed_string(&metadata.data.uri),
+            "seller_fee_basis_points": metadata.data.seller_fee_basis_points,
+            "creators": metadata
+                .data
+                .creators
+                .as_ref()
+                .map(|creators| creators.iter().map(creator_json).collect::<Vec<_>>()),
+        },
+        "primary_sale_happened": metadata.primary_sale_happened,
+        "is_mutable": metadata.is_mutable,
+        "edition_nonce": metadata.edition_nonce,
+        "token_standard": metadata
+            .token_standard
+            .as_ref()
+            .map(|token_standard| format!("{:?}", token_standard)),
+        "collection": metadata.collection.as_ref().map(|collection| json!({
+            "key": collection.key.to_string(),
+            "verified": collection.verified,
+        })),
+        "uses": metadata.uses.as_ref().map(|uses| json!({
+            "use_method": format!("{:?}", uses.use_method),
+            "remaining": uses.remaining,
+            "total": uses.total,
+        })),
+        "collection_details": metadata.collection_details.as_ref().map(|details| match details {
+            CollectionDetails::V1 { size } => json!({ "version": "V1", "size": size }),
+        }),
+        "rule_set": metadata.rule_set.map(|rule_set| rule_set.to_string()),
+    })
+}
+
+/// The master edition or print edition stored in `data`, `None` for other accounts.
+pub fn edition_json(edition_key: &Pubkey, data: &[u8]) -> Option<Value> {
+    let mut edition = match data.first().copied() {
+        Some(key) if key == Key::MasterEditionV1 as u8 => {
+            let master_edition: MasterEditionV1 = try_from_slice_unchecked(data).ok()?;
+            json!({
+                "type": "MasterEditionV1",
+                "supply": master_edition.supply,
+                "max_supply": master_edition.max_supply,
+                "printing_mint": master_edition.printing_mint.to_string(),
+                "one_time_printing_authorization_mint": master_edition
+                    .one_time_printing_authorization_mint
+                    .to_string(),
+            })
+        }
+        Some(key) if key == Key::MasterEditionV2 as u8 => {
+            let master_edition: MasterEditionV2 = try_from_slice_unchecked(data).ok()?;
+            json!({
+                "type": "MasterEditionV2",
+                "supply": master_edition.supply,
+                "max_supply": master_edition.max_supply,
+                "print_template": master_edition.print_template.map(|template| json!({
+                    "append_edition_number": template.append_edition_number,
+                    "uri_template": template.uri_template,
+                })),
+                "print_config": master_edition.print_config.map(|config| config.to_string()),
+            })
+        }
+        Some(key) if key == Key::EditionV1 as u8 => {
+            let edition: Edition = try_from_slice_unchecked(data).ok()?;
+            json!({
+                "type": "Edition",
+                "parent": edition.parent.to_string(),
+                "edition": edition.edition,
+                "uri_override": edition.uri_override,
+            })
+        }
+        _ => return None,
+    };
+
+    edition["key"] = json!(edition_key.to_string());
+    Some(edition)
+}
@@ -0,0 +1,553 @@
+use {
+    crate::utils::largest_token_account,
+    borsh::BorshDeserialize,
+    clap::{App, Arg, ArgMatches, SubCommand},
+    mpl_token_metadata::{
+        pda::find_metadata_account,
+        state::{Key, Metadata},
+        utils::unpuffed_string,
+    },
+    serde_json::{json, Value},
+    solana_account_decoder::{UiAccount, UiAccountEncoding},
+    solana_clap_utils::{input_parsers::pubkey_of, input_validators::is_valid_pubkey},
+    solana_client::{
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+        rpc_response::RpcKeyedAccount,
+    },
+    solana_program::program_pack::Pack,
+    solana_sdk::{account::Account, pubkey::Pubkey, signature::Keypair},
+    spl_token::state::Account as TokenAccount,
+    std::{collections::HashMap, fs, io::Write, path::Path, str::FromStr},
+};
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![SubCommand::with_name("snapshot")
+        .about("Export the items of a collection or creator with their holders as CSV or JSON")
+        .arg(
+            Arg::with_name("collection")
+                .long("collection")
+                .value_name("MINT")
+                .validator(is_valid_pubkey)
+                .takes_value(true)
+                .required_unless("creator")
+                .help("Only export verified items of this collection"),
+        )
+        .arg(
+            Arg::with_name("creator")
+                .long("creator")
+                .value_name("ADDRESS")
+                .validator(is_valid_pubkey)
+                .takes_value(true)
+                .help("Only export items this creator verified, wherever it is among the creators"),
+        )
+        .arg(
+            Arg::with_name("accounts_dump")
+                .long("accounts_dump")
+                .value_name("FILE")
+                .takes_value(true)
+                .conflicts_with("record")
+                .help(
+                    "Read the accounts from a JSON dump of keyed accounts, as returned by \
+                     getProgramAccounts, instead of the cluster",
+                ),
+        )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Write the accounts read from the cluster to a dump for --accounts_dump"),
+        )
+        .arg(
+            Arg::with_name("out")
+                .long("out")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("File to write the snapshot to, defaults to stdout"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .possible_values(&["csv", "json"])
+                .help("Format of the snapshot, defaults to the extension of --out or csv"),
+        )]
+}
+
+/// Largest holder of a mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holder {
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Where the snapshot reads its accounts from.
+pub trait AccountSource {
+    /// All metadata accounts. Creators may sit anywhere in the list and strings may be unpuffed, so
+    /// there is no reliable offset to filter on and callers filter what is returned.
+    fn metadata_accounts(&mut self) -> Vec<(Pubkey, Vec<u8>)>;
+
+    fn largest_holder(&mut self, mint: &Pubkey) -> Option<Holder>;
+}
+
+/// Reads accounts from the cluster, keeping a copy of each of them when recording.
+pub struct RpcSource<'a> {
+    client: &'a RpcClient,
+    recorded: Option<Vec<RpcKeyedAccount>>,
+}
+
+impl<'a> RpcSource<'a> {
+    pub fn new(client: &'a RpcClient, record: bool) -> Self {
+        Self {
+            client,
+            recorded: if record { Some(vec![]) } else { None },
+        }
+    }
+
+    fn record(&mut self, pubkey: &Pubkey, account: &Account) {
+        if let Some(recorded) = self.recorded.as_mut() {
+            recorded.push(RpcKeyedAccount {
+                pubkey: pubkey.to_string(),
+                account: UiAccount::encode(pubkey, account, UiAccountEncoding::Base64, None, None),
+            });
+        }
+    }
+}
+
+impl<'a> AccountSource for RpcSource<'a> {
+    fn metadata_accounts(&mut self) -> Vec<(Pubkey, Vec<u8>)> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                vec![Key::MetadataV1 as u8],
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let accounts = self
+            .client
+            .get_program_accounts_with_config(&mpl_token_metadata::id(), config)
+            .unwrap();
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                self.record(&pubkey, &account);
+                (pubkey, account.data)
+            })
+            .collect()
+    }
+
+    fn largest_holder(&mut self, mint: &Pubkey) -> Option<Holder> {
+        let token_account = largest_token_account(self.client, mint)?;
+        let account = self.client.get_account(&token_account).unwrap();
+        self.record(&token_account, &account);
+
+        let token = TokenAccount::unpack(&account.data).ok()?;
+        Some(Holder {
+            token_account,
+            owner: token.owner,
+            amount: token.amount,
+        })
+    }
+}
+
+/// Reads accounts from a dump of keyed accounts, such as one written with `--record`.
+pub struct DumpSource {
+    metadata_accounts: Vec<(Pubkey, Vec<u8>)>,
+    holders: HashMap<Pubkey, Holder>,
+}
+
+impl DumpSource {
+    pub fn new(dump: &[RpcKeyedAccount]) -> Self {
+        let mut metadata_accounts = vec![];
+        let mut holders: HashMap<Pubkey, Holder> = HashMap::new();
+
+        for keyed_account in dump {
+            let pubkey = Pubkey::from_str(&keyed_account.pubkey).unwrap();
+            let account: Account = match keyed_account.account.decode() {
+                Some(account) => account,
+                None => continue,
+            };
+
+            if account.owner == mpl_token_metadata::id() {
+                if account.data.first() == Some(&(Key::MetadataV1 as u8)) {
+                    metadata_accounts.push((pubkey, account.data));
+                }
+            } else if account.owner == spl_token::id() {
+                let token = match TokenAccount::unpack(&account.data) {
+                    Ok(token) if token.amount > 0 => token,
+                    _ => continue,
+                };
+                let holder = Holder {
+                    token_account: pubkey,
+                    owner: token.owner,
+                    amount: token.amount,
+                };
+                match holders.get(&token.mint) {
+                    Some(largest) if largest.amount >= holder.amount => {}
+                    _ => {
+                        holders.insert(token.mint, holder);
+                    }
+                }
+            }
+        }
+
+        Self {
+            metadata_accounts,
+            holders,
+        }
+    }
+}
+
+impl AccountSource for DumpSource {
+    fn metadata_accounts(&mut self) -> Vec<(Pubkey, Vec<u8>)> {
+        self.metadata_accounts.clone()
+    }
+
+    fn largest_holder(&mut self, mint: &Pubkey) -> Option<Holder> {
+        self.holders.get(mint).cloned()
+    }
+}
+
+/// One exported item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRow {
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub holder: Option<Holder>,
+}
+
+impl SnapshotRow {
+    fn to_json(&self) -> Value {
+        json!({
+            "mint": self.mint.to_string(),
+            "metadata": self.metadata.to_string(),
+            "name": self.name,
+            "symbol": self.symbol,
+            "uri": self.uri,
+            "owner": self.holder.as_ref().map(|holder| holder.owner.to_string()),
+            "token_account": self.holder.as_ref().map(|holder| holder.token_account.to_string()),
+            "amount": self.holder.as_ref().map(|holder| holder.amount),
+        })
+    }
+
+    fn to_csv(&self) -> String {
+        let holder = |f: fn(&Holder) -> String| self.holder.as_ref().map(f).unwrap_or_default();
+        [
+            self.mint.to_string(),
+            self.metadata.to_string(),
+            self.name.clone(),
+            self.symbol.clone(),
+            self.uri.clone(),
+            holder(|holder| holder.owner.to_string()),
+            holder(|holder| holder.token_account.to_string()),
+            holder(|holder| holder.amount.to_string()),
+        ]
+        .iter()
+        .map(|cell| csv_cell(cell))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+const CSV_HEADER: &str = "mint,metadata,name,symbol,uri,owner,token_account,amount";
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn is_member(metadata: &Metadata, collection: Option<&Pubkey>, creator: Option<&Pubkey>) -> bool {
+    if let Some(collection) = collection {
+        match &metadata.collection {
+            Some(c) if c.verified && c.key == *collection => {}
+            _ => return false,
+        }
+    }
+    if let Some(creator) = creator {
+        let mut creators = metadata.data.creators.iter().flatten();
+        if !creators.any(|c| c.verified && c.address == *creator) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Verified items of `collection` and `creator`, with their largest holders, sorted by mint.
+pub fn take_snapshot(
+    source: &mut dyn AccountSource,
+    collection: Option<&Pubkey>,
+    creator: Option<&Pubkey>,
+) -> Vec<SnapshotRow> {
+    let mut rows: Vec<SnapshotRow> = source
+        .metadata_accounts()
+        .into_iter()
+        .filter_map(|(metadata_key, data)| {
+            // Decodes through `meta_deser_unchecked`, which recovers from corrupted trailing fields.
+            let metadata = Metadata::deserialize(&mut data.as_slice()).ok()?;
+            // Skip accounts that merely look like metadata at another address.
+            if find_metadata_account(&metadata.mint).0 != metadata_key {
+                return None;
+            }
+            is_member(&metadata, collection, creator).then_some((metadata_key, metadata))
+        })
+        .map(|(metadata_key, metadata)| SnapshotRow {
+            mint: metadata.mint,
+            metadata: metadata_key,
+            name: unpuffed_string(&metadata.data.name),
+            symbol: unpuffed_string(&metadata.data.symbol),
+            uri: unpuffed_string(&metadata.data.uri),
+            holder: None,
+        })
+        .collect();
+    rows.sort_by_key(|row| row.mint.to_string());
+
+    for row in rows.iter_mut() {
+        row.holder = source.largest_holder(&row.mint);
+    }
+    rows
+}
+
+pub fn format_snapshot(rows: &[SnapshotRow], format: &str) -> String {
+    match format {
+        "json" => {
+            let rows: Vec<Value> = rows.iter().map(|row| row.to_json()).collect();
+            serde_json::to_string_pretty(&rows).unwrap()
+        }
+        _ => {
+            let mut lines = vec![CSV_HEADER.to_owned()];
+            lines.extend(rows.iter().map(|row| row.to_csv()));
+            lines.join("\n")
+        }
+    }
+}
+
+pub fn snapshot(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let collection = pubkey_of(app_matches, "collection");
+    let creator = pubkey_of(app_matches, "creator");
+    let out = app_matches.value_of("out").map(Path::new);
+    let format = app_matches
+        .value_of("format")
+        .map(|format| format.to_owned())
+        .or_else(|| {
+            out.and_then(|out| out.extension())
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+        })
+        .unwrap_or_else(|| "csv".to_owned());
+
+    let rows = match app_matches.value_of("accounts_dump") {
+        Some(path) => {
+            let dump: Vec<RpcKeyedAccount> =
+                serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+            take_snapshot(
+                &mut DumpSource::new(&dump),
+                collection.as_ref(),
+                creator.as_ref(),
+            )
+        }
+        None => {
+            let record = app_matches.value_of("record");
+            let mut source = RpcSource::new(&client, record.is_some());
+            let rows = take_snapshot(&mut source, collection.as_ref(), creator.as_ref());
+            if let (Some(path), Some(recorded)) = (record, source.recorded) {
+                fs::write(path, serde_json::to_string_pretty(&recorded).unwrap()).unwrap();
+            }
+            rows
+        }
+    };
+
+    let formatted = format_snapshot(&rows, &format);
+    match out {
+        Some(out) => {
+            let mut file = fs::File::create(out).unwrap();
+            writeln!(file, "{}", formatted).unwrap();
+            eprintln!("Wrote {} items to {}", rows.len(), out.display());
+        }
+        None => println!("{}", formatted),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        borsh::BorshSerialize,
+        mpl_token_metadata::state::{Collection, Creator, Data},
+        solana_program::program_option::COption,
+        spl_token::state::AccountState,
+    };
+
+    fn keyed(pubkey: &Pubkey, owner: &Pubkey, data: Vec<u8>) -> RpcKeyedAccount {
+        let account = Account {
+            lamports: 1_000_000,
+            data,
+            owner: *owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        RpcKeyedAccount {
+            pubkey: pubkey.to_string(),
+            account: UiAccount::encode(pubkey, &account, UiAccountEncoding::Base64, None, None),
+        }
+    }
+
+    fn metadata_account(
+        mint: &Pubkey,
+        collection: Option<Collection>,
+        creators: Option<Vec<Creator>>,
+    ) -> RpcKeyedAccount {
+        let metadata = Metadata {
+            mint: *mint,
+            data: Data {
+                name: "Item, \"one\"".to_owned(),
+                symbol: "ITM".to_owned(),
+                uri: "https://example.com/item.json".to_owned(),
+                seller_fee_basis_points: 500,
+                creators,
+            },
+            collection,
+            ..Metadata::default()
+        };
+        keyed(
+            &find_metadata_account(mint).0,
+            &mpl_token_metadata::id(),
+            metadata.try_to_vec().unwrap(),
+        )
+    }
+
+    fn token_account(
+        pubkey: &Pubkey,
+        mint: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> RpcKeyedAccount {
+        let token = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(token, &mut data).unwrap();
+        keyed(pubkey, &spl_token::id(), data)
+    }
+
+    fn verified_in(collection: &Pubkey) -> Option<Collection> {
+        Some(Collection {
+            verified: true,
+            key: *collection,
+        })
+    }
+
+    #[test]
+    fn snapshot_of_collection_from_dump() {
+        let collection = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let unverified = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let holder_account = Pubkey::new_unique();
+
+        let dump = vec![
+            metadata_account(&member, verified_in(&collection), None),
+            metadata_account(
+                &unverified,
+                Some(Collection {
+                    verified: false,
+                    key: collection,
+                }),
+                None,
+            ),
+            metadata_account(&outsider, verified_in(&Pubkey::new_unique()), None),
+            token_account(&Pubkey::new_unique(), &member, &Pubkey::new_unique(), 0),
+            token_account(&holder_account, &member, &owner, 1),
+        ];
+        // The dump is read back from its JSON form, as written with --record.
+        let dump: Vec<RpcKeyedAccount> =
+            serde_json::from_str(&serde_json::to_string(&dump).unwrap()).unwrap();
+
+        let rows = take_snapshot(&mut DumpSource::new(&dump), Some(&collection), None);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].mint, member);
+        assert_eq!(rows[0].name, "Item, \"one\"");
+        assert_eq!(
+            rows[0].holder,
+            Some(Holder {
+                token_account: holder_account,
+                owner,
+                amount: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn snapshot_filters_on_verified_creator() {
+        let creator = Pubkey::new_unique();
+        let verified = Pubkey::new_unique();
+        let unverified = Pubkey::new_unique();
+        let creators = |verified| {
+            Some(vec![
+                Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 50,
+                },
+                Creator {
+                    address: creator,
+                    verified,
+                    share: 50,
+                },
+            ])
+        };
+
+        let dump = vec![
+            metadata_account(&verified, None, creators(true)),
+            metadata_account(&unverified, None, creators(false)),
+        ];
+
+        let rows = take_snapshot(&mut DumpSource::new(&dump), None, Some(&creator));
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].mint, verified);
+        assert_eq!(rows[0].holder, None);
+    }
+
+    #[test]
+    fn csv_output_quotes_cells() {
+        let mint = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let dump = vec![metadata_account(&mint, verified_in(&collection), None)];
+
+        let rows = take_snapshot(&mut DumpSource::new(&dump), Some(&collection), None);
+        let csv = format_snapshot(&rows, "csv");
+
+        assert_eq!(
+            csv,
+            format!(
+                "{}\n{},{},\"Item, \"\"one\"\"\",ITM,https://example.com/item.json,,,",
+                CSV_HEADER,
+                mint,
+                find_metadata_account(&mint).0
+            )
+        );
+    }
+}
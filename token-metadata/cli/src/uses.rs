@@ -0,0 +1,114 @@
+use {
+    crate::utils::{amount_arg, find_token_account, pubkey_arg, send, value_of},
+    clap::{App, ArgMatches, SubCommand},
+    mpl_token_metadata::client::{ApproveUseAuthority, RevokeUseAuthority, Utilize},
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        SubCommand::with_name("approve_use_authority")
+            .about("Let another account utilize an asset you own")
+            .arg(pubkey_arg("mint", "Mint of the asset"))
+            .arg(pubkey_arg("user", "New use authority"))
+            .arg(amount_arg(
+                "number_of_uses",
+                "Number of uses the authority may consume",
+            ))
+            .arg(
+                amount_arg(
+                    "expiry",
+                    "Unix timestamp after which the authority can't be used",
+                )
+                .required(false),
+            )
+            .arg(
+                pubkey_arg(
+                    "caller_program",
+                    "Program the use authority must be invoked through",
+                )
+                .required(false),
+            ),
+        SubCommand::with_name("revoke_use_authority")
+            .about("Revoke a use authority of an asset you own")
+            .arg(pubkey_arg("mint", "Mint of the asset"))
+            .arg(pubkey_arg("user", "Use authority to revoke")),
+        SubCommand::with_name("utilize")
+            .about("Consume uses of an asset, as its owner or as its use authority")
+            .arg(pubkey_arg("mint", "Mint of the asset"))
+            .arg(amount_arg("number_of_uses", "Number of uses to consume"))
+            .arg(
+                pubkey_arg(
+                    "owner",
+                    "Owner of the asset when you are its use authority, defaults to you",
+                )
+                .required(false),
+            ),
+    ]
+}
+
+pub fn approve_use_authority(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let user = pubkey_of(app_matches, "user").unwrap();
+
+    let instruction = ApproveUseAuthority {
+        mint,
+        user,
+        owner: payer.pubkey(),
+        payer: payer.pubkey(),
+        owner_token_account: find_token_account(&client, &payer.pubkey(), &mint),
+        number_of_uses: value_of(app_matches, "number_of_uses").unwrap(),
+        expiry: value_of(app_matches, "expiry"),
+        caller_program: pubkey_of(app_matches, "caller_program"),
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Approved {} as use authority of {} with signature {}",
+        user, mint, signature
+    );
+}
+
+pub fn revoke_use_authority(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let user = pubkey_of(app_matches, "user").unwrap();
+
+    let instruction = RevokeUseAuthority {
+        mint,
+        user,
+        owner: payer.pubkey(),
+        owner_token_account: find_token_account(&client, &payer.pubkey(), &mint),
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Revoked {} as use authority of {} with signature {}",
+        user, mint, signature
+    );
+}
+
+pub fn utilize(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let mint = pubkey_of(app_matches, "mint").unwrap();
+    let number_of_uses = value_of(app_matches, "number_of_uses").unwrap();
+    let owner = pubkey_of(app_matches, "owner").unwrap_or_else(|| payer.pubkey());
+
+    let instruction = Utilize {
+        mint,
+        token_account: find_token_account(&client, &owner, &mint),
+        use_authority: payer.pubkey(),
+        owner,
+        number_of_uses,
+        delegated: owner != payer.pubkey(),
+    }
+    .instruction();
+    let signature = send(&client, &payer, &[instruction], &[]);
+
+    println!(
+        "Used {} {} times with signature {}",
+        mint, number_of_uses, signature
+    );
+}
@@ -0,0 +1,154 @@
+use {
+    clap::{Arg, ArgMatches},
+    mpl_token_metadata::{pda::find_metadata_account, state::Metadata},
+    solana_clap_utils::input_validators::{is_valid_pubkey, is_valid_signer},
+    solana_client::{
+        client_error::Result as ClientResult,
+        rpc_client::RpcClient,
+        rpc_request::{RpcRequest, TokenAccountsFilter},
+        rpc_response::{Response, RpcTokenAccountBalance},
+    },
+    solana_program::borsh::try_from_slice_unchecked,
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        transaction::Transaction,
+    },
+    std::str::FromStr,
+};
+
+/// Required pubkey argument passed as `--<name>`.
+pub fn pubkey_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .required(true)
+        .validator(is_valid_pubkey)
+        .takes_value(true)
+        .help(help)
+}
+
+/// Optional keypair argument passed as `--<name>`, see [`keypair_or_payer`].
+pub fn signer_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .required(false)
+        .validator(is_valid_signer)
+        .takes_value(true)
+        .help(help)
+}
+
+/// Required numeric argument passed as `--<name>`.
+pub fn amount_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .required(true)
+        .takes_value(true)
+        .help(help)
+}
+
+/// Flag passed as `--<name>`.
+pub fn flag_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .takes_value(false)
+        .required(false)
+        .help(help)
+}
+
+/// Reads the keypair passed as `--<name>`, defaulting to the keypair of the payer.
+pub fn keypair_or_payer(app_matches: &ArgMatches, name: &str) -> Keypair {
+    read_keypair_file(
+        app_matches
+            .value_of(name)
+            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
+    )
+    .unwrap()
+}
+
+pub fn value_of<T: FromStr>(app_matches: &ArgMatches, name: &str) -> Option<T> {
+    app_matches.value_of(name).map(|val| {
+        val.parse::<T>()
+            .unwrap_or_else(|_| panic!("Invalid value for --{}", name))
+    })
+}
+
+pub fn get_metadata(client: &RpcClient, mint: &Pubkey) -> (Pubkey, Metadata) {
+    let (metadata_key, _) = find_metadata_account(mint);
+    let metadata_account = client.get_account(&metadata_key).unwrap();
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    (metadata_key, metadata)
+}
+
+/// Token account of `owner` holding tokens of `mint`.
+pub fn find_token_account(client: &RpcClient, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_account = client
+        .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+        .unwrap()
+        .into_iter()
+        .find(|x| {
+            client
+                .get_token_account_balance(&Pubkey::from_str(&x.pubkey).unwrap())
+                .unwrap()
+                .amount
+                != "0"
+        })
+        .expect("Could not find a token account holding the mint.");
+
+    Pubkey::from_str(&token_account.pubkey).unwrap()
+}
+
+/// Token account holding the most tokens of `mint`, which is its only holder for an NFT.
+pub fn find_largest_token_account(client: &RpcClient, mint: &Pubkey) -> Pubkey {
+    largest_token_account(client, mint).expect("Could not find a token account holding the mint.")
+}
+
+/// Like [`find_largest_token_account`], but `None` when no token account holds the mint.
+pub fn largest_token_account(client: &RpcClient, mint: &Pubkey) -> Option<Pubkey> {
+    let largest_accounts: Response<Vec<RpcTokenAccountBalance>> = client
+        .send(
+            RpcRequest::Custom {
+                method: "getTokenLargestAccounts",
+            },
+            serde_json::json!([mint.to_string()]),
+        )
+        .unwrap();
+
+    largest_accounts
+        .value
+        .into_iter()
+        .find(|balance| balance.amount.amount != "0")
+        .map(|balance| Pubkey::from_str(&balance.address).unwrap())
+}
+
+/// Signs `instructions` with the payer and `signers`, and sends them in one transaction.
+pub fn send(
+    client: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Signature {
+    try_send(client, payer, instructions, signers).unwrap()
+}
+
+/// Like [`send`], but hands the error back instead of panicking.
+#[allow(clippy::result_large_err)]
+pub fn try_send(
+    client: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> ClientResult<Signature> {
+    let mut all_signers = vec![payer];
+    for signer in signers {
+        if !all_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            all_signers.push(signer);
+        }
+    }
+
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    transaction.sign(&all_signers, recent_blockhash);
+    client.send_and_confirm_transaction(&transaction)
+}
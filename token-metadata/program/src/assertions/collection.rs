@@ -1,4 +1,7 @@
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, program_error::ProgramError, pubkey::Pubkey,
+    sysvar::Sysvar,
+};
 
 use crate::{
     error::MetadataError,
@@ -42,11 +45,14 @@ pub fn assert_is_collection_delegated_authority(
     Ok(bump)
 }
 
+/// Checks `collection_authority_info` is the update authority of the collection, or a delegated
+/// collection authority granted `permission` (one of the `COLLECTION_PERMISSION_*` bits).
 pub fn assert_has_collection_authority(
     collection_authority_info: &AccountInfo,
     collection_data: &Metadata,
     mint: &Pubkey,
     delegate_collection_authority_record: Option<&AccountInfo>,
+    permission: u8,
 ) -> Result<(), ProgramError> {
     // Mint is the correct one for the metadata account.
     if collection_data.mint != *mint {
@@ -75,6 +81,14 @@ pub fn assert_has_collection_authority(
             }
             None => return Err(MetadataError::InvalidCollectionUpdateAuthority.into()),
         }
+        if record.permissions & permission != permission {
+            return Err(MetadataError::CollectionAuthorityNotPermitted.into());
+        }
+        if let Some(expiry) = record.expiry {
+            if Clock::get()?.unix_timestamp > expiry {
+                return Err(MetadataError::CollectionAuthorityExpired.into());
+            }
+        }
     } else if collection_data.update_authority != *collection_authority_info.key {
         return Err(MetadataError::InvalidCollectionUpdateAuthority.into());
     }
@@ -122,9 +136,11 @@ pub fn assert_master_edition(
 ) -> Result<(), ProgramError> {
     let edition = MasterEditionV2::from_account_info(edition_account_info)
         .map_err(|_err: ProgramError| MetadataError::CollectionMustBeAUniqueMasterEdition)?;
-    if collection_data.token_standard != Some(TokenStandard::NonFungible)
-        || edition.max_supply != Some(0)
-    {
+    let is_master_edition = matches!(
+        collection_data.token_standard,
+        Some(TokenStandard::NonFungible) | Some(TokenStandard::ProgrammableNonFungible)
+    );
+    if !is_master_edition || edition.max_supply != Some(0) {
         return Err(MetadataError::CollectionMustBeAUniqueMasterEdition.into());
     }
     Ok(())
@@ -1,2 +1,4 @@
 pub mod collection;
+pub mod rule_set;
 pub mod uses;
+pub mod validation;
@@ -0,0 +1,118 @@
+use crate::{
+    error::MetadataError,
+    state::{Metadata, RuleSet, TokenMetadataAccount, PREFIX, RULE_SET},
+    utils::{assert_derivation, assert_owned_by},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Checks `delegate_info` against the rule set attached to `metadata`, if there is one.
+/// The rule set account is looked up by address in `remaining_accounts`, so it is only
+/// required for assets that have a rule set.
+pub fn assert_rule_set_allows(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    delegate_info: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> ProgramResult {
+    let rule_set_key = match metadata.rule_set {
+        Some(rule_set_key) => rule_set_key,
+        None => return Ok(()),
+    };
+
+    let rule_set_info = remaining_accounts
+        .iter()
+        .find(|account_info| *account_info.key == rule_set_key)
+        .ok_or(MetadataError::InvalidRuleSet)?;
+
+    assert_rule_set_account_allows(program_id, &metadata.mint, rule_set_info, delegate_info)
+}
+
+/// Checks that `authority_info` may move the asset without paying royalties. Only the update
+/// authority and authorities explicitly listed in the allow list of the asset's rule set may,
+/// the rule set account being looked up by address in `remaining_accounts`.
+pub fn assert_royalty_free_transfer_allowed(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    authority_info: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> ProgramResult {
+    if *authority_info.key == metadata.update_authority {
+        return Ok(());
+    }
+
+    let rule_set_info = metadata.rule_set.and_then(|rule_set_key| {
+        remaining_accounts
+            .iter()
+            .find(|account_info| *account_info.key == rule_set_key)
+    });
+
+    let rule_set_info = match rule_set_info {
+        Some(rule_set_info) => rule_set_info,
+        None => return Err(MetadataError::RoyaltiesRequired.into()),
+    };
+
+    let rule_set = load_rule_set(program_id, &metadata.mint, rule_set_info)?;
+    if !rule_set.explicitly_allows(authority_info.key, authority_info.owner) {
+        return Err(MetadataError::RoyaltiesRequired.into());
+    }
+
+    Ok(())
+}
+
+/// Checks `delegate_info` against the rule set PDA of `mint_info`, for instructions that
+/// don't take the metadata account. An empty PDA means the asset has no rule set.
+pub fn assert_rule_set_pda_allows(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    rule_set_info: &AccountInfo,
+    delegate_info: &AccountInfo,
+) -> ProgramResult {
+    assert_derivation(
+        program_id,
+        rule_set_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            RULE_SET.as_bytes(),
+        ],
+    )?;
+
+    if rule_set_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_rule_set_account_allows(program_id, mint_info.key, rule_set_info, delegate_info)
+}
+
+fn assert_rule_set_account_allows(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    rule_set_info: &AccountInfo,
+    delegate_info: &AccountInfo,
+) -> ProgramResult {
+    let rule_set = load_rule_set(program_id, mint, rule_set_info)?;
+    if !rule_set.allows(delegate_info.key, delegate_info.owner) {
+        return Err(MetadataError::DelegateNotAllowedByRuleSet.into());
+    }
+
+    Ok(())
+}
+
+fn load_rule_set(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    rule_set_info: &AccountInfo,
+) -> Result<RuleSet, ProgramError> {
+    assert_owned_by(rule_set_info, program_id)?;
+
+    let rule_set = RuleSet::from_account_info(rule_set_info)?;
+    if rule_set.mint != *mint {
+        return Err(MetadataError::InvalidRuleSet.into());
+    }
+
+    Ok(rule_set)
+}
@@ -1,4 +1,11 @@
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{self, instructions::get_instruction_relative, Sysvar},
+};
 
 use crate::{
     error::MetadataError,
@@ -18,6 +25,11 @@ pub fn assert_valid_use(
         if i.use_method == UseMethod::Multiple && (i.total < 2 || i.total < i.remaining) {
             return Err(MetadataError::InvalidUseMethod.into());
         }
+        if (i.use_method == UseMethod::Recharging || i.use_method == UseMethod::Expiring)
+            && (i.total < 1 || i.total < i.remaining)
+        {
+            return Err(MetadataError::InvalidUseMethod.into());
+        }
     }
     match (incoming_use, current_use) {
         (Some(incoming), Some(current)) => {
@@ -54,6 +66,33 @@ pub fn assert_valid_bump(
     Ok(())
 }
 
+/// Checks the use authority is used before its expiry and through its caller program. The
+/// calling program is read from the instructions sysvar.
+pub fn assert_use_authority_scope(
+    use_authority_record: &UseAuthorityRecord,
+    instructions_sysvar_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    if let Some(expiry) = use_authority_record.expiry {
+        if Clock::get()?.unix_timestamp > expiry {
+            return Err(MetadataError::UseAuthorityExpired.into());
+        }
+    }
+
+    if let Some(caller_program) = use_authority_record.caller_program {
+        let instructions_sysvar_info = match instructions_sysvar_info {
+            Some(info) if *info.key == sysvar::instructions::id() => info,
+            _ => return Err(MetadataError::InvalidCallerProgram.into()),
+        };
+
+        let current_instruction = get_instruction_relative(0, instructions_sysvar_info)?;
+        if current_instruction.program_id != caller_program {
+            return Err(MetadataError::InvalidCallerProgram.into());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn assert_use_authority_derivation(
     program_id: &Pubkey,
     use_authority_record_info: &AccountInfo,
@@ -0,0 +1,251 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    collection::collection_validation_profile_seeds,
+    error::MetadataError,
+    state::{
+        CollectionValidationProfile, Data, Metadata, TokenMetadataAccount, VALIDATION_RULES_ALL,
+        VALIDATION_RULE_NAME_CHARSET, VALIDATION_RULE_NO_ZERO_WIDTH,
+        VALIDATION_RULE_UPPERCASE_SYMBOL, VALIDATION_RULE_URI_SCHEME,
+    },
+    utils::{assert_derivation, assert_owned_by, unpuffed_string},
+};
+
+const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ar://", "ipfs://"];
+
+pub fn assert_validation_rules_are_valid(rules: u8) -> Result<(), MetadataError> {
+    if rules & !VALIDATION_RULES_ALL != 0 {
+        return Err(MetadataError::InvalidValidationRules);
+    }
+
+    Ok(())
+}
+
+/// Zero-width and other invisible characters, which make a name look like another one.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}'
+    )
+}
+
+/// Checks the unpadded `name`, `symbol` and `uri` of an item against the `rules` bitmask.
+pub fn assert_meets_validation_rules(
+    rules: u8,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<(), MetadataError> {
+    if rules & VALIDATION_RULE_NO_ZERO_WIDTH != 0
+        && [name, symbol, uri]
+            .iter()
+            .any(|value| value.chars().any(is_invisible))
+    {
+        return Err(MetadataError::ZeroWidthCharacterNotAllowed);
+    }
+
+    if rules & VALIDATION_RULE_NAME_CHARSET != 0
+        && !name.chars().all(|c| c == ' ' || c.is_ascii_graphic())
+    {
+        return Err(MetadataError::NameCharsetNotAllowed);
+    }
+
+    if rules & VALIDATION_RULE_UPPERCASE_SYMBOL != 0
+        && !symbol
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return Err(MetadataError::SymbolNotUppercase);
+    }
+
+    if rules & VALIDATION_RULE_URI_SCHEME != 0
+        && !ALLOWED_URI_SCHEMES
+            .iter()
+            .any(|scheme| uri.starts_with(scheme) && uri.len() > scheme.len())
+    {
+        return Err(MetadataError::UriSchemeNotAllowed);
+    }
+
+    Ok(())
+}
+
+/// Checks an item against the validation profile of the collection of `collection_mint`.
+/// Collections without a validation profile accept any item.
+pub fn assert_collection_validation_profile(
+    program_id: &Pubkey,
+    collection_mint: &Pubkey,
+    validation_profile_info: &AccountInfo,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> ProgramResult {
+    assert_derivation(
+        program_id,
+        validation_profile_info,
+        &collection_validation_profile_seeds(program_id, collection_mint),
+    )?;
+
+    if validation_profile_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(validation_profile_info, program_id)?;
+    let profile = CollectionValidationProfile::from_account_info(validation_profile_info)?;
+
+    assert_meets_validation_rules(profile.rules, name, symbol, uri)?;
+
+    Ok(())
+}
+
+/// Checks the name, symbol and uri of `metadata` against the validation profile of the
+/// collection of `collection_mint`.
+pub fn assert_metadata_validation_profile(
+    program_id: &Pubkey,
+    collection_mint: &Pubkey,
+    validation_profile_info: &AccountInfo,
+    metadata: &Metadata,
+) -> ProgramResult {
+    assert_collection_validation_profile(
+        program_id,
+        collection_mint,
+        validation_profile_info,
+        &unpuffed_string(&metadata.data.name),
+        &unpuffed_string(&metadata.data.symbol),
+        &unpuffed_string(&metadata.data.uri),
+    )
+}
+
+/// Finds the validation profile of the collection of `collection_mint` among `accounts` by its
+/// address. It has to be passed even when the collection has no profile, uninitialized.
+pub fn find_validation_profile_info<'b, 'a>(
+    program_id: &Pubkey,
+    collection_mint: &Pubkey,
+    accounts: &'b [AccountInfo<'a>],
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let (validation_profile_key, _) = Pubkey::find_program_address(
+        &collection_validation_profile_seeds(program_id, collection_mint),
+        program_id,
+    );
+
+    accounts
+        .iter()
+        .find(|info| *info.key == validation_profile_key)
+        .ok_or_else(|| MetadataError::MissingValidationProfile.into())
+}
+
+/// Checks a verified collection item whose name, symbol or uri is changed by an update against
+/// the validation profile of its collection, found among `accounts` by its address.
+pub fn assert_update_meets_validation_profile(
+    program_id: &Pubkey,
+    old_data: &Data,
+    metadata: &Metadata,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let collection = match &metadata.collection {
+        Some(collection) if collection.verified => collection,
+        _ => return Ok(()),
+    };
+
+    let changed = [
+        (&old_data.name, &metadata.data.name),
+        (&old_data.symbol, &metadata.data.symbol),
+        (&old_data.uri, &metadata.data.uri),
+    ]
+    .iter()
+    .any(|(old, new)| unpuffed_string(old) != unpuffed_string(new));
+
+    if !changed {
+        return Ok(());
+    }
+
+    let validation_profile_info =
+        find_validation_profile_info(program_id, &collection.key, accounts)?;
+    assert_metadata_validation_profile(
+        program_id,
+        &collection.key,
+        validation_profile_info,
+        metadata,
+    )
+}
+
+/// Splits the accounts following a single item verification into the optional collection
+/// authority record and the validation profile, which comes last. A lone account is the
+/// validation profile when it has its address.
+pub fn split_validation_profile<'b, 'a>(
+    program_id: &Pubkey,
+    collection_mint: &Pubkey,
+    accounts: &'b [AccountInfo<'a>],
+) -> (Option<&'b AccountInfo<'a>>, Option<&'b AccountInfo<'a>>) {
+    match accounts {
+        [] => (None, None),
+        [info] => {
+            let (validation_profile_key, _) = Pubkey::find_program_address(
+                &collection_validation_profile_seeds(program_id, collection_mint),
+                program_id,
+            );
+            if *info.key == validation_profile_key {
+                (None, Some(info))
+            } else {
+                (Some(info), None)
+            }
+        }
+        [collection_authority_record, validation_profile_info, ..] => (
+            Some(collection_authority_record),
+            Some(validation_profile_info),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_items_following_the_rules() {
+        assert!(
+            assert_meets_validation_rules(VALIDATION_RULES_ALL, "Item #1", "ITM1", "ar://abc")
+                .is_ok()
+        );
+        // Rules that aren't set are not checked.
+        assert!(assert_meets_validation_rules(0, "It\u{0435}m", "itm", "uri").is_ok());
+    }
+
+    #[test]
+    fn rejects_items_breaking_the_rules() {
+        let rules = VALIDATION_RULES_ALL;
+        let uri = "https://example.com/1.json";
+
+        assert_eq!(
+            assert_meets_validation_rules(rules, "It\u{200B}em", "ITM", uri),
+            Err(MetadataError::ZeroWidthCharacterNotAllowed)
+        );
+        assert_eq!(
+            assert_meets_validation_rules(rules, "It\u{0435}m", "ITM", uri),
+            Err(MetadataError::NameCharsetNotAllowed)
+        );
+        assert_eq!(
+            assert_meets_validation_rules(rules, "Item", "Itm", uri),
+            Err(MetadataError::SymbolNotUppercase)
+        );
+        assert_eq!(
+            assert_meets_validation_rules(rules, "Item", "ITM", "http://example.com"),
+            Err(MetadataError::UriSchemeNotAllowed)
+        );
+        assert_eq!(
+            assert_meets_validation_rules(rules, "Item", "ITM", "ipfs://"),
+            Err(MetadataError::UriSchemeNotAllowed)
+        );
+        assert_eq!(
+            assert_validation_rules_are_valid(VALIDATION_RULES_ALL + 1),
+            Err(MetadataError::InvalidValidationRules)
+        );
+    }
+}
@@ -0,0 +1,48 @@
+pub mod remove_attribute;
+pub mod set_attribute;
+
+pub use remove_attribute::*;
+pub use set_attribute::*;
+
+use crate::{
+    error::MetadataError,
+    state::{Metadata, MetadataDelegateRole, ATTRIBUTES, PREFIX},
+    update::assert_metadata_delegate,
+    utils::assert_signer,
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn attributes_seeds<'a>(program_id: &'a Pubkey, mint: &'a Pubkey) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        ATTRIBUTES.as_bytes(),
+    ]
+}
+
+/// Checks that `authority_info` is the update authority, or an attributes delegate backed by
+/// `delegate_record_info`.
+pub fn assert_attributes_authority(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    authority_info: &AccountInfo,
+    delegate_record_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    assert_signer(authority_info)?;
+
+    if *authority_info.key == metadata.update_authority {
+        return Ok(());
+    }
+
+    let delegate_record_info =
+        delegate_record_info.ok_or(MetadataError::UpdateAuthorityIncorrect)?;
+    let role =
+        assert_metadata_delegate(program_id, metadata, authority_info, delegate_record_info)?;
+
+    if role != MetadataDelegateRole::Attributes {
+        return Err(MetadataError::DelegateRoleCannotUpdateField.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{
+    attributes::{assert_attributes_authority, attributes_seeds},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, MetadataAttributes, TokenMetadataAccount},
+    utils::{assert_derivation, assert_owned_by, close_account_raw, shrink_account_raw},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct RemoveAttributeArgs {
+    pub key: String,
+}
+
+///# Remove Attribute
+///
+/// Removes an on-chain attribute of an asset. The attributes account is closed once its last
+/// attribute is removed, the freed rent going to the update authority.
+///
+///### Accounts:
+///
+///   0. `[writable]` Attributes account (pda of ['metadata', program id, mint id, 'attributes'])
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority or attributes delegate
+///   4. `[writable]` Update authority of the metadata, receives the freed rent
+///   5. Optional `[]` Metadata delegate record, when the authority is a delegate
+pub fn remove_attribute(
+    program_id: Pubkey,
+    attributes: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    receiver: Pubkey,
+    delegate_record: Option<Pubkey>,
+    key: String,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(attributes, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    if let Some(delegate_record) = delegate_record {
+        accounts.push(AccountMeta::new_readonly(delegate_record, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::RemoveAttribute(RemoveAttributeArgs { key })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_remove_attribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RemoveAttributeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attributes_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+    let delegate_record_info = account_info_iter.next();
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(attributes_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    assert_attributes_authority(program_id, &metadata, authority_info, delegate_record_info)?;
+
+    assert_derivation(
+        program_id,
+        attributes_info,
+        &attributes_seeds(program_id, mint_info.key),
+    )?;
+
+    let mut attributes = MetadataAttributes::from_account_info(attributes_info)?;
+
+    if *receiver_info.key != metadata.update_authority {
+        return Err(MetadataError::RentReceiverMismatch.into());
+    }
+
+    if !attributes.remove(&args.key) {
+        return Err(MetadataError::AttributeNotFound.into());
+    }
+
+    if attributes.attributes.is_empty() {
+        return close_account_raw(receiver_info, attributes_info);
+    }
+
+    shrink_account_raw(attributes_info, receiver_info, attributes.serialized_len())?;
+    BorshSerialize::serialize(&attributes, &mut *attributes_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use crate::{
+    attributes::{assert_attributes_authority, attributes_seeds},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{
+        Key, Metadata, MetadataAttributes, TokenMetadataAccount, MAX_ATTRIBUTES,
+        MAX_ATTRIBUTE_KEY_LENGTH, MAX_ATTRIBUTE_VALUE_LENGTH,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        resize_or_reallocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetAttributeArgs {
+    pub key: String,
+    pub value: String,
+}
+
+///# Set Attribute
+///
+/// Sets an on-chain attribute of an asset, creating its attributes account on first use.
+///
+///### Accounts:
+///
+///   0. `[writable]` Attributes account (pda of ['metadata', program id, mint id, 'attributes'])
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority or attributes delegate
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+///   6. Optional `[]` Metadata delegate record, when the authority is a delegate
+#[allow(clippy::too_many_arguments)]
+pub fn set_attribute(
+    program_id: Pubkey,
+    attributes: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    delegate_record: Option<Pubkey>,
+    key: String,
+    value: String,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(attributes, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    if let Some(delegate_record) = delegate_record {
+        accounts.push(AccountMeta::new_readonly(delegate_record, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetAttribute(SetAttributeArgs { key, value })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_set_attribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetAttributeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attributes_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let delegate_record_info = account_info_iter.next();
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    assert_attributes_authority(program_id, &metadata, authority_info, delegate_record_info)?;
+
+    if args.key.is_empty()
+        || args.key.len() > MAX_ATTRIBUTE_KEY_LENGTH
+        || args.value.len() > MAX_ATTRIBUTE_VALUE_LENGTH
+    {
+        return Err(MetadataError::InvalidAttribute.into());
+    }
+
+    let attributes_path = attributes_seeds(program_id, mint_info.key);
+    let bump = assert_derivation(program_id, attributes_info, &attributes_path)?;
+
+    let mut attributes = if attributes_info.data_is_empty() {
+        MetadataAttributes {
+            key: Key::MetadataAttributes,
+            mint: *mint_info.key,
+            bump,
+            attributes: vec![],
+        }
+    } else {
+        assert_owned_by(attributes_info, program_id)?;
+        MetadataAttributes::from_account_info(attributes_info)?
+    };
+
+    attributes.set(args.key, args.value);
+
+    if attributes.attributes.len() > MAX_ATTRIBUTES {
+        return Err(MetadataError::TooManyAttributes.into());
+    }
+
+    let attributes_len = attributes.serialized_len();
+
+    if attributes_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut attributes_seeds = attributes_path.clone();
+        attributes_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            attributes_info,
+            system_program_info,
+            payer_info,
+            attributes_len,
+            &attributes_seeds,
+        )?;
+    } else if attributes_info.data_len() < attributes_len {
+        resize_or_reallocate_account_raw(
+            attributes_info,
+            payer_info,
+            system_program_info,
+            attributes_len,
+        )?;
+    } else if attributes_info.data_len() > attributes_len {
+        // A replaced value may be shorter. The freed rent stays in the account, it goes to
+        // the update authority through RemoveAttribute.
+        attributes_info.realloc(attributes_len, false)?;
+    }
+
+    BorshSerialize::serialize(&attributes, &mut *attributes_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use crate::{
+    burn::{close_delegate_records, remove_from_collection},
+    error::MetadataError,
+    events::emit_burn,
+    instruction::MetadataInstruction,
+    pda::find_master_edition_account,
+    processor::{process_burn_edition_nft, process_burn_nft},
+    state::{CollectionDetails, Key, Metadata, TokenMetadataAccount, TokenStandard},
+    utils::{
+        assert_currently_holding, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, close_account_raw, get_mint_authority,
+        get_mint_supply, spl_token_burn, TokenBurnParams,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct BurnArgs {
+    /// Amount of tokens to burn, ignored for non-fungible assets.
+    pub amount: u64,
+    /// Burn a collection parent even though it still has verified items.
+    pub force: bool,
+}
+
+/// A use authority or collection authority record to close along with the asset.
+#[derive(Debug, Clone)]
+pub struct BurnDelegateRecord {
+    pub record: Pubkey,
+    pub delegate: Pubkey,
+    /// Account that funded the record, which gets its rent back.
+    pub payer: Pubkey,
+}
+
+/// Accounts of the master edition a print edition was printed from.
+#[derive(Debug, Clone)]
+pub struct BurnPrintAccounts {
+    pub master_edition_mint: Pubkey,
+    pub master_edition_token_account: Pubkey,
+    pub edition_marker: Pubkey,
+}
+
+///# Burn
+///
+/// Burns an asset of any token standard. Non-fungible assets are burnt completely, closing
+/// their token, metadata and edition accounts. Fungible assets burn `amount` tokens and close
+/// the metadata, refunding its rent to the update authority, once the mint supply reaches zero
+/// and no more tokens can be minted. Collection parents with verified items are only burnt when
+/// forced.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[writable, signer]` Owner of the token account
+///   2. `[writable]` Mint account
+///   3. `[writable]` Token account
+///   4. `[writable]` Edition account (pda of ['metadata', program id, mint id, 'edition']), may be uninitialized for fungible assets
+///   5. `[]` SPL Token program
+///   6. Print editions only `[]` Master edition mint
+///   7. Print editions only `[]` Master edition token account
+///   8. Print editions only `[writable]` Master edition account
+///   9. Print editions only `[writable]` Edition marker account
+///   10. Verified collection items only `[writable]` Collection metadata account
+///   11. Fungible assets only `[writable]` Update authority, receives the metadata rent
+///   12. Optional `[writable]` Use authority or collection authority record, its delegate and the payer of the record, in triples; only closed with the metadata
+#[allow(clippy::too_many_arguments)]
+pub fn burn(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    print: Option<BurnPrintAccounts>,
+    collection_metadata: Option<Pubkey>,
+    update_authority: Option<Pubkey>,
+    delegate_records: Vec<BurnDelegateRecord>,
+    amount: u64,
+    force: bool,
+) -> Instruction {
+    let (edition, _) = find_master_edition_account(&mint);
+
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(edition, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if let Some(print) = print {
+        let (master_edition, _) = find_master_edition_account(&print.master_edition_mint);
+        accounts.push(AccountMeta::new_readonly(print.master_edition_mint, false));
+        accounts.push(AccountMeta::new_readonly(
+            print.master_edition_token_account,
+            false,
+        ));
+        accounts.push(AccountMeta::new(master_edition, false));
+        accounts.push(AccountMeta::new(print.edition_marker, false));
+    }
+
+    if let Some(collection_metadata) = collection_metadata {
+        accounts.push(AccountMeta::new(collection_metadata, false));
+    }
+
+    if let Some(update_authority) = update_authority {
+        accounts.push(AccountMeta::new(update_authority, false));
+    }
+
+    for delegate_record in delegate_records {
+        accounts.push(AccountMeta::new(delegate_record.record, false));
+        accounts.push(AccountMeta::new_readonly(delegate_record.delegate, false));
+        accounts.push(AccountMeta::new(delegate_record.payer, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Burn(BurnArgs { amount, force })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_burn<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'a>],
+    args: BurnArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let spl_token_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(owner_info)?;
+    assert_token_program_matches_package(spl_token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // Burning a parent leaves its verified items pointing at a collection that no longer exists.
+    if let Some(CollectionDetails::V1 { size }) = metadata.collection_details {
+        if size > 0 && !args.force {
+            return Err(MetadataError::CollectionNotEmpty.into());
+        }
+    }
+
+    let in_verified_collection =
+        matches!(&metadata.collection, Some(collection) if collection.verified);
+
+    // Legacy fungibles have no token standard set, but never have an edition.
+    let is_fungible = match metadata.token_standard {
+        Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset) => true,
+        Some(_) => false,
+        None => edition_info.data_is_empty(),
+    };
+
+    let metadata_closed = if is_fungible {
+        msg!("Burning fungible asset");
+        assert_currently_holding(
+            program_id,
+            owner_info,
+            metadata_info,
+            &metadata,
+            mint_info,
+            token_info,
+        )?;
+
+        // The collection metadata is expected whenever the asset is verified so clients don't
+        // need to predict the remaining supply.
+        let collection_metadata_info = if in_verified_collection {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            None
+        };
+        let update_authority_info = next_account_info(account_info_iter)?;
+        if *update_authority_info.key != metadata.update_authority {
+            return Err(MetadataError::RentReceiverMismatch.into());
+        }
+
+        spl_token_burn(TokenBurnParams {
+            mint: mint_info.clone(),
+            source: token_info.clone(),
+            authority: owner_info.clone(),
+            token_program: spl_token_program_info.clone(),
+            amount: args.amount,
+            authority_signer_seeds: None,
+        })?;
+        emit_burn(mint_info, args.amount)?;
+
+        // The metadata is still needed while more tokens can be minted.
+        if get_mint_supply(mint_info)? == 0 && get_mint_authority(mint_info)?.is_none() {
+            msg!("Supply is zero, closing metadata");
+            if let Some(collection_metadata_info) = collection_metadata_info {
+                remove_from_collection(program_id, &metadata, collection_metadata_info)?;
+            }
+            close_account_raw(update_authority_info, metadata_info)?;
+            true
+        } else {
+            false
+        }
+    } else if edition_info.try_borrow_data()?.first() == Some(&(Key::EditionV1 as u8)) {
+        msg!("Burning print edition");
+        let master_edition_mint_info = next_account_info(account_info_iter)?;
+        let master_edition_token_info = next_account_info(account_info_iter)?;
+        let master_edition_info = next_account_info(account_info_iter)?;
+        let edition_marker_info = next_account_info(account_info_iter)?;
+
+        process_burn_edition_nft(
+            program_id,
+            &[
+                metadata_info.clone(),
+                owner_info.clone(),
+                mint_info.clone(),
+                master_edition_mint_info.clone(),
+                token_info.clone(),
+                master_edition_token_info.clone(),
+                master_edition_info.clone(),
+                edition_info.clone(),
+                edition_marker_info.clone(),
+                spl_token_program_info.clone(),
+            ],
+        )?;
+
+        if in_verified_collection {
+            remove_from_collection(program_id, &metadata, next_account_info(account_info_iter)?)?;
+        }
+        true
+    } else {
+        msg!("Burning master edition");
+        let mut nft_accounts = vec![
+            metadata_info.clone(),
+            owner_info.clone(),
+            mint_info.clone(),
+            token_info.clone(),
+            edition_info.clone(),
+            spl_token_program_info.clone(),
+        ];
+
+        if in_verified_collection {
+            nft_accounts.push(next_account_info(account_info_iter)?.clone());
+        }
+
+        process_burn_nft(program_id, &nft_accounts)?;
+        true
+    };
+
+    // Records of an asset that is still around stay usable by the other holders.
+    if metadata_closed {
+        close_delegate_records(program_id, mint_info.key, account_info_iter.as_slice())?;
+    }
+
+    Ok(())
+}
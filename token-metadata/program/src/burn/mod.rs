@@ -0,0 +1,107 @@
+pub mod burn_asset;
+
+pub use burn_asset::*;
+
+use crate::{
+    deser::clean_write_metadata,
+    error::MetadataError,
+    state::{
+        CollectionAuthorityRecord, CollectionDetails, Key, Metadata, TokenMetadataAccount,
+        UseAuthorityRecord, COLLECTION_AUTHORITY, PREFIX, USER,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_verified_member_of_collection, close_account_raw,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Decrements the size of the sized collection `metadata` is a verified member of. Unsized
+/// collections are left untouched.
+pub fn remove_from_collection(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    collection_metadata_info: &AccountInfo,
+) -> ProgramResult {
+    assert_owned_by(collection_metadata_info, program_id)?;
+
+    let mut collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    assert_verified_member_of_collection(metadata, &collection_metadata)?;
+
+    if let Some(CollectionDetails::V1 { size }) = collection_metadata.collection_details {
+        collection_metadata.collection_details = Some(CollectionDetails::V1 {
+            size: size
+                .checked_sub(1)
+                .ok_or(MetadataError::NumericalOverflowError)?,
+        });
+        clean_write_metadata(&mut collection_metadata, collection_metadata_info)?;
+    }
+
+    Ok(())
+}
+
+/// Closes use authority and collection authority records of `mint`, passed as
+/// (record, delegate, payer) triples, refunding their rent to the payer stored in each record.
+pub fn close_delegate_records<'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    record_infos: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let triples = record_infos.chunks_exact(3);
+    if !triples.remainder().is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for triple in triples {
+        let record_info = &triple[0];
+        let delegate_info = &triple[1];
+        let payer_info = &triple[2];
+
+        assert_owned_by(record_info, program_id)?;
+
+        let key = *record_info
+            .try_borrow_data()?
+            .first()
+            .ok_or(MetadataError::InvalidDelegateRecord)?;
+
+        let (role, error, payer) = if key == Key::UseAuthorityRecord as u8 {
+            (
+                USER,
+                MetadataError::InvalidUseAuthorityRecord,
+                UseAuthorityRecord::from_account_info(record_info)?.payer,
+            )
+        } else if key == Key::CollectionAuthorityRecord as u8 {
+            (
+                COLLECTION_AUTHORITY,
+                MetadataError::InvalidCollectionAuthorityRecord,
+                CollectionAuthorityRecord::from_account_info(record_info)?.payer,
+            )
+        } else {
+            return Err(MetadataError::InvalidDelegateRecord.into());
+        };
+
+        assert_derivation(
+            program_id,
+            record_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint.as_ref(),
+                role.as_bytes(),
+                delegate_info.key.as_ref(),
+            ],
+        )
+        .map_err(|_| error)?;
+
+        // Records approved before the payer was stored can't be refunded to anyone here.
+        if payer != Some(*payer_info.key) {
+            return Err(MetadataError::RentReceiverMismatch.into());
+        }
+
+        close_account_raw(payer_info, record_info)?;
+    }
+
+    Ok(())
+}
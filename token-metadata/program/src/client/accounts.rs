@@ -0,0 +1,126 @@
+use num_traits::FromPrimitive;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::MetadataError,
+    state::{
+        CollectionAuthorityRecord, CollectionCreators, CollectionRoyalties,
+        CollectionValidationProfile, CreatorsProposal, Edition, EditionMarker, FungibleConfig, Key,
+        MasterEditionV1, MasterEditionV2, Metadata, MetadataAttributes, MetadataDelegateRecord,
+        PrintConfig, PrintRecord, ReservationListV1, ReservationListV2, RuleSet,
+        TokenMetadataAccount, TokenOwnedEscrow, UseAuthorityRecord, UseSchedule,
+    },
+};
+
+/// Any account owned by the token metadata program, decoded according to its [`Key`].
+#[derive(PartialEq, Debug, Clone)]
+pub enum TokenMetadataAccountData {
+    Edition(Edition),
+    MasterEditionV1(MasterEditionV1),
+    ReservationListV1(ReservationListV1),
+    Metadata(Metadata),
+    ReservationListV2(ReservationListV2),
+    MasterEditionV2(MasterEditionV2),
+    EditionMarker(EditionMarker),
+    UseAuthorityRecord(UseAuthorityRecord),
+    CollectionAuthorityRecord(CollectionAuthorityRecord),
+    TokenOwnedEscrow(TokenOwnedEscrow),
+    RuleSet(RuleSet),
+    MetadataDelegateRecord(MetadataDelegateRecord),
+    MetadataAttributes(MetadataAttributes),
+    CollectionRoyalties(CollectionRoyalties),
+    PrintConfig(PrintConfig),
+    PrintRecord(PrintRecord),
+    UseSchedule(UseSchedule),
+    CollectionCreators(CollectionCreators),
+    CreatorsProposal(CreatorsProposal),
+    FungibleConfig(FungibleConfig),
+    CollectionValidationProfile(CollectionValidationProfile),
+}
+
+impl TokenMetadataAccountData {
+    /// Decodes `data` as the account type named by its leading [`Key`] byte.
+    pub fn from_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        let key = data
+            .first()
+            .and_then(|key| Key::from_u8(*key))
+            .ok_or(MetadataError::DataTypeMismatch)?;
+
+        let account = match key {
+            Key::Uninitialized => return Err(MetadataError::Uninitialized.into()),
+            Key::EditionV1 => Self::Edition(fetch(data)?),
+            Key::MasterEditionV1 => Self::MasterEditionV1(fetch(data)?),
+            Key::ReservationListV1 => Self::ReservationListV1(fetch(data)?),
+            Key::MetadataV1 => Self::Metadata(fetch(data)?),
+            Key::ReservationListV2 => Self::ReservationListV2(fetch(data)?),
+            Key::MasterEditionV2 => Self::MasterEditionV2(fetch(data)?),
+            Key::EditionMarker => Self::EditionMarker(fetch(data)?),
+            Key::UseAuthorityRecord => Self::UseAuthorityRecord(fetch(data)?),
+            Key::CollectionAuthorityRecord => Self::CollectionAuthorityRecord(fetch(data)?),
+            Key::TokenOwnedEscrow => Self::TokenOwnedEscrow(fetch(data)?),
+            Key::RuleSet => Self::RuleSet(fetch(data)?),
+            Key::MetadataDelegate => Self::MetadataDelegateRecord(fetch(data)?),
+            Key::MetadataAttributes => Self::MetadataAttributes(fetch(data)?),
+            Key::CollectionRoyalties => Self::CollectionRoyalties(fetch(data)?),
+            Key::PrintConfig => Self::PrintConfig(fetch(data)?),
+            Key::PrintRecord => Self::PrintRecord(fetch(data)?),
+            Key::UseSchedule => Self::UseSchedule(fetch(data)?),
+            Key::CollectionCreators => Self::CollectionCreators(fetch(data)?),
+            Key::CreatorsProposal => Self::CreatorsProposal(fetch(data)?),
+            Key::FungibleConfig => Self::FungibleConfig(fetch(data)?),
+            Key::CollectionValidationProfile => Self::CollectionValidationProfile(fetch(data)?),
+        };
+
+        Ok(account)
+    }
+
+    /// Same as [`Self::from_account_data`], but first checks the account is owned by this program.
+    pub fn from_account(owner: &Pubkey, data: &[u8]) -> Result<Self, ProgramError> {
+        if *owner != crate::ID {
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        Self::from_account_data(data)
+    }
+}
+
+/// Decodes `data` as `T`, failing when the account holds a different type.
+pub fn fetch<T: TokenMetadataAccount>(data: &[u8]) -> Result<T, ProgramError> {
+    T::safe_deserialize(data).map_err(|_| MetadataError::DataTypeMismatch.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+
+    fn use_authority_record_data() -> Vec<u8> {
+        let mut data = UseAuthorityRecord::default().try_to_vec().unwrap();
+        UseAuthorityRecord::pad_length(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn decodes_account_by_key() {
+        let account =
+            TokenMetadataAccountData::from_account_data(&use_authority_record_data()).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountData::UseAuthorityRecord(UseAuthorityRecord::default())
+        );
+    }
+
+    #[test]
+    fn fails_for_uninitialized_or_foreign_accounts() {
+        let data = use_authority_record_data();
+
+        assert!(TokenMetadataAccountData::from_account_data(&[]).is_err());
+        assert!(TokenMetadataAccountData::from_account_data(&[0; 8]).is_err());
+        assert!(TokenMetadataAccountData::from_account(&Pubkey::new_unique(), &data).is_err());
+        assert!(TokenMetadataAccountData::from_account(&crate::ID, &data).is_ok());
+        assert!(fetch::<CollectionAuthorityRecord>(&data).is_err());
+    }
+}
@@ -0,0 +1,1204 @@
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    burn::{self, BurnDelegateRecord, BurnPrintAccounts},
+    collection, edition,
+    escrow::{self, find_escrow_account},
+    fungible, instruction,
+    pda::{
+        find_collection_authority_account, find_collection_creators_account,
+        find_collection_validation_profile_account, find_edition_marker_account,
+        find_fungible_config_account, find_master_edition_account, find_metadata_account,
+        find_metadata_delegate_record_account, find_print_config_account,
+        find_program_as_burner_account, find_rule_set_account, find_use_authority_account,
+    },
+    programmable,
+    state::{CollectionDetails, DataV2, EscrowAuthority, MetadataDelegateRole, PrintPrice},
+    update::{self, UpdateArgs},
+};
+
+///# Create Metadata Account V3
+///
+///Creates the metadata account of `mint` at its derived address.
+pub struct CreateMetadataAccountV3 {
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub payer: Pubkey,
+    pub update_authority: Pubkey,
+    pub update_authority_is_signer: bool,
+    pub data: DataV2,
+    pub is_mutable: bool,
+    pub collection_details: Option<CollectionDetails>,
+}
+
+impl CreateMetadataAccountV3 {
+    pub fn instruction(&self) -> Instruction {
+        let data = self.data.clone();
+        instruction::create_metadata_accounts_v3(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            self.mint_authority,
+            self.payer,
+            self.update_authority,
+            data.name,
+            data.symbol,
+            data.uri,
+            data.creators,
+            data.seller_fee_basis_points,
+            self.update_authority_is_signer,
+            self.is_mutable,
+            data.collection,
+            data.uses,
+            self.collection_details.clone(),
+        )
+    }
+}
+
+///# Update Metadata Account V2
+///
+///Updates the metadata account of `mint`, fields left as `None` are unchanged.
+pub struct UpdateMetadataAccountV2 {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub new_update_authority: Option<Pubkey>,
+    pub data: Option<DataV2>,
+    pub primary_sale_happened: Option<bool>,
+    pub is_mutable: Option<bool>,
+}
+
+impl UpdateMetadataAccountV2 {
+    pub fn instruction(&self) -> Instruction {
+        instruction::update_metadata_accounts_v2(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.update_authority,
+            self.new_update_authority,
+            self.data.clone(),
+            self.primary_sale_happened,
+            self.is_mutable,
+        )
+    }
+}
+
+///# Create Master Edition V3
+///
+///Creates the master edition of `mint`, which must already have its metadata account.
+pub struct CreateMasterEditionV3 {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub mint_authority: Pubkey,
+    pub payer: Pubkey,
+    pub max_supply: Option<u64>,
+}
+
+impl CreateMasterEditionV3 {
+    pub fn instruction(&self) -> Instruction {
+        instruction::create_master_edition_v3(
+            crate::ID,
+            find_master_edition_account(&self.mint).0,
+            self.mint,
+            self.update_authority,
+            self.mint_authority,
+            find_metadata_account(&self.mint).0,
+            self.payer,
+            self.max_supply,
+        )
+    }
+}
+
+///# Mint New Edition From Master Edition Via Token
+///
+///Prints `edition` of the master edition of `master_mint` into `new_mint`.
+pub struct MintNewEditionFromMasterEditionViaToken {
+    pub master_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub new_mint_authority: Pubkey,
+    pub new_metadata_update_authority: Pubkey,
+    pub payer: Pubkey,
+    pub token_account_owner: Pubkey,
+    /// Token account of `token_account_owner` holding the master edition token
+    pub token_account: Pubkey,
+    pub edition: u64,
+}
+
+impl MintNewEditionFromMasterEditionViaToken {
+    pub fn instruction(&self) -> Instruction {
+        instruction::mint_new_edition_from_master_edition_via_token(
+            crate::ID,
+            find_metadata_account(&self.new_mint).0,
+            find_master_edition_account(&self.new_mint).0,
+            find_master_edition_account(&self.master_mint).0,
+            self.new_mint,
+            self.new_mint_authority,
+            self.payer,
+            self.token_account_owner,
+            self.token_account,
+            self.new_metadata_update_authority,
+            find_metadata_account(&self.master_mint).0,
+            self.master_mint,
+            self.edition,
+        )
+    }
+
+    /// Edition marker recording that `edition` was printed.
+    pub fn edition_marker(&self) -> Pubkey {
+        find_edition_marker_account(&self.master_mint, self.edition).0
+    }
+}
+
+///# Sign Metadata
+///
+///Verifies `creator` on the metadata account of `mint`.
+pub struct SignMetadata {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl SignMetadata {
+    pub fn instruction(&self) -> Instruction {
+        instruction::sign_metadata(crate::ID, find_metadata_account(&self.mint).0, self.creator)
+    }
+}
+
+///# Verify Collection
+///
+///Verifies the item `mint` as a member of the collection of `collection_mint`. Set `sized` for
+///sized collection parents, and `delegated` when `collection_authority` signs through a
+///collection authority record.
+pub struct VerifyCollection {
+    pub mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub collection_authority: Pubkey,
+    pub payer: Pubkey,
+    pub sized: bool,
+    pub delegated: bool,
+}
+
+impl VerifyCollection {
+    pub fn instruction(&self) -> Instruction {
+        let verify = if self.sized {
+            instruction::verify_sized_collection_item
+        } else {
+            instruction::verify_collection
+        };
+
+        verify(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.collection_authority,
+            self.payer,
+            self.collection_mint,
+            find_metadata_account(&self.collection_mint).0,
+            find_master_edition_account(&self.collection_mint).0,
+            collection_authority_record(
+                self.delegated,
+                &self.collection_mint,
+                &self.collection_authority,
+            ),
+        )
+    }
+}
+
+///# Unverify Collection
+///
+///Removes the verification of the item `mint` from the collection of `collection_mint`.
+pub struct UnverifyCollection {
+    pub mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub collection_authority: Pubkey,
+    /// Only used by sized collections
+    pub payer: Pubkey,
+    pub sized: bool,
+    pub delegated: bool,
+}
+
+impl UnverifyCollection {
+    pub fn instruction(&self) -> Instruction {
+        let metadata = find_metadata_account(&self.mint).0;
+        let collection = find_metadata_account(&self.collection_mint).0;
+        let collection_master_edition = find_master_edition_account(&self.collection_mint).0;
+        let collection_authority_record = collection_authority_record(
+            self.delegated,
+            &self.collection_mint,
+            &self.collection_authority,
+        );
+
+        if self.sized {
+            instruction::unverify_sized_collection_item(
+                crate::ID,
+                metadata,
+                self.collection_authority,
+                self.payer,
+                self.collection_mint,
+                collection,
+                collection_master_edition,
+                collection_authority_record,
+            )
+        } else {
+            instruction::unverify_collection(
+                crate::ID,
+                metadata,
+                self.collection_authority,
+                self.collection_mint,
+                collection,
+                collection_master_edition,
+                collection_authority_record,
+            )
+        }
+    }
+}
+
+///# Approve Collection Authority
+///
+///Delegates verification of items in the collection of `mint` to `new_collection_authority`.
+pub struct ApproveCollectionAuthority {
+    pub mint: Pubkey,
+    pub new_collection_authority: Pubkey,
+    pub update_authority: Pubkey,
+    pub payer: Pubkey,
+    pub permissions: u8,
+    pub expiry: Option<i64>,
+}
+
+impl ApproveCollectionAuthority {
+    pub fn instruction(&self) -> Instruction {
+        instruction::approve_collection_authority_v2(
+            crate::ID,
+            find_collection_authority_account(&self.mint, &self.new_collection_authority).0,
+            self.new_collection_authority,
+            self.update_authority,
+            self.payer,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            self.permissions,
+            self.expiry,
+        )
+    }
+}
+
+///# Revoke Collection Authority
+pub struct RevokeCollectionAuthority {
+    pub mint: Pubkey,
+    pub delegate_authority: Pubkey,
+    pub revoke_authority: Pubkey,
+}
+
+impl RevokeCollectionAuthority {
+    pub fn instruction(&self) -> Instruction {
+        instruction::revoke_collection_authority(
+            crate::ID,
+            find_collection_authority_account(&self.mint, &self.delegate_authority).0,
+            self.delegate_authority,
+            self.revoke_authority,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+        )
+    }
+}
+
+///# Approve Use Authority
+///
+///Lets `user` call [`Utilize`] on `mint` up to `number_of_uses` times.
+pub struct ApproveUseAuthority {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub owner: Pubkey,
+    pub payer: Pubkey,
+    pub owner_token_account: Pubkey,
+    pub number_of_uses: u64,
+    pub expiry: Option<i64>,
+    pub caller_program: Option<Pubkey>,
+}
+
+impl ApproveUseAuthority {
+    pub fn instruction(&self) -> Instruction {
+        instruction::approve_use_authority_v2(
+            crate::ID,
+            find_use_authority_account(&self.mint, &self.user).0,
+            self.user,
+            self.owner,
+            self.payer,
+            self.owner_token_account,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            find_program_as_burner_account().0,
+            self.number_of_uses,
+            self.expiry,
+            self.caller_program,
+        )
+    }
+}
+
+///# Revoke Use Authority
+pub struct RevokeUseAuthority {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub owner: Pubkey,
+    pub owner_token_account: Pubkey,
+}
+
+impl RevokeUseAuthority {
+    pub fn instruction(&self) -> Instruction {
+        instruction::revoke_use_authority(
+            crate::ID,
+            find_use_authority_account(&self.mint, &self.user).0,
+            self.user,
+            self.owner,
+            self.owner_token_account,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+        )
+    }
+}
+
+///# Utilize
+///
+///Consumes `number_of_uses` uses of `mint`. Set `delegated` when `use_authority` is not the owner
+///and signs through a use authority record.
+pub struct Utilize {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub use_authority: Pubkey,
+    pub owner: Pubkey,
+    pub number_of_uses: u64,
+    pub delegated: bool,
+}
+
+impl Utilize {
+    pub fn instruction(&self) -> Instruction {
+        let (use_authority_record, burner) = if self.delegated {
+            (
+                Some(find_use_authority_account(&self.mint, &self.use_authority).0),
+                Some(find_program_as_burner_account().0),
+            )
+        } else {
+            (None, None)
+        };
+
+        instruction::utilize(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.token_account,
+            self.mint,
+            use_authority_record,
+            self.use_authority,
+            self.owner,
+            burner,
+            self.number_of_uses,
+        )
+    }
+}
+
+///# Burn NFT
+///
+///Burns the token, metadata and master edition of `mint`. Pass the collection mint when the item
+///is a verified member of a sized collection.
+pub struct BurnNft {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub collection_mint: Option<Pubkey>,
+}
+
+impl BurnNft {
+    pub fn instruction(&self) -> Instruction {
+        instruction::burn_nft(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.owner,
+            self.mint,
+            self.token_account,
+            find_master_edition_account(&self.mint).0,
+            spl_token::ID,
+            self.collection_mint
+                .map(|collection_mint| find_metadata_account(&collection_mint).0),
+        )
+    }
+}
+
+///# Transfer
+///
+///Moves a programmable NFT from `source` to the associated token account of `destination_owner`.
+///List the creators of the metadata when `sale_price` is set, and set `rule_set` when
+///`authority` relies on the allowlist of the rule set of `mint`.
+pub struct Transfer {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub authority: Pubkey,
+    pub destination_owner: Pubkey,
+    pub payer: Pubkey,
+    pub creators: Vec<Pubkey>,
+    pub amount: u64,
+    pub sale_price: Option<u64>,
+    pub rule_set: bool,
+}
+
+impl Transfer {
+    pub fn instruction(&self) -> Instruction {
+        let mut instruction = programmable::transfer(
+            crate::ID,
+            self.source,
+            self.authority,
+            self.destination(),
+            self.destination_owner,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            find_master_edition_account(&self.mint).0,
+            self.payer,
+            self.creators.clone(),
+            self.amount,
+            self.sale_price,
+        );
+
+        if self.rule_set {
+            instruction.accounts.push(AccountMeta::new_readonly(
+                find_rule_set_account(&self.mint).0,
+                false,
+            ));
+        }
+
+        instruction
+    }
+
+    /// Token account receiving the NFT, created by the transfer when missing.
+    pub fn destination(&self) -> Pubkey {
+        get_associated_token_address(&self.destination_owner, &self.mint)
+    }
+}
+
+///# Update
+///
+///Updates the metadata of `mint`. Set `delegate_role` when `authority` is a metadata delegate,
+///`payer` to fund growing a resized account, and `collection_mint` when renaming a verified item
+///of a collection with a validation profile.
+pub struct Update {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub delegate_role: Option<MetadataDelegateRole>,
+    pub payer: Option<Pubkey>,
+    pub collection_mint: Option<Pubkey>,
+    pub args: UpdateArgs,
+}
+
+impl Update {
+    pub fn instruction(&self) -> Instruction {
+        update::update(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.authority,
+            self.mint,
+            find_master_edition_account(&self.mint).0,
+            self.delegate_role.map(|role| {
+                find_metadata_delegate_record_account(&self.mint, role, &self.authority).0
+            }),
+            self.payer,
+            self.collection_mint.map(|collection_mint| {
+                find_collection_validation_profile_account(&collection_mint).0
+            }),
+            self.args.clone(),
+        )
+    }
+}
+
+/// Master edition a print edition being burnt was printed from.
+pub struct BurnPrint {
+    pub master_mint: Pubkey,
+    pub master_token_account: Pubkey,
+    pub edition: u64,
+}
+
+///# Burn
+///
+///Burns an asset of any token standard. Pass `print` for print editions, the collection mint
+///for verified items of a collection, and the update authority for fungible assets.
+pub struct Burn {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub print: Option<BurnPrint>,
+    pub collection_mint: Option<Pubkey>,
+    pub update_authority: Option<Pubkey>,
+    pub delegate_records: Vec<BurnDelegateRecord>,
+    pub amount: u64,
+    pub force: bool,
+}
+
+impl Burn {
+    pub fn instruction(&self) -> Instruction {
+        burn::burn(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.owner,
+            self.mint,
+            self.token_account,
+            self.print.as_ref().map(|print| BurnPrintAccounts {
+                master_edition_mint: print.master_mint,
+                master_edition_token_account: print.master_token_account,
+                edition_marker: find_edition_marker_account(&print.master_mint, print.edition).0,
+            }),
+            self.collection_mint
+                .map(|collection_mint| find_metadata_account(&collection_mint).0),
+            self.update_authority,
+            self.delegate_records.clone(),
+            self.amount,
+            self.force,
+        )
+    }
+}
+
+///# Create Escrow Account
+///
+///Creates the escrow of `mint` held by the owner of `token_account`, or by `creator` when set.
+pub struct CreateEscrowAccount {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub payer: Pubkey,
+    pub creator: Option<Pubkey>,
+}
+
+impl CreateEscrowAccount {
+    pub fn instruction(&self) -> Instruction {
+        escrow::create_escrow_account(
+            crate::ID,
+            self.escrow(),
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            self.token_account,
+            find_master_edition_account(&self.mint).0,
+            self.payer,
+            self.creator,
+        )
+    }
+
+    pub fn escrow(&self) -> Pubkey {
+        find_escrow_account(&self.mint, &escrow_authority(self.creator)).0
+    }
+}
+
+///# Close Escrow Account
+///
+///Closes the escrow of `mint` held by the owner of `token_account`, or by `creator` when set.
+pub struct CloseEscrowAccount {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub payer: Pubkey,
+    pub creator: Option<Pubkey>,
+}
+
+impl CloseEscrowAccount {
+    pub fn instruction(&self) -> Instruction {
+        escrow::close_escrow_account(
+            crate::ID,
+            find_escrow_account(&self.mint, &escrow_authority(self.creator)).0,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            find_master_edition_account(&self.mint).0,
+            self.payer,
+            self.token_account,
+        )
+    }
+}
+
+///# Transfer Into Escrow
+///
+///Moves `amount` tokens of `attribute_mint` from `attribute_source` into the escrow of
+///`escrow_mint`. `creator` selects a creator escrow instead of the token owner escrow, and
+///`ancestor_escrows` lists the escrows holding this one, from the direct parent up to the root.
+pub struct TransferIntoEscrow {
+    pub escrow_mint: Pubkey,
+    pub escrow_token_account: Pubkey,
+    pub creator: Option<Pubkey>,
+    pub payer: Pubkey,
+    pub attribute_mint: Pubkey,
+    pub attribute_source: Pubkey,
+    pub ancestor_escrows: Vec<Pubkey>,
+    pub amount: u64,
+}
+
+impl TransferIntoEscrow {
+    pub fn instruction(&self) -> Instruction {
+        let escrow = find_escrow_account(&self.escrow_mint, &escrow_authority(self.creator)).0;
+
+        escrow::transfer_into_escrow(
+            crate::ID,
+            escrow,
+            find_metadata_account(&self.escrow_mint).0,
+            self.payer,
+            self.attribute_mint,
+            self.attribute_source,
+            get_associated_token_address(&escrow, &self.attribute_mint),
+            self.escrow_mint,
+            self.escrow_token_account,
+            self.ancestor_escrows.clone(),
+            self.amount,
+        )
+    }
+}
+
+///# Transfer Out Of Escrow
+///
+///Moves `amount` tokens of `attribute_mint` out of the escrow of `escrow_mint` to the associated
+///token account of `payer`. Set `nested` when the tokens own an escrow themselves.
+pub struct TransferOutOfEscrow {
+    pub escrow_mint: Pubkey,
+    pub escrow_token_account: Pubkey,
+    pub creator: Option<Pubkey>,
+    pub payer: Pubkey,
+    /// Signs in place of `payer`, when set
+    pub authority: Option<Pubkey>,
+    pub attribute_mint: Pubkey,
+    pub nested: bool,
+    pub ancestor_escrows: Vec<Pubkey>,
+    pub amount: u64,
+}
+
+impl TransferOutOfEscrow {
+    pub fn instruction(&self) -> Instruction {
+        let escrow = find_escrow_account(&self.escrow_mint, &escrow_authority(self.creator)).0;
+        let nested_escrow = if self.nested {
+            Some(find_escrow_account(&self.attribute_mint, &EscrowAuthority::TokenOwner).0)
+        } else {
+            None
+        };
+
+        escrow::transfer_out_of_escrow(
+            crate::ID,
+            escrow,
+            find_metadata_account(&self.escrow_mint).0,
+            self.payer,
+            self.attribute_mint,
+            get_associated_token_address(&escrow, &self.attribute_mint),
+            get_associated_token_address(&self.payer, &self.attribute_mint),
+            self.escrow_mint,
+            self.escrow_token_account,
+            self.authority,
+            nested_escrow,
+            self.ancestor_escrows.clone(),
+            self.amount,
+        )
+    }
+}
+
+///# Set Collection Creators
+///
+///Lets `creators` verify items of the collection of `collection_mint`.
+pub struct SetCollectionCreators {
+    pub collection_mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub payer: Pubkey,
+    pub creators: Vec<Pubkey>,
+}
+
+impl SetCollectionCreators {
+    pub fn instruction(&self) -> Instruction {
+        collection::set_collection_creators(
+            crate::ID,
+            find_collection_creators_account(&self.collection_mint).0,
+            find_metadata_account(&self.collection_mint).0,
+            self.collection_mint,
+            self.update_authority,
+            self.payer,
+            self.creators.clone(),
+        )
+    }
+}
+
+///# Remove Collection Creators
+pub struct RemoveCollectionCreators {
+    pub collection_mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub receiver: Pubkey,
+}
+
+impl RemoveCollectionCreators {
+    pub fn instruction(&self) -> Instruction {
+        collection::remove_collection_creators(
+            crate::ID,
+            find_collection_creators_account(&self.collection_mint).0,
+            find_metadata_account(&self.collection_mint).0,
+            self.collection_mint,
+            self.update_authority,
+            self.receiver,
+        )
+    }
+}
+
+///# Verify Collection Item As Creator
+///
+///Verifies the item `mint` in the collection of `collection_mint` on behalf of `creator`.
+pub struct VerifyCollectionItemAsCreator {
+    pub mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl VerifyCollectionItemAsCreator {
+    pub fn instruction(&self) -> Instruction {
+        collection::verify_collection_item_as_creator(
+            crate::ID,
+            find_metadata_account(&self.mint).0,
+            self.creator,
+            self.collection_mint,
+            find_metadata_account(&self.collection_mint).0,
+            find_master_edition_account(&self.collection_mint).0,
+            find_collection_creators_account(&self.collection_mint).0,
+        )
+    }
+}
+
+///# Set Print Config
+///
+///Sets the sale rules of the prints of the master edition of `mint`.
+pub struct SetPrintConfig {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub payer: Pubkey,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub max_per_wallet: Option<u64>,
+    pub price: Option<PrintPrice>,
+}
+
+impl SetPrintConfig {
+    pub fn instruction(&self) -> Instruction {
+        edition::set_print_config(
+            crate::ID,
+            find_print_config_account(&self.mint).0,
+            find_master_edition_account(&self.mint).0,
+            find_metadata_account(&self.mint).0,
+            self.update_authority,
+            self.payer,
+            self.start_time,
+            self.end_time,
+            self.max_per_wallet,
+            self.price.clone(),
+        )
+    }
+}
+
+///# Remove Print Config
+pub struct RemovePrintConfig {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub receiver: Pubkey,
+}
+
+impl RemovePrintConfig {
+    pub fn instruction(&self) -> Instruction {
+        edition::remove_print_config(
+            crate::ID,
+            find_print_config_account(&self.mint).0,
+            find_master_edition_account(&self.mint).0,
+            find_metadata_account(&self.mint).0,
+            self.update_authority,
+            self.receiver,
+        )
+    }
+}
+
+///# Create Fungible Config
+///
+///Caps the supply of the fungible asset `mint` at `max_supply`.
+pub struct CreateFungibleConfig {
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub update_authority: Pubkey,
+    pub payer: Pubkey,
+    pub max_supply: u64,
+}
+
+impl CreateFungibleConfig {
+    pub fn instruction(&self) -> Instruction {
+        fungible::create_fungible_config(
+            crate::ID,
+            find_fungible_config_account(&self.mint).0,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            self.mint_authority,
+            self.update_authority,
+            self.payer,
+            self.max_supply,
+        )
+    }
+}
+
+///# Mint Fungible
+///
+///Mints `amount` tokens of the capped fungible asset `mint` into `destination`.
+pub struct MintFungible {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub mint_authority: Pubkey,
+    pub amount: u64,
+}
+
+impl MintFungible {
+    pub fn instruction(&self) -> Instruction {
+        fungible::mint_fungible(
+            crate::ID,
+            find_fungible_config_account(&self.mint).0,
+            self.mint,
+            self.destination,
+            self.mint_authority,
+            self.amount,
+        )
+    }
+}
+
+///# Update Fungible Config
+///
+///Hands minting of `mint` to `new_mint_authority` and lowers its max supply, when set.
+pub struct UpdateFungibleConfig {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub new_mint_authority: Option<Pubkey>,
+    pub max_supply: Option<u64>,
+}
+
+impl UpdateFungibleConfig {
+    pub fn instruction(&self) -> Instruction {
+        fungible::update_fungible_config(
+            crate::ID,
+            find_fungible_config_account(&self.mint).0,
+            find_metadata_account(&self.mint).0,
+            self.mint,
+            self.update_authority,
+            self.new_mint_authority,
+            self.max_supply,
+        )
+    }
+}
+
+fn escrow_authority(creator: Option<Pubkey>) -> EscrowAuthority {
+    match creator {
+        Some(creator) => EscrowAuthority::Creator(creator),
+        None => EscrowAuthority::TokenOwner,
+    }
+}
+
+fn collection_authority_record(
+    delegated: bool,
+    collection_mint: &Pubkey,
+    collection_authority: &Pubkey,
+) -> Option<Pubkey> {
+    if delegated {
+        Some(find_collection_authority_account(collection_mint, collection_authority).0)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_edition_marker_of_printed_edition() {
+        let builder = MintNewEditionFromMasterEditionViaToken {
+            master_mint: Pubkey::new_unique(),
+            new_mint: Pubkey::new_unique(),
+            new_mint_authority: Pubkey::new_unique(),
+            new_metadata_update_authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            token_account_owner: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            edition: 300,
+        };
+        let instruction = builder.instruction();
+
+        assert_eq!(instruction.accounts[4].pubkey, builder.edition_marker());
+        assert_eq!(
+            builder.edition_marker(),
+            crate::pda::find_edition_account(&builder.master_mint, "1".to_string()).0
+        );
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            find_master_edition_account(&builder.master_mint).0
+        );
+    }
+
+    #[test]
+    fn adds_use_authority_record_only_when_delegated() {
+        let mut builder = Utilize {
+            mint: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            use_authority: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            number_of_uses: 1,
+            delegated: false,
+        };
+        assert_eq!(builder.instruction().accounts.len(), 8);
+
+        builder.delegated = true;
+        let accounts = builder.instruction().accounts;
+        assert_eq!(accounts.len(), 10);
+        assert_eq!(
+            accounts[8].pubkey,
+            find_use_authority_account(&builder.mint, &builder.use_authority).0
+        );
+    }
+
+    #[test]
+    fn derives_accounts_of_transfer_update_and_burn() {
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let destination_owner = Pubkey::new_unique();
+        let metadata = find_metadata_account(&mint).0;
+        let edition = find_master_edition_account(&mint).0;
+
+        let transfer = Transfer {
+            mint,
+            source: Pubkey::new_unique(),
+            authority,
+            destination_owner,
+            payer: Pubkey::new_unique(),
+            creators: vec![],
+            amount: 1,
+            sale_price: None,
+            rule_set: true,
+        };
+        let accounts = transfer.instruction().accounts;
+        assert_eq!(
+            accounts[2].pubkey,
+            get_associated_token_address(&destination_owner, &mint)
+        );
+        assert_eq!(accounts[4].pubkey, metadata);
+        assert_eq!(accounts[6].pubkey, edition);
+        assert_eq!(accounts[11].pubkey, find_rule_set_account(&mint).0);
+
+        let collection_mint = Pubkey::new_unique();
+        let update = Update {
+            mint,
+            authority,
+            delegate_role: Some(MetadataDelegateRole::Uri),
+            payer: None,
+            collection_mint: Some(collection_mint),
+            args: UpdateArgs::default(),
+        };
+        let accounts = update.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, metadata);
+        assert_eq!(accounts[3].pubkey, edition);
+        assert_eq!(
+            accounts[4].pubkey,
+            find_metadata_delegate_record_account(&mint, MetadataDelegateRole::Uri, &authority).0
+        );
+        assert_eq!(
+            accounts[5].pubkey,
+            find_collection_validation_profile_account(&collection_mint).0
+        );
+
+        let master_mint = Pubkey::new_unique();
+        let burn = Burn {
+            mint,
+            owner: authority,
+            token_account: Pubkey::new_unique(),
+            print: Some(BurnPrint {
+                master_mint,
+                master_token_account: Pubkey::new_unique(),
+                edition: 300,
+            }),
+            collection_mint: Some(collection_mint),
+            update_authority: None,
+            delegate_records: vec![],
+            amount: 1,
+            force: false,
+        };
+        let accounts = burn.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, metadata);
+        assert_eq!(accounts[4].pubkey, edition);
+        assert_eq!(
+            accounts[8].pubkey,
+            find_master_edition_account(&master_mint).0
+        );
+        assert_eq!(
+            accounts[9].pubkey,
+            find_edition_marker_account(&master_mint, 300).0
+        );
+        assert_eq!(
+            accounts[10].pubkey,
+            find_metadata_account(&collection_mint).0
+        );
+    }
+
+    #[test]
+    fn derives_escrow_accounts() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let attribute_mint = Pubkey::new_unique();
+
+        let create = CreateEscrowAccount {
+            mint,
+            token_account: Pubkey::new_unique(),
+            payer,
+            creator: Some(creator),
+        };
+        let creator_escrow = find_escrow_account(&mint, &EscrowAuthority::Creator(creator)).0;
+        let accounts = create.instruction().accounts;
+        assert_eq!(create.escrow(), creator_escrow);
+        assert_eq!(accounts[0].pubkey, creator_escrow);
+        assert_eq!(accounts[1].pubkey, find_metadata_account(&mint).0);
+        assert_eq!(accounts[4].pubkey, find_master_edition_account(&mint).0);
+
+        let escrow = find_escrow_account(&mint, &EscrowAuthority::TokenOwner).0;
+        let close = CloseEscrowAccount {
+            mint,
+            token_account: Pubkey::new_unique(),
+            payer,
+            creator: None,
+        };
+        assert_eq!(close.instruction().accounts[0].pubkey, escrow);
+
+        let transfer_in = TransferIntoEscrow {
+            escrow_mint: mint,
+            escrow_token_account: Pubkey::new_unique(),
+            creator: None,
+            payer,
+            attribute_mint,
+            attribute_source: Pubkey::new_unique(),
+            ancestor_escrows: vec![],
+            amount: 1,
+        };
+        let accounts = transfer_in.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, escrow);
+        assert_eq!(
+            accounts[5].pubkey,
+            get_associated_token_address(&escrow, &attribute_mint)
+        );
+        assert_eq!(
+            accounts[11].pubkey,
+            find_escrow_account(&attribute_mint, &EscrowAuthority::TokenOwner).0
+        );
+
+        let transfer_out = TransferOutOfEscrow {
+            escrow_mint: mint,
+            escrow_token_account: Pubkey::new_unique(),
+            creator: None,
+            payer,
+            authority: None,
+            attribute_mint,
+            nested: true,
+            ancestor_escrows: vec![],
+            amount: 1,
+        };
+        let accounts = transfer_out.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, escrow);
+        assert_eq!(
+            accounts[4].pubkey,
+            get_associated_token_address(&escrow, &attribute_mint)
+        );
+        assert_eq!(
+            accounts[5].pubkey,
+            get_associated_token_address(&payer, &attribute_mint)
+        );
+        assert_eq!(
+            accounts[12].pubkey,
+            find_escrow_account(&attribute_mint, &EscrowAuthority::TokenOwner).0
+        );
+    }
+
+    #[test]
+    fn derives_collection_creators_print_and_fungible_configs() {
+        let mint = Pubkey::new_unique();
+        let collection_mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let collection_creators = find_collection_creators_account(&collection_mint).0;
+
+        let set_creators = SetCollectionCreators {
+            collection_mint,
+            update_authority: authority,
+            payer: authority,
+            creators: vec![],
+        };
+        let accounts = set_creators.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, collection_creators);
+        assert_eq!(
+            accounts[1].pubkey,
+            find_metadata_account(&collection_mint).0
+        );
+
+        let remove_creators = RemoveCollectionCreators {
+            collection_mint,
+            update_authority: authority,
+            receiver: authority,
+        };
+        assert_eq!(
+            remove_creators.instruction().accounts[0].pubkey,
+            collection_creators
+        );
+
+        let verify = VerifyCollectionItemAsCreator {
+            mint,
+            collection_mint,
+            creator: authority,
+        };
+        let accounts = verify.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, find_metadata_account(&mint).0);
+        assert_eq!(
+            accounts[4].pubkey,
+            find_master_edition_account(&collection_mint).0
+        );
+        assert_eq!(accounts[5].pubkey, collection_creators);
+
+        let set_print_config = SetPrintConfig {
+            mint,
+            update_authority: authority,
+            payer: authority,
+            start_time: None,
+            end_time: None,
+            max_per_wallet: Some(1),
+            price: None,
+        };
+        let accounts = set_print_config.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, find_print_config_account(&mint).0);
+        assert_eq!(accounts[1].pubkey, find_master_edition_account(&mint).0);
+        assert_eq!(accounts[2].pubkey, find_metadata_account(&mint).0);
+
+        let remove_print_config = RemovePrintConfig {
+            mint,
+            update_authority: authority,
+            receiver: authority,
+        };
+        assert_eq!(
+            remove_print_config.instruction().accounts[0].pubkey,
+            find_print_config_account(&mint).0
+        );
+
+        let create_fungible_config = CreateFungibleConfig {
+            mint,
+            mint_authority: authority,
+            update_authority: authority,
+            payer: authority,
+            max_supply: 1_000,
+        };
+        let accounts = create_fungible_config.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, find_fungible_config_account(&mint).0);
+        assert_eq!(accounts[1].pubkey, find_metadata_account(&mint).0);
+
+        let mint_fungible = MintFungible {
+            mint,
+            destination: Pubkey::new_unique(),
+            mint_authority: authority,
+            amount: 1,
+        };
+        assert_eq!(
+            mint_fungible.instruction().accounts[0].pubkey,
+            find_fungible_config_account(&mint).0
+        );
+
+        let update_fungible_config = UpdateFungibleConfig {
+            mint,
+            update_authority: authority,
+            new_mint_authority: Some(Pubkey::new_unique()),
+            max_supply: None,
+        };
+        let accounts = update_fungible_config.instruction().accounts;
+        assert_eq!(accounts[0].pubkey, find_fungible_config_account(&mint).0);
+        assert_eq!(accounts[1].pubkey, find_metadata_account(&mint).0);
+    }
+}
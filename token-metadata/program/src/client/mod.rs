@@ -0,0 +1,13 @@
+//! Helpers for off-chain clients, available with the `client` feature.
+//!
+//! [`builders`] wraps the instruction functions of the common metadata, edition, collection,
+//! transfer, burn, escrow and fungible instructions in structs that derive the program addresses
+//! from the mints involved, and [`accounts`] decodes raw account data of any [`Key`] variant.
+//!
+//! [`Key`]: crate::state::Key
+
+pub mod accounts;
+pub mod builders;
+
+pub use accounts::*;
+pub use builders::*;
@@ -0,0 +1,293 @@
+use crate::{
+    assertions::{
+        collection::{assert_collection_verify_is_valid, assert_has_collection_authority},
+        validation::assert_metadata_validation_profile,
+    },
+    deser::clean_write_metadata,
+    error::MetadataError,
+    events::{emit_event, MetadataEventV1},
+    instruction::MetadataInstruction,
+    pda::{find_collection_authority_account, find_collection_validation_profile_account},
+    state::{
+        CollectionDetails, Metadata, TokenMetadataAccount, COLLECTION_PERMISSION_UNVERIFY,
+        COLLECTION_PERMISSION_VERIFY,
+    },
+    utils::{assert_owned_by, assert_signer},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+///# Batch Verify Sized Collection Items
+///
+/// Verifies every item metadata against one sized collection parent and increases the
+/// collection size once by the number of items. Every item must meet the validation profile of
+/// the collection.
+///
+///### Accounts:
+///
+///   0. `[signer]` Collection update authority or delegated collection authority
+///   1. `[]` Mint of the collection
+///   2. `[writable]` Metadata account of the collection
+///   3. `[]` MasterEdition2 account of the collection token
+///   4. `[]` Collection authority record PDA of the authority, may be uninitialized
+///   5. `[]` Collection validation profile PDA, may be uninitialized
+///   6. `[writable]` Item metadata accounts, one or more
+pub fn batch_verify_sized_collection_items(
+    program_id: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+    items: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: batch_accounts(
+            collection_authority,
+            collection_mint,
+            collection,
+            collection_master_edition_account,
+            Some(find_collection_validation_profile_account(&collection_mint).0),
+            items,
+        ),
+        data: MetadataInstruction::BatchVerifySizedCollectionItems
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Batch Unverify Sized Collection Items
+///
+/// Unverifies every item metadata against one sized collection parent and decreases the
+/// collection size once by the number of items.
+///
+///### Accounts:
+///
+///   0. `[signer]` Collection update authority or delegated collection authority
+///   1. `[]` Mint of the collection
+///   2. `[writable]` Metadata account of the collection
+///   3. `[]` MasterEdition2 account of the collection token
+///   4. `[]` Collection authority record PDA of the authority, may be uninitialized
+///   5. `[writable]` Item metadata accounts, one or more
+pub fn batch_unverify_sized_collection_items(
+    program_id: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+    items: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: batch_accounts(
+            collection_authority,
+            collection_mint,
+            collection,
+            collection_master_edition_account,
+            None,
+            items,
+        ),
+        data: MetadataInstruction::BatchUnverifySizedCollectionItems
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+fn batch_accounts(
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+    validation_profile: Option<Pubkey>,
+    items: Vec<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (collection_authority_record, _) =
+        find_collection_authority_account(&collection_mint, &collection_authority);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(collection_authority, true),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new(collection, false),
+        AccountMeta::new_readonly(collection_master_edition_account, false),
+        AccountMeta::new_readonly(collection_authority_record, false),
+    ];
+
+    if let Some(validation_profile) = validation_profile {
+        accounts.push(AccountMeta::new_readonly(validation_profile, false));
+    }
+
+    for item in items {
+        accounts.push(AccountMeta::new(item, false));
+    }
+
+    accounts
+}
+
+pub fn process_batch_verify_sized_collection_items(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    process_batch(program_id, accounts, true)
+}
+
+pub fn process_batch_unverify_sized_collection_items(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    process_batch(program_id, accounts, false)
+}
+
+fn process_batch(program_id: &Pubkey, accounts: &[AccountInfo], verify: bool) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let edition_account_info = next_account_info(account_info_iter)?;
+    let collection_authority_record = next_account_info(account_info_iter)?;
+    let validation_profile_info = if verify {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
+    let item_infos = account_info_iter.as_slice();
+
+    assert_signer(collection_authority_info)?;
+
+    assert_owned_by(collection_info, program_id)?;
+    assert_owned_by(collection_mint, &spl_token::id())?;
+    assert_owned_by(edition_account_info, program_id)?;
+
+    if item_infos.is_empty() {
+        return Err(MetadataError::NoCollectionItems.into());
+    }
+
+    let mut collection_metadata = Metadata::from_account_info(collection_info)?;
+
+    // An uninitialized record means the authority is acting as the collection update authority.
+    let delegate_record = if collection_authority_record.data_is_empty() {
+        None
+    } else {
+        Some(collection_authority_record)
+    };
+    assert_has_collection_authority(
+        collection_authority_info,
+        &collection_metadata,
+        collection_mint.key,
+        delegate_record,
+        if verify {
+            COLLECTION_PERMISSION_VERIFY
+        } else {
+            COLLECTION_PERMISSION_UNVERIFY
+        },
+    )?;
+
+    let size = match collection_metadata.collection_details {
+        Some(CollectionDetails::V1 { size }) => size,
+        None => return Err(MetadataError::UnsizedCollection.into()),
+    };
+
+    // Each item is logged with the size the collection reaches once it is counted.
+    let mut new_size = size;
+    for (index, item_info) in item_infos.iter().enumerate() {
+        let old_size = new_size;
+        new_size = if verify {
+            new_size.checked_add(1)
+        } else {
+            new_size.checked_sub(1)
+        }
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+        if let Err(err) = set_item_verified(
+            program_id,
+            item_info,
+            collection_info,
+            &collection_metadata,
+            collection_mint,
+            edition_account_info,
+            validation_profile_info,
+            (old_size, new_size),
+        ) {
+            msg!("Collection item {} ({}) failed", index, item_info.key);
+            return Err(err);
+        }
+    }
+    let size = new_size;
+
+    msg!("Clean writing collection parent metadata");
+    collection_metadata.collection_details = Some(CollectionDetails::V1 { size });
+    clean_write_metadata(&mut collection_metadata, collection_info)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set_item_verified(
+    program_id: &Pubkey,
+    item_info: &AccountInfo,
+    collection_info: &AccountInfo,
+    collection_metadata: &Metadata,
+    collection_mint: &AccountInfo,
+    edition_account_info: &AccountInfo,
+    validation_profile_info: Option<&AccountInfo>,
+    (old_collection_size, new_collection_size): (u64, u64),
+) -> Result<(), ProgramError> {
+    // Only verifying passes the validation profile.
+    let verify = validation_profile_info.is_some();
+
+    assert_owned_by(item_info, program_id)?;
+
+    // The parent is written last and would overwrite the change.
+    if item_info.key == collection_info.key {
+        return Err(MetadataError::CollectionNotFound.into());
+    }
+
+    let mut metadata = Metadata::from_account_info(item_info)?;
+
+    // Don't count items already in the requested state, otherwise the size data becomes invalid.
+    if let Some(collection) = &metadata.collection {
+        if collection.verified == verify {
+            return Err(if verify {
+                MetadataError::AlreadyVerified.into()
+            } else {
+                MetadataError::AlreadyUnverified.into()
+            });
+        }
+    }
+
+    assert_collection_verify_is_valid(
+        &metadata.collection,
+        collection_metadata,
+        collection_mint,
+        edition_account_info,
+    )?;
+
+    if let Some(validation_profile_info) = validation_profile_info {
+        assert_metadata_validation_profile(
+            program_id,
+            collection_mint.key,
+            validation_profile_info,
+            &metadata,
+        )?;
+    }
+
+    let old_collection = metadata.collection.clone();
+    if let Some(collection) = &mut metadata.collection {
+        collection.verified = verify;
+    }
+
+    clean_write_metadata(&mut metadata, item_info)?;
+
+    emit_event(MetadataEventV1::CollectionVerification {
+        metadata: *item_info.key,
+        mint: metadata.mint,
+        old_collection,
+        new_collection: metadata.collection,
+        old_collection_size: Some(old_collection_size),
+        new_collection_size: Some(new_collection_size),
+    })
+}
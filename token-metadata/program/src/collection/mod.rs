@@ -0,0 +1,65 @@
+pub mod batch_verify;
+pub mod remove_collection_creators;
+pub mod remove_collection_royalties;
+pub mod remove_collection_validation_profile;
+pub mod set_collection_creators;
+pub mod set_collection_royalties;
+pub mod set_collection_validation_profile;
+pub mod verify_as_creator;
+
+pub use batch_verify::*;
+pub use remove_collection_creators::*;
+pub use remove_collection_royalties::*;
+pub use remove_collection_validation_profile::*;
+pub use set_collection_creators::*;
+pub use set_collection_royalties::*;
+pub use set_collection_validation_profile::*;
+pub use verify_as_creator::*;
+
+use crate::state::{
+    Metadata, COLLECTION_CREATORS, COLLECTION_ROYALTIES, COLLECTION_VALIDATION_PROFILE, PREFIX,
+};
+use solana_program::pubkey::Pubkey;
+
+pub fn collection_royalties_seeds<'a>(
+    program_id: &'a Pubkey,
+    collection_mint: &'a Pubkey,
+) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        collection_mint.as_ref(),
+        COLLECTION_ROYALTIES.as_bytes(),
+    ]
+}
+
+pub fn collection_creators_seeds<'a>(
+    program_id: &'a Pubkey,
+    collection_mint: &'a Pubkey,
+) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        collection_mint.as_ref(),
+        COLLECTION_CREATORS.as_bytes(),
+    ]
+}
+
+pub fn collection_validation_profile_seeds<'a>(
+    program_id: &'a Pubkey,
+    collection_mint: &'a Pubkey,
+) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        collection_mint.as_ref(),
+        COLLECTION_VALIDATION_PROFILE.as_bytes(),
+    ]
+}
+
+pub fn is_verified_creator(metadata: &Metadata, creator: &Pubkey) -> bool {
+    match &metadata.data.creators {
+        Some(creators) => creators.iter().any(|c| c.verified && c.address == *creator),
+        None => false,
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    collection::collection_validation_profile_seeds,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, TokenMetadataAccount},
+    utils::{
+        assert_derivation, assert_owned_by, assert_update_authority_is_correct, close_account_raw,
+    },
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Remove Collection Validation Profile
+///
+/// Removes the validation profile of a collection and closes its account, so any item can be
+/// verified again.
+///
+///### Accounts:
+///
+///   0. `[writable]` Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile'])
+///   1. `[]` Metadata account of the collection
+///   2. `[]` Mint of the collection
+///   3. `[signer]` Update authority of the collection
+///   4. `[writable]` Account receiving the validation profile rent
+pub fn remove_collection_validation_profile(
+    program_id: Pubkey,
+    validation_profile: Pubkey,
+    collection: Pubkey,
+    collection_mint: Pubkey,
+    update_authority: Pubkey,
+    receiver: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(validation_profile, false),
+        AccountMeta::new_readonly(collection, false),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::RemoveCollectionValidationProfile
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_remove_collection_validation_profile(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let validation_profile_info = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(collection_info, program_id)?;
+    assert_owned_by(validation_profile_info, program_id)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_info)?;
+    assert_update_authority_is_correct(&collection_metadata, update_authority_info)?;
+
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    assert_derivation(
+        program_id,
+        validation_profile_info,
+        &collection_validation_profile_seeds(program_id, collection_mint_info.key),
+    )?;
+
+    close_account_raw(receiver_info, validation_profile_info)
+}
@@ -0,0 +1,156 @@
+use crate::{
+    collection::{collection_creators_seeds, is_verified_creator},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{CollectionCreators, Key, Metadata, TokenMetadataAccount, MAX_CREATOR_LIMIT},
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_update_authority_is_correct,
+        create_or_allocate_account_raw, resize_or_reallocate_account_raw, shrink_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetCollectionCreatorsArgs {
+    pub creators: Vec<Pubkey>,
+}
+
+///# Set Collection Creators
+///
+/// Sets the creators of a collection allowed to verify the items they are verified creators of.
+/// Each of them must be a verified creator of the collection itself.
+///
+///### Accounts:
+///
+///   0. `[writable]` Collection creators account (pda of ['metadata', program id, collection mint id, 'collection_creators'])
+///   1. `[]` Metadata account of the collection
+///   2. `[]` Mint of the collection
+///   3. `[signer]` Update authority of the collection
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+pub fn set_collection_creators(
+    program_id: Pubkey,
+    collection_creators: Pubkey,
+    collection: Pubkey,
+    collection_mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    creators: Vec<Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(collection_creators, false),
+        AccountMeta::new_readonly(collection, false),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetCollectionCreators(SetCollectionCreatorsArgs { creators })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_set_collection_creators(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetCollectionCreatorsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let collection_creators_info = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(collection_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_info)?;
+    assert_update_authority_is_correct(&collection_metadata, update_authority_info)?;
+
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if args.creators.is_empty() || args.creators.len() > MAX_CREATOR_LIMIT {
+        return Err(MetadataError::InvalidCollectionCreators.into());
+    }
+
+    for (index, creator) in args.creators.iter().enumerate() {
+        if args.creators[..index].contains(creator)
+            || !is_verified_creator(&collection_metadata, creator)
+        {
+            return Err(MetadataError::InvalidCollectionCreators.into());
+        }
+    }
+
+    let creators_path = collection_creators_seeds(program_id, collection_mint_info.key);
+    let bump = assert_derivation(program_id, collection_creators_info, &creators_path)?;
+
+    let collection_creators = CollectionCreators {
+        key: Key::CollectionCreators,
+        mint: *collection_mint_info.key,
+        bump,
+        creators: args.creators,
+    };
+    let creators_len = collection_creators.serialized_len();
+
+    if collection_creators_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut creators_seeds = creators_path.clone();
+        creators_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            collection_creators_info,
+            system_program_info,
+            payer_info,
+            creators_len,
+            &creators_seeds,
+        )?;
+    } else {
+        assert_owned_by(collection_creators_info, program_id)?;
+        CollectionCreators::from_account_info(collection_creators_info)?;
+
+        if collection_creators_info.data_len() < creators_len {
+            resize_or_reallocate_account_raw(
+                collection_creators_info,
+                payer_info,
+                system_program_info,
+                creators_len,
+            )?;
+        } else if collection_creators_info.data_len() > creators_len {
+            shrink_account_raw(collection_creators_info, payer_info, creators_len)?;
+        }
+    }
+
+    BorshSerialize::serialize(
+        &collection_creators,
+        &mut *collection_creators_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
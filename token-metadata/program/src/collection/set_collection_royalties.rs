@@ -0,0 +1,182 @@
+use crate::{
+    collection::collection_royalties_seeds,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{CollectionRoyalties, Creator, Data, Key, Metadata, TokenMetadataAccount},
+    utils::{
+        assert_data_valid, assert_derivation, assert_owned_by, assert_signer,
+        assert_update_authority_is_correct, create_or_allocate_account_raw,
+        resize_or_reallocate_account_raw, shrink_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetCollectionRoyaltiesArgs {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+///# Set Collection Royalties
+///
+/// Sets the default royalties of a sized collection. Verified items of the collection that
+/// don't declare creators of their own inherit them.
+///
+///### Accounts:
+///
+///   0. `[writable]` Collection royalties account (pda of ['metadata', program id, collection mint id, 'collection_royalties'])
+///   1. `[]` Metadata account of the collection
+///   2. `[]` Mint of the collection
+///   3. `[signer]` Update authority of the collection
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+#[allow(clippy::too_many_arguments)]
+pub fn set_collection_royalties(
+    program_id: Pubkey,
+    collection_royalties: Pubkey,
+    collection: Pubkey,
+    collection_mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    seller_fee_basis_points: u16,
+    creators: Vec<Creator>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(collection_royalties, false),
+        AccountMeta::new_readonly(collection, false),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetCollectionRoyalties(SetCollectionRoyaltiesArgs {
+            seller_fee_basis_points,
+            creators,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_set_collection_royalties(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetCollectionRoyaltiesArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let collection_royalties_info = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(collection_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_info)?;
+    assert_update_authority_is_correct(&collection_metadata, update_authority_info)?;
+
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if collection_metadata.collection_details.is_none() {
+        return Err(MetadataError::NotACollectionParent.into());
+    }
+
+    let royalties_path = collection_royalties_seeds(program_id, collection_mint_info.key);
+    let bump = assert_derivation(program_id, collection_royalties_info, &royalties_path)?;
+
+    let existing_royalties = if collection_royalties_info.data_is_empty() {
+        None
+    } else {
+        assert_owned_by(collection_royalties_info, program_id)?;
+        Some(CollectionRoyalties::from_account_info(
+            collection_royalties_info,
+        )?)
+    };
+
+    // Creators follow the same rules as the creators of a metadata account, so compare against
+    // the current defaults as if they were the existing creators.
+    let existing_metadata = Metadata {
+        data: Data {
+            creators: existing_royalties.map(|royalties| royalties.creators),
+            ..Data::default()
+        },
+        ..Metadata::default()
+    };
+    let data = Data {
+        seller_fee_basis_points: args.seller_fee_basis_points,
+        creators: Some(args.creators.clone()),
+        ..Data::default()
+    };
+    assert_data_valid(
+        &data,
+        update_authority_info.key,
+        &existing_metadata,
+        false,
+        true,
+    )?;
+
+    let royalties = CollectionRoyalties {
+        key: Key::CollectionRoyalties,
+        mint: *collection_mint_info.key,
+        bump,
+        seller_fee_basis_points: args.seller_fee_basis_points,
+        creators: args.creators,
+    };
+    let royalties_len = royalties.serialized_len();
+
+    if collection_royalties_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut royalties_seeds = royalties_path.clone();
+        royalties_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            collection_royalties_info,
+            system_program_info,
+            payer_info,
+            royalties_len,
+            &royalties_seeds,
+        )?;
+    } else if collection_royalties_info.data_len() < royalties_len {
+        resize_or_reallocate_account_raw(
+            collection_royalties_info,
+            payer_info,
+            system_program_info,
+            royalties_len,
+        )?;
+    } else if collection_royalties_info.data_len() > royalties_len {
+        shrink_account_raw(collection_royalties_info, payer_info, royalties_len)?;
+    }
+
+    BorshSerialize::serialize(
+        &royalties,
+        &mut *collection_royalties_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use crate::{
+    assertions::validation::assert_validation_rules_are_valid,
+    collection::collection_validation_profile_seeds,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{
+        CollectionValidationProfile, Key, Metadata, TokenMetadataAccount,
+        COLLECTION_VALIDATION_PROFILE_SIZE,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_update_authority_is_correct,
+        create_or_allocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetCollectionValidationProfileArgs {
+    /// Bitmask of the `VALIDATION_RULE_*` rules items must follow
+    pub rules: u8,
+}
+
+///# Set Collection Validation Profile
+///
+/// Sets the rules a sized collection requires of the name, symbol and uri of its items. Items
+/// breaking them can't be verified as members of the collection.
+///
+///### Accounts:
+///
+///   0. `[writable]` Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile'])
+///   1. `[]` Metadata account of the collection
+///   2. `[]` Mint of the collection
+///   3. `[signer]` Update authority of the collection
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+pub fn set_collection_validation_profile(
+    program_id: Pubkey,
+    validation_profile: Pubkey,
+    collection: Pubkey,
+    collection_mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    rules: u8,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(validation_profile, false),
+        AccountMeta::new_readonly(collection, false),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetCollectionValidationProfile(
+            SetCollectionValidationProfileArgs { rules },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_set_collection_validation_profile(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetCollectionValidationProfileArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let validation_profile_info = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(collection_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_info)?;
+    assert_update_authority_is_correct(&collection_metadata, update_authority_info)?;
+
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if collection_metadata.collection_details.is_none() {
+        return Err(MetadataError::NotACollectionParent.into());
+    }
+
+    assert_validation_rules_are_valid(args.rules)?;
+
+    let profile_path = collection_validation_profile_seeds(program_id, collection_mint_info.key);
+    let bump = assert_derivation(program_id, validation_profile_info, &profile_path)?;
+
+    if validation_profile_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut profile_seeds = profile_path.clone();
+        profile_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            validation_profile_info,
+            system_program_info,
+            payer_info,
+            COLLECTION_VALIDATION_PROFILE_SIZE,
+            &profile_seeds,
+        )?;
+    } else {
+        assert_owned_by(validation_profile_info, program_id)?;
+    }
+
+    let profile = CollectionValidationProfile {
+        key: Key::CollectionValidationProfile,
+        mint: *collection_mint_info.key,
+        bump,
+        rules: args.rules,
+    };
+    BorshSerialize::serialize(
+        &profile,
+        &mut *validation_profile_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::{
+    assertions::{
+        collection::assert_collection_verify_is_valid,
+        validation::assert_metadata_validation_profile,
+    },
+    collection::{collection_creators_seeds, is_verified_creator},
+    deser::clean_write_metadata,
+    error::MetadataError,
+    events::{collection_size, emit_collection_verification},
+    instruction::MetadataInstruction,
+    pda::find_collection_validation_profile_account,
+    state::{CollectionCreators, Metadata, TokenMetadataAccount},
+    utils::{assert_derivation, assert_owned_by, assert_signer, increment_collection_size},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    pubkey::Pubkey,
+};
+
+///# Verify Collection Item As Creator
+///
+/// Verifies an item of a sized or unsized collection on behalf of a collection creator. The
+/// creator must be listed in the collection creators and be a verified creator of both the
+/// collection and the item, and the item must meet the validation profile of the collection.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account of the item
+///   1. `[signer]` Collection creator
+///   2. `[]` Mint of the collection
+///   3. `[writable]` Metadata account of the collection
+///   4. `[]` MasterEdition2 account of the collection
+///   5. `[]` Collection creators account (pda of ['metadata', program id, collection mint id, 'collection_creators'])
+///   6. `[]` Collection validation profile PDA, may be uninitialized
+pub fn verify_collection_item_as_creator(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    creator: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+    collection_creators: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(creator, true),
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new(collection, false),
+        AccountMeta::new_readonly(collection_master_edition_account, false),
+        AccountMeta::new_readonly(collection_creators, false),
+        AccountMeta::new_readonly(
+            find_collection_validation_profile_account(&collection_mint).0,
+            false,
+        ),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::VerifyCollectionItemAsCreator
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_verify_collection_item_as_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let collection_mint = next_account_info(account_info_iter)?;
+    let collection_info = next_account_info(account_info_iter)?;
+    let edition_account_info = next_account_info(account_info_iter)?;
+    let collection_creators_info = next_account_info(account_info_iter)?;
+    let validation_profile_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(collection_info, program_id)?;
+    assert_owned_by(collection_mint, &spl_token::id())?;
+    assert_owned_by(edition_account_info, program_id)?;
+    assert_owned_by(collection_creators_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let mut collection_metadata = Metadata::from_account_info(collection_info)?;
+
+    // Don't verify already verified items, otherwise we end up with invalid size data.
+    if let Some(collection) = &metadata.collection {
+        if collection.verified {
+            return Err(MetadataError::AlreadyVerified.into());
+        }
+    }
+
+    assert_collection_verify_is_valid(
+        &metadata.collection,
+        &collection_metadata,
+        collection_mint,
+        edition_account_info,
+    )?;
+
+    assert_derivation(
+        program_id,
+        collection_creators_info,
+        &collection_creators_seeds(program_id, collection_mint.key),
+    )?;
+    let collection_creators = CollectionCreators::from_account_info(collection_creators_info)?;
+
+    // The creator may have unverified themselves from the collection since being listed.
+    if !collection_creators.creators.contains(creator_info.key)
+        || !is_verified_creator(&collection_metadata, creator_info.key)
+        || !is_verified_creator(&metadata, creator_info.key)
+    {
+        return Err(MetadataError::NotACollectionCreator.into());
+    }
+
+    assert_metadata_validation_profile(
+        program_id,
+        collection_mint.key,
+        validation_profile_info,
+        &metadata,
+    )?;
+
+    let old_collection = metadata.collection.clone();
+    let old_collection_size = collection_size(&collection_metadata);
+    if collection_metadata.collection_details.is_some() {
+        msg!("Verifying sized collection item");
+        increment_collection_size(&mut collection_metadata, collection_info)?;
+    }
+
+    if let Some(collection) = &mut metadata.collection {
+        collection.verified = true;
+        clean_write_metadata(&mut metadata, metadata_info)?;
+    }
+
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        old_collection_size,
+        &collection_metadata,
+    )
+}
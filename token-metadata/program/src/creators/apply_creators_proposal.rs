@@ -0,0 +1,90 @@
+use crate::{
+    creators::{load_creators_proposal, required_approvers},
+    deser::clean_write_metadata,
+    error::MetadataError,
+    events::{emit_update, MetadataValues},
+    instruction::MetadataInstruction,
+    state::{Metadata, TokenMetadataAccount},
+    utils::{assert_owned_by, assert_update_authority_is_correct, close_account_raw},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Apply Creators Proposal
+///
+/// Replaces the creators of a metadata account with the proposed creators and closes the
+/// proposal. Every verified creator of the current and proposed creators must have approved it.
+///
+///### Accounts:
+///
+///   0. `[writable]` Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])
+///   1. `[writable]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[writable]` Account receiving the creators proposal rent
+pub fn apply_creators_proposal(
+    program_id: Pubkey,
+    creators_proposal: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    receiver: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(creators_proposal, false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ApplyCreatorsProposal
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_apply_creators_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creators_proposal_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let creators_proposal =
+        load_creators_proposal(program_id, creators_proposal_info, &metadata.mint)?;
+
+    // Approvers are derived from the creators at the time of applying, so a direct update of the
+    // creators after the proposal was made cannot skip anyone.
+    let approved = required_approvers(&metadata, &creators_proposal.creators)
+        .iter()
+        .all(|approver| creators_proposal.approvals.contains(approver));
+    if !approved {
+        return Err(MetadataError::CreatorsProposalNotApproved.into());
+    }
+
+    let old_values = MetadataValues::from(&metadata);
+    metadata.data.creators = Some(creators_proposal.creators);
+    clean_write_metadata(&mut metadata, metadata_info)?;
+    emit_update(metadata_info.key, old_values, &metadata)?;
+
+    close_account_raw(receiver_info, creators_proposal_info)
+}
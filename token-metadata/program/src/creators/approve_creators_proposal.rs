@@ -0,0 +1,97 @@
+use crate::{
+    creators::{load_creators_proposal, required_approvers},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Creator, Metadata, TokenMetadataAccount},
+    utils::{assert_owned_by, assert_signer},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ApproveCreatorsProposalArgs {
+    /// Creators being approved, which must match the proposed creators
+    pub creators: Vec<Creator>,
+}
+
+///# Approve Creators Proposal
+///
+/// Approves the proposed creators of a metadata account as a verified creator of the current or
+/// proposed creators.
+///
+///### Accounts:
+///
+///   0. `[writable]` Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Creator
+pub fn approve_creators_proposal(
+    program_id: Pubkey,
+    creators_proposal: Pubkey,
+    metadata: Pubkey,
+    creator: Pubkey,
+    creators: Vec<Creator>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(creators_proposal, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(creator, true),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ApproveCreatorsProposal(ApproveCreatorsProposalArgs {
+            creators,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_approve_creators_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ApproveCreatorsProposalArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creators_proposal_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let mut creators_proposal =
+        load_creators_proposal(program_id, creators_proposal_info, &metadata.mint)?;
+
+    // Guards against the proposal being replaced while the approval was in flight.
+    if creators_proposal.creators != args.creators {
+        return Err(MetadataError::CreatorsProposalMismatch.into());
+    }
+
+    if !required_approvers(&metadata, &creators_proposal.creators).contains(creator_info.key) {
+        return Err(MetadataError::InvalidProposalApprover.into());
+    }
+
+    if !creators_proposal.approvals.contains(creator_info.key) {
+        creators_proposal.approvals.push(*creator_info.key);
+        BorshSerialize::serialize(
+            &creators_proposal,
+            &mut *creators_proposal_info.try_borrow_mut_data()?,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{
+    creators::load_creators_proposal,
+    instruction::MetadataInstruction,
+    state::{Metadata, TokenMetadataAccount},
+    utils::{assert_owned_by, assert_update_authority_is_correct, close_account_raw},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Cancel Creators Proposal
+///
+/// Closes the creators proposal of a metadata account without applying it.
+///
+///### Accounts:
+///
+///   0. `[writable]` Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[writable]` Account receiving the creators proposal rent
+pub fn cancel_creators_proposal(
+    program_id: Pubkey,
+    creators_proposal: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    receiver: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(creators_proposal, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::CancelCreatorsProposal
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_cancel_creators_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creators_proposal_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    load_creators_proposal(program_id, creators_proposal_info, &metadata.mint)?;
+
+    close_account_raw(receiver_info, creators_proposal_info)
+}
@@ -0,0 +1,61 @@
+pub mod apply_creators_proposal;
+pub mod approve_creators_proposal;
+pub mod cancel_creators_proposal;
+pub mod propose_creators;
+
+pub use apply_creators_proposal::*;
+pub use approve_creators_proposal::*;
+pub use cancel_creators_proposal::*;
+pub use propose_creators::*;
+
+use crate::{
+    error::MetadataError,
+    state::{Creator, CreatorsProposal, Metadata, TokenMetadataAccount, CREATORS_PROPOSAL, PREFIX},
+    utils::{assert_derivation, assert_owned_by},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+pub fn creators_proposal_seeds<'a>(program_id: &'a Pubkey, mint: &'a Pubkey) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        CREATORS_PROPOSAL.as_bytes(),
+    ]
+}
+
+/// Loads the creators proposal of `mint`, checking its derivation.
+pub fn load_creators_proposal(
+    program_id: &Pubkey,
+    creators_proposal_info: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<CreatorsProposal, ProgramError> {
+    assert_owned_by(creators_proposal_info, program_id)?;
+    assert_derivation(
+        program_id,
+        creators_proposal_info,
+        &creators_proposal_seeds(program_id, mint),
+    )?;
+
+    let creators_proposal = CreatorsProposal::from_account_info(creators_proposal_info)?;
+    if creators_proposal.mint != *mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    Ok(creators_proposal)
+}
+
+/// Creators who must approve `proposed` before it replaces the creators of `metadata`: every
+/// verified creator of either set.
+pub fn required_approvers(metadata: &Metadata, proposed: &[Creator]) -> Vec<Pubkey> {
+    let mut approvers: Vec<Pubkey> = Vec::new();
+    let current = metadata.data.creators.as_deref().unwrap_or_default();
+
+    for creator in current.iter().chain(proposed.iter()) {
+        if creator.verified && !approvers.contains(&creator.address) {
+            approvers.push(creator.address);
+        }
+    }
+
+    approvers
+}
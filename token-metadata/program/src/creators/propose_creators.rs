@@ -0,0 +1,141 @@
+use crate::{
+    creators::creators_proposal_seeds,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{
+        Creator, CreatorsProposal, Data, Key, Metadata, TokenMetadataAccount,
+        CREATORS_PROPOSAL_SIZE,
+    },
+    utils::{
+        assert_data_valid, assert_derivation, assert_owned_by, assert_signer,
+        assert_update_authority_is_correct, create_or_allocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program_memory::sol_memset,
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ProposeCreatorsArgs {
+    pub creators: Vec<Creator>,
+}
+
+///# Propose Creators
+///
+/// Proposes new creators for a metadata account. The proposal replaces any previous one and
+/// takes effect once every verified creator of the current and proposed creators approved it.
+///
+///### Accounts:
+///
+///   0. `[writable]` Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[writable, signer]` Payer
+///   4. `[]` System program
+pub fn propose_creators(
+    program_id: Pubkey,
+    creators_proposal: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    creators: Vec<Creator>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(creators_proposal, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ProposeCreators(ProposeCreatorsArgs { creators })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_propose_creators(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ProposeCreatorsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creators_proposal_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    // Verified flags are consented to by approving the proposal, so only the shape of the
+    // creators is checked here.
+    let data = Data {
+        creators: Some(args.creators.clone()),
+        ..Data::default()
+    };
+    assert_data_valid(&data, update_authority_info.key, &metadata, true, true)?;
+
+    let proposal_path = creators_proposal_seeds(program_id, &metadata.mint);
+    let bump = assert_derivation(program_id, creators_proposal_info, &proposal_path)?;
+
+    if creators_proposal_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut proposal_seeds = proposal_path.clone();
+        proposal_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            creators_proposal_info,
+            system_program_info,
+            payer_info,
+            CREATORS_PROPOSAL_SIZE,
+            &proposal_seeds,
+        )?;
+    } else {
+        assert_owned_by(creators_proposal_info, program_id)?;
+        CreatorsProposal::from_account_info(creators_proposal_info)?;
+    }
+
+    // Approvals of a previous proposal do not carry over.
+    let creators_proposal = CreatorsProposal {
+        key: Key::CreatorsProposal,
+        mint: metadata.mint,
+        bump,
+        creators: args.creators,
+        approvals: vec![],
+    };
+
+    let data = &mut *creators_proposal_info.try_borrow_mut_data()?;
+    sol_memset(data, 0, CREATORS_PROPOSAL_SIZE);
+    BorshSerialize::serialize(&creators_proposal, &mut &mut data[..])?;
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::{
+    deser::clean_write_metadata,
     error::MetadataError,
+    events::{emit_update, MetadataValues},
     state::{Data, DataV2, Metadata, TokenMetadataAccount},
     utils::{
         assert_data_valid, assert_owned_by, assert_update_authority_is_correct,
@@ -7,7 +9,6 @@ use crate::{
         CreateMetadataAccountsLogicArgs,
     },
 };
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -68,6 +69,7 @@ pub fn process_deprecated_update_metadata_accounts(
     let metadata_account_info = next_account_info(account_info_iter)?;
     let update_authority_info = next_account_info(account_info_iter)?;
     let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    let old_values = MetadataValues::from(&metadata);
 
     assert_owned_by(metadata_account_info, program_id)?;
     assert_update_authority_is_correct(&metadata, update_authority_info)?;
@@ -100,7 +102,7 @@ pub fn process_deprecated_update_metadata_accounts(
     }
 
     puff_out_data_fields(&mut metadata);
+    clean_write_metadata(&mut metadata, metadata_account_info)?;
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
-    Ok(())
+    emit_update(metadata_account_info.key, old_values, &metadata)
 }
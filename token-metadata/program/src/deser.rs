@@ -1,6 +1,14 @@
-use crate::state::{Collection, CollectionDetails, Data, Key, Metadata, TokenStandard, Uses};
+use crate::{
+    error::MetadataError,
+    state::{
+        Collection, CollectionDetails, Data, Key, Metadata, TokenStandard, Uses, MAX_METADATA_LEN,
+    },
+    utils::{assert_signer, resize_or_reallocate_account_raw, unpuff_data_fields},
+};
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
 
 // Custom deserialization function to handle NFTs with corrupted data.
 // This function is used in a custom deserialization implementation for the
@@ -29,6 +37,9 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     let collection_details_res: Result<Option<CollectionDetails>, BorshError> =
         BorshDeserialize::deserialize(buf);
 
+    // V1.4
+    let rule_set_res: Result<Option<Pubkey>, BorshError> = BorshDeserialize::deserialize(buf);
+
     /* We can have accidentally valid, but corrupted data, particularly on the Collection struct,
     so to increase probability of catching errors If any of these deserializations fail, set all values to None.
     */
@@ -40,11 +51,18 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     };
 
     // Handle v1.3 separately
+    let collection_details_ok = collection_details_res.is_ok();
     let collection_details = match collection_details_res {
         Ok(details) => details,
         Err(_) => None,
     };
 
+    // Only trust v1.4 if the fields before it were read cleanly.
+    let rule_set = match rule_set_res {
+        Ok(rule_set) if collection_details_ok => rule_set,
+        _ => None,
+    };
+
     let metadata = Metadata {
         key,
         update_authority,
@@ -57,6 +75,7 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         collection,
         uses,
         collection_details,
+        rule_set,
     };
 
     Ok(metadata)
@@ -66,15 +85,59 @@ pub fn clean_write_metadata(
     metadata: &mut Metadata,
     metadata_account_info: &AccountInfo,
 ) -> ProgramResult {
-    // Clear all data to ensure it is serialized cleanly with no trailing data due to creators array resizing.
+    // Resized accounts store their strings without padding.
+    if metadata_account_info.data_len() < MAX_METADATA_LEN {
+        unpuff_data_fields(metadata);
+    }
+
+    let data = metadata.try_to_vec()?;
     let mut metadata_account_info_data = metadata_account_info.try_borrow_mut_data()?;
-    metadata_account_info_data[0..].fill(0);
 
-    metadata.serialize(&mut *metadata_account_info_data)?;
+    if data.len() > metadata_account_info_data.len() {
+        return Err(MetadataError::MetadataAccountTooSmall.into());
+    }
+
+    // Clear all data to ensure it is serialized cleanly with no trailing data due to creators array resizing.
+    metadata_account_info_data[0..].fill(0);
+    metadata_account_info_data[..data.len()].copy_from_slice(&data);
 
     Ok(())
 }
 
+/// Writes `metadata` like `clean_write_metadata`, first growing a resized account that is too
+/// small for it. The extra rent is paid by `payer_info`; without a payer the write fails with
+/// `MetadataAccountTooSmall` as before.
+pub fn realloc_and_write_metadata<'a>(
+    metadata: &mut Metadata,
+    metadata_account_info: &AccountInfo<'a>,
+    payer_info: Option<&AccountInfo<'a>>,
+    system_program_info: Option<&AccountInfo<'a>>,
+) -> ProgramResult {
+    let current_len = metadata_account_info.data_len();
+    if current_len < MAX_METADATA_LEN {
+        unpuff_data_fields(metadata);
+        let new_len = metadata.try_to_vec()?.len();
+
+        if new_len > current_len {
+            if let (Some(payer_info), Some(system_program_info)) = (payer_info, system_program_info)
+            {
+                assert_signer(payer_info)?;
+                if *system_program_info.key != system_program::id() {
+                    return Err(MetadataError::InvalidSystemProgram.into());
+                }
+                resize_or_reallocate_account_raw(
+                    metadata_account_info,
+                    payer_info,
+                    system_program_info,
+                    new_len,
+                )?;
+            }
+        }
+    }
+
+    clean_write_metadata(metadata, metadata_account_info)
+}
+
 #[cfg(test)]
 pub mod tests {
     use solana_program::pubkey;
@@ -158,6 +221,7 @@ pub mod tests {
             collection: None,
             uses: None,
             collection_details: None,
+            rule_set: None,
         };
 
         puff_out_data_fields(&mut metadata);
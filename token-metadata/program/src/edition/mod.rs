@@ -0,0 +1,45 @@
+pub mod print_config;
+pub mod remove_print_config;
+pub mod set_print_config;
+pub mod set_print_template;
+
+pub use print_config::*;
+pub use remove_print_config::*;
+pub use set_print_config::*;
+pub use set_print_template::*;
+
+use crate::{
+    state::{MasterEditionV2, Metadata, TokenMetadataAccount, EDITION, PREFIX},
+    utils::{assert_derivation, assert_owned_by, assert_update_authority_is_correct},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// Loads the master edition of `metadata_info` after checking `update_authority_info` may change
+/// its print settings.
+pub fn load_master_edition_for_update(
+    program_id: &Pubkey,
+    master_edition_info: &AccountInfo,
+    metadata_info: &AccountInfo,
+    update_authority_info: &AccountInfo,
+) -> Result<(Metadata, MasterEditionV2), ProgramError> {
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(master_edition_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    assert_derivation(
+        program_id,
+        master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    let master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+
+    Ok((metadata, master_edition))
+}
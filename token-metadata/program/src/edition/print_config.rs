@@ -0,0 +1,224 @@
+use crate::{
+    error::MetadataError,
+    pda::{find_print_config_account, find_print_record_account},
+    state::{
+        Key, MasterEditionV2, Metadata, PrintConfig, PrintRecord, TokenMetadataAccount, PREFIX,
+        PRINT_CONFIG, PRINT_RECORD_SIZE,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_token_program_matches_package,
+        create_or_allocate_account_raw,
+    },
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::invoke,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction, sysvar,
+    sysvar::Sysvar,
+};
+use spl_token::state::Account;
+use std::slice::Iter;
+
+pub fn print_config_seeds<'a>(program_id: &'a Pubkey, mint: &'a Pubkey) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        PRINT_CONFIG.as_bytes(),
+    ]
+}
+
+/// Accounts appended to `MintNewEditionFromMasterEditionViaToken` when the master edition has a
+/// print config.
+///
+///   0. `[]` Print config account (pda of ['metadata', program id, master mint id, 'print_config'])
+///   1. `[writable]` Print record of the payer (pda of ['metadata', program id, master mint id, 'print_config', payer])
+///   2. `[writable]` Payment destination, when the print config has a price
+///   3. `[writable]` Payer token account, when the price is paid in an SPL token
+pub fn print_config_accounts(
+    master_mint: Pubkey,
+    payer: Pubkey,
+    payment_destination: Option<Pubkey>,
+    payer_token_account: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (print_config, _) = find_print_config_account(&master_mint);
+    let (print_record, _) = find_print_record_account(&master_mint, &payer);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(print_config, false),
+        AccountMeta::new(print_record, false),
+    ];
+
+    if let Some(payment_destination) = payment_destination {
+        accounts.push(AccountMeta::new(payment_destination, false));
+    }
+
+    if let Some(payer_token_account) = payer_token_account {
+        accounts.push(AccountMeta::new(payer_token_account, false));
+    }
+
+    accounts
+}
+
+/// Enforces the print config attached to the master edition, if any, reading its accounts from
+/// the remaining accounts of a print instruction.
+pub fn apply_print_config<'a>(
+    program_id: &Pubkey,
+    master_edition_info: &'a AccountInfo<'a>,
+    master_metadata_info: &'a AccountInfo<'a>,
+    payer_info: &'a AccountInfo<'a>,
+    token_program_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+    remaining_accounts: &mut Iter<'a, AccountInfo<'a>>,
+) -> ProgramResult {
+    if master_edition_info.try_borrow_data()?[0] != Key::MasterEditionV2 as u8 {
+        return Ok(());
+    }
+
+    let master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+    if master_edition.print_config.is_none() {
+        return Ok(());
+    }
+
+    assert_owned_by(master_metadata_info, program_id)?;
+    let master_metadata = Metadata::from_account_info(master_metadata_info)?;
+    let print_config_key = match master_edition.print_config_of(program_id, &master_metadata.mint) {
+        Some(print_config_key) => print_config_key,
+        None => return Ok(()),
+    };
+
+    // Older clients still pass the optional rent sysvar first.
+    if let Some(account) = remaining_accounts.clone().next() {
+        if sysvar::rent::check_id(account.key) {
+            remaining_accounts.next();
+        }
+    }
+
+    let print_config_info = next_account_info(remaining_accounts)?;
+    let print_record_info = next_account_info(remaining_accounts)?;
+
+    if *print_config_info.key != print_config_key {
+        return Err(MetadataError::InvalidPrintConfig.into());
+    }
+
+    assert_owned_by(print_config_info, program_id)?;
+    let print_config = PrintConfig::from_account_info(print_config_info)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if let Some(start_time) = print_config.start_time {
+        if now < start_time {
+            return Err(MetadataError::PrintingNotStarted.into());
+        }
+    }
+
+    if let Some(end_time) = print_config.end_time {
+        if now > end_time {
+            return Err(MetadataError::PrintingEnded.into());
+        }
+    }
+
+    if let Some(max_per_wallet) = print_config.max_per_wallet {
+        let mut print_record_path = print_config_seeds(program_id, &print_config.mint);
+        print_record_path.push(payer_info.key.as_ref());
+        let bump = assert_derivation(program_id, print_record_info, &print_record_path)?;
+
+        let mut print_record = if print_record_info.data_is_empty() {
+            let bump_seed = &[bump];
+            let mut print_record_seeds = print_record_path.clone();
+            print_record_seeds.push(bump_seed);
+
+            create_or_allocate_account_raw(
+                *program_id,
+                print_record_info,
+                system_program_info,
+                payer_info,
+                PRINT_RECORD_SIZE,
+                &print_record_seeds,
+            )?;
+
+            PrintRecord {
+                key: Key::PrintRecord,
+                bump,
+                count: 0,
+            }
+        } else {
+            assert_owned_by(print_record_info, program_id)?;
+            PrintRecord::from_account_info(print_record_info)?
+        };
+
+        if print_record.count >= max_per_wallet {
+            return Err(MetadataError::PrintLimitReached.into());
+        }
+
+        print_record.count = print_record
+            .count
+            .checked_add(1)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        BorshSerialize::serialize(
+            &print_record,
+            &mut *print_record_info.try_borrow_mut_data()?,
+        )?;
+    }
+
+    if let Some(price) = print_config.price {
+        let destination_info = next_account_info(remaining_accounts)?;
+
+        if *destination_info.key != price.destination {
+            return Err(MetadataError::PaymentDestinationMismatch.into());
+        }
+
+        match price.mint {
+            Some(mint) => {
+                let payer_token_account_info = next_account_info(remaining_accounts)?;
+
+                assert_token_program_matches_package(token_program_info)?;
+                assert_owned_by(destination_info, &spl_token::id())?;
+                let destination = Account::unpack(&destination_info.try_borrow_data()?)?;
+                if destination.mint != mint {
+                    return Err(MetadataError::PaymentDestinationMismatch.into());
+                }
+
+                // The token program checks the payer owns the source account of the same mint.
+                invoke(
+                    &spl_token::instruction::transfer(
+                        token_program_info.key,
+                        payer_token_account_info.key,
+                        destination_info.key,
+                        payer_info.key,
+                        &[],
+                        price.amount,
+                    )?,
+                    &[
+                        payer_token_account_info.clone(),
+                        destination_info.clone(),
+                        payer_info.clone(),
+                        token_program_info.clone(),
+                    ],
+                )?;
+            }
+            None => {
+                invoke(
+                    &system_instruction::transfer(
+                        payer_info.key,
+                        destination_info.key,
+                        price.amount,
+                    ),
+                    &[
+                        payer_info.clone(),
+                        destination_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::{
+    edition::{load_master_edition_for_update, print_config_seeds},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    utils::{assert_derivation, assert_owned_by, close_account_raw},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Remove Print Config
+///
+/// Detaches the print config from a master edition and closes the print config account.
+///
+///### Accounts:
+///
+///   0. `[writable]` Print config account (pda of ['metadata', program id, mint id, 'print_config'])
+///   1. `[writable]` Master edition account (pda of ['metadata', program id, mint id, 'edition'])
+///   2. `[]` Metadata account of the master edition
+///   3. `[signer]` Update authority
+///   4. `[writable]` Account receiving the print config rent
+pub fn remove_print_config(
+    program_id: Pubkey,
+    print_config: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    receiver: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(print_config, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::RemovePrintConfig.try_to_vec().unwrap(),
+    }
+}
+
+pub fn process_remove_print_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let print_config_info = next_account_info(account_info_iter)?;
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+
+    let (metadata, mut master_edition) = load_master_edition_for_update(
+        program_id,
+        master_edition_info,
+        metadata_info,
+        update_authority_info,
+    )?;
+
+    assert_owned_by(print_config_info, program_id)?;
+    assert_derivation(
+        program_id,
+        print_config_info,
+        &print_config_seeds(program_id, &metadata.mint),
+    )?;
+
+    if master_edition.print_config != Some(*print_config_info.key) {
+        return Err(MetadataError::InvalidPrintConfig.into());
+    }
+
+    close_account_raw(receiver_info, print_config_info)?;
+
+    master_edition.print_config = None;
+    BorshSerialize::serialize(
+        &master_edition,
+        &mut *master_edition_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
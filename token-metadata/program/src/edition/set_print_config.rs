@@ -0,0 +1,159 @@
+use crate::{
+    edition::{load_master_edition_for_update, print_config_seeds},
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Key, PrintConfig, PrintPrice, TokenMetadataAccount, PRINT_CONFIG_SIZE},
+    utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetPrintConfigArgs {
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub max_per_wallet: Option<u64>,
+    pub price: Option<PrintPrice>,
+}
+
+///# Set Print Config
+///
+/// Creates or replaces the print config of a master edition. Prints can then only be minted
+/// inside the time window, up to the per wallet limit and against the price.
+///
+///### Accounts:
+///
+///   0. `[writable]` Print config account (pda of ['metadata', program id, mint id, 'print_config'])
+///   1. `[writable]` Master edition account (pda of ['metadata', program id, mint id, 'edition'])
+///   2. `[]` Metadata account of the master edition
+///   3. `[signer]` Update authority
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+#[allow(clippy::too_many_arguments)]
+pub fn set_print_config(
+    program_id: Pubkey,
+    print_config: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    max_per_wallet: Option<u64>,
+    price: Option<PrintPrice>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(print_config, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetPrintConfig(SetPrintConfigArgs {
+            start_time,
+            end_time,
+            max_per_wallet,
+            price,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_set_print_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetPrintConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let print_config_info = next_account_info(account_info_iter)?;
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_signer(payer_info)?;
+
+    let (metadata, mut master_edition) = load_master_edition_for_update(
+        program_id,
+        master_edition_info,
+        metadata_info,
+        update_authority_info,
+    )?;
+
+    if let (Some(start_time), Some(end_time)) = (args.start_time, args.end_time) {
+        if start_time > end_time {
+            return Err(MetadataError::InvalidPrintConfig.into());
+        }
+    }
+
+    if args.max_per_wallet == Some(0) {
+        return Err(MetadataError::InvalidPrintConfig.into());
+    }
+
+    let print_config_path = print_config_seeds(program_id, &metadata.mint);
+    let bump = assert_derivation(program_id, print_config_info, &print_config_path)?;
+
+    if print_config_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut print_config_seeds = print_config_path.clone();
+        print_config_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            print_config_info,
+            system_program_info,
+            payer_info,
+            PRINT_CONFIG_SIZE,
+            &print_config_seeds,
+        )?;
+    } else {
+        assert_owned_by(print_config_info, program_id)?;
+        // Make sure the account really is a print config before overwriting it.
+        PrintConfig::from_account_info(print_config_info)?;
+    }
+
+    let print_config = PrintConfig {
+        key: Key::PrintConfig,
+        mint: metadata.mint,
+        bump,
+        start_time: args.start_time,
+        end_time: args.end_time,
+        max_per_wallet: args.max_per_wallet,
+        price: args.price,
+    };
+    BorshSerialize::serialize(
+        &print_config,
+        &mut *print_config_info.try_borrow_mut_data()?,
+    )?;
+
+    master_edition.print_config = Some(*print_config_info.key);
+    BorshSerialize::serialize(
+        &master_edition,
+        &mut *master_edition_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::{
+    edition::load_master_edition_for_update, error::MetadataError,
+    instruction::MetadataInstruction, state::PrintTemplate,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetPrintTemplateArgs {
+    pub print_template: Option<PrintTemplate>,
+}
+
+///# Set Print Template
+///
+/// Sets or clears the print template of a master edition. Prints minted afterwards get the
+/// edition number appended to their name and/or a uri derived from the template; existing
+/// prints are not changed.
+///
+///### Accounts:
+///
+///   0. `[writable]` Master edition account (pda of ['metadata', program id, mint id, 'edition'])
+///   1. `[]` Metadata account of the master edition
+///   2. `[signer]` Update authority
+pub fn set_print_template(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    print_template: Option<PrintTemplate>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetPrintTemplate(SetPrintTemplateArgs { print_template })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_set_print_template(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetPrintTemplateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    let (_, mut master_edition) = load_master_edition_for_update(
+        program_id,
+        master_edition_info,
+        metadata_info,
+        update_authority_info,
+    )?;
+
+    if let Some(print_template) = &args.print_template {
+        if !print_template.is_valid() {
+            return Err(MetadataError::InvalidPrintTemplate.into());
+        }
+    }
+
+    master_edition.print_template = args.print_template;
+    BorshSerialize::serialize(
+        &master_edition,
+        &mut *master_edition_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
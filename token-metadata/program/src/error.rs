@@ -524,6 +524,198 @@ pub enum MetadataError {
     /// 132
     #[error("Invalid bubblegum signer")]
     InvalidBubblegumSigner,
+
+    /// 133
+    #[error("Invalid token standard for this operation")]
+    InvalidTokenStandard,
+
+    /// 134
+    #[error("Creator account does not match the creators on the metadata")]
+    InvalidCreatorAccount,
+
+    /// 135
+    #[error("Delegate is not allowed by the rule set")]
+    DelegateNotAllowedByRuleSet,
+
+    /// 136
+    #[error("Rule set account is missing or invalid")]
+    InvalidRuleSet,
+
+    /// 137
+    #[error("Metadata delegate record is invalid")]
+    InvalidMetadataDelegate,
+
+    /// 138
+    #[error("Delegate role does not allow updating this field")]
+    DelegateRoleCannotUpdateField,
+
+    /// 139
+    #[error("Metadata account is too small for its data, resize it first")]
+    MetadataAccountTooSmall,
+
+    /// 140
+    #[error("Invalid metadata account size")]
+    InvalidMetadataSize,
+
+    /// 141
+    #[error("Attribute key or value is empty or too long")]
+    InvalidAttribute,
+
+    /// 142
+    #[error("Too many attributes")]
+    TooManyAttributes,
+
+    /// 143
+    #[error("Attribute not found")]
+    AttributeNotFound,
+
+    /// 144
+    #[error("No collection items were provided")]
+    NoCollectionItems,
+
+    /// 145
+    #[error("Print template must number the name or contain an {{edition}} uri placeholder")]
+    InvalidPrintTemplate,
+
+    /// 146
+    #[error("Print config is invalid or does not match the master edition")]
+    InvalidPrintConfig,
+
+    /// 147
+    #[error("Printing has not started yet")]
+    PrintingNotStarted,
+
+    /// 148
+    #[error("Printing has ended")]
+    PrintingEnded,
+
+    /// 149
+    #[error("Wallet reached the print limit")]
+    PrintLimitReached,
+
+    /// 150
+    #[error("Payment destination does not match the print config")]
+    PaymentDestinationMismatch,
+
+    /// 151
+    #[error("Collection still has verified items, burning requires force")]
+    CollectionNotEmpty,
+
+    /// 152
+    #[error("Account is not a use authority or collection authority record")]
+    InvalidDelegateRecord,
+
+    /// 153
+    #[error("Escrow still holds tokens")]
+    EscrowHasHoldings,
+
+    /// 154
+    #[error("Escrow nesting exceeds the maximum depth")]
+    EscrowTooDeep,
+
+    /// 155
+    #[error("Escrow cannot hold its own base token or one of its ancestors")]
+    InvalidEscrowNesting,
+
+    /// 156
+    #[error("Use schedule is invalid or doesn't match the use method")]
+    InvalidUseSchedule,
+
+    /// 157
+    #[error("Uses have expired")]
+    UsesExpired,
+
+    /// 158
+    #[error("No recharge period has elapsed yet")]
+    RechargeNotDue,
+
+    /// 159
+    #[error("Use authority has expired")]
+    UseAuthorityExpired,
+
+    /// 160
+    #[error("Use authority must be invoked through its caller program")]
+    InvalidCallerProgram,
+
+    /// 161
+    #[error("Collection authority is not permitted to perform this operation")]
+    CollectionAuthorityNotPermitted,
+
+    /// 162
+    #[error("Collection authority has expired")]
+    CollectionAuthorityExpired,
+
+    /// 163
+    #[error("Collection permissions must be a non-empty set of known permissions")]
+    InvalidCollectionPermissions,
+
+    /// 164
+    #[error("Collection creators must be distinct verified creators of the collection")]
+    InvalidCollectionCreators,
+
+    /// 165
+    #[error("Signer is not a verified creator of both the collection and the item")]
+    NotACollectionCreator,
+
+    /// 166
+    #[error("Shares of verified creators can only be changed through a creators proposal")]
+    CannotChangeVerifiedCreatorShare,
+
+    /// 167
+    #[error("Signer is not a verified creator of the current or proposed creators")]
+    InvalidProposalApprover,
+
+    /// 168
+    #[error("Approved creators do not match the proposed creators")]
+    CreatorsProposalMismatch,
+
+    /// 169
+    #[error("Every verified creator of the current and proposed creators must approve")]
+    CreatorsProposalNotApproved,
+
+    /// 170
+    #[error("Minting would exceed the max supply of the fungible config")]
+    SupplyCapExceeded,
+
+    /// 171
+    #[error("Max supply is below the current supply of the mint")]
+    MaxSupplyBelowCurrentSupply,
+
+    /// 172
+    #[error("Validation rules contain unknown flags")]
+    InvalidValidationRules,
+
+    /// 173
+    #[error("Uri must use the https, ar or ipfs scheme")]
+    UriSchemeNotAllowed,
+
+    /// 174
+    #[error("Name must only contain printable ASCII characters")]
+    NameCharsetNotAllowed,
+
+    /// 175
+    #[error("Symbol must only contain uppercase letters and digits")]
+    SymbolNotUppercase,
+
+    /// 176
+    #[error("Name, symbol and uri must not contain zero-width characters")]
+    ZeroWidthCharacterNotAllowed,
+
+    /// 177
+    #[error("Transfers without a sale price must be made by the update authority or an authority allowlisted by the rule set")]
+    RoyaltiesRequired,
+
+    /// 178
+    #[error("Rent must be refunded to the account entitled to it")]
+    RentReceiverMismatch,
+
+    /// 179
+    #[error("The validation profile account of the collection is missing")]
+    MissingValidationProfile,
+
+    /// 180
+    #[error("The max supply of a fungible config can only be lowered")]
+    MaxSupplyCannotBeRaised,
 }
 
 impl PrintProgramError for MetadataError {
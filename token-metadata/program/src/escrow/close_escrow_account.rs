@@ -1,10 +1,8 @@
 use crate::{
     error::MetadataError,
+    escrow::pda::find_escrow_seeds,
     instruction::MetadataInstruction,
-    state::{
-        EscrowAuthority, Metadata, TokenMetadataAccount, TokenOwnedEscrow, TokenStandard,
-        ESCROW_POSTFIX, PREFIX,
-    },
+    state::{EscrowAuthority, Metadata, TokenMetadataAccount, TokenOwnedEscrow, TokenStandard},
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
         check_token_standard, close_account_raw,
@@ -85,21 +83,16 @@ pub fn process_close_escrow_account(
         return Err(MetadataError::MustBeNonFungible.into());
     };
 
+    assert_owned_by(escrow_account_info, program_id)?;
+    let token_account: spl_token::state::Account = assert_initialized(token_account_info)?;
+    let toe = TokenOwnedEscrow::from_account_info(escrow_account_info)?;
+
     let bump_seed = assert_derivation(
         program_id,
         escrow_account_info,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            mint_account_info.key.as_ref(),
-            ESCROW_POSTFIX.as_bytes(),
-        ],
+        &find_escrow_seeds(mint_account_info.key, &toe.authority),
     )?;
 
-    assert_owned_by(escrow_account_info, program_id)?;
-    let token_account: spl_token::state::Account = assert_initialized(token_account_info)?;
-    let toe = TokenOwnedEscrow::from_account_info(escrow_account_info)?;
-
     if bump_seed != toe.bump {
         return Err(MetadataError::InvalidEscrowBumpSeed.into());
     }
@@ -117,6 +110,11 @@ pub fn process_close_escrow_account(
         }
     }
 
+    // Closing would strand the tracked tokens in ATAs nobody can sign for.
+    if !toe.holdings.is_empty() {
+        return Err(MetadataError::EscrowHasHoldings.into());
+    }
+
     // Close the account.
     close_account_raw(payer_account_info, escrow_account_info)?;
 
@@ -137,6 +137,8 @@ pub fn process_create_escrow_account(
         base_token: *mint_account_info.key,
         authority: creator_type,
         bump: bump_seed[0],
+        holdings: vec![],
+        parent: None,
     };
 
     let serialized_data = toe
@@ -1,9 +1,13 @@
 pub mod close_escrow_account;
 pub mod create_escrow_account;
+pub mod nesting;
 pub mod pda;
+pub mod transfer_in;
 pub mod transfer_out;
 
 pub use close_escrow_account::*;
 pub use create_escrow_account::*;
+pub use nesting::*;
 pub use pda::*;
+pub use transfer_in::*;
 pub use transfer_out::*;
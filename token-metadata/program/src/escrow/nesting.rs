@@ -0,0 +1,114 @@
+use crate::{
+    error::MetadataError,
+    escrow::pda::find_escrow_seeds,
+    state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow, MAX_ESCROW_DEPTH},
+    utils::{
+        assert_derivation, assert_owned_by, resize_or_reallocate_account_raw, shrink_account_raw,
+    },
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use std::slice::Iter;
+
+pub type LoadedEscrow<'b, 'a> = (&'b AccountInfo<'a>, TokenOwnedEscrow);
+
+/// Takes the token-owner escrow of `mint` from the remaining accounts, checked by derivation.
+/// Returns `None` when the account is uninitialized, i.e. the token has no escrow.
+pub fn next_nested_escrow<'b, 'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    account_info_iter: &mut Iter<'b, AccountInfo<'a>>,
+) -> Result<Option<LoadedEscrow<'b, 'a>>, ProgramError> {
+    let escrow_info = next_account_info(account_info_iter)?;
+    assert_derivation(
+        program_id,
+        escrow_info,
+        &find_escrow_seeds(mint, &EscrowAuthority::TokenOwner),
+    )
+    .map_err(|_| MetadataError::InvalidEscrowNesting)?;
+
+    if escrow_info.data_is_empty() {
+        return Ok(None);
+    }
+    assert_owned_by(escrow_info, program_id)?;
+
+    let escrow = TokenOwnedEscrow::from_account_info(escrow_info)?;
+    Ok(Some((escrow_info, escrow)))
+}
+
+/// Loads the escrows holding `escrow` by following parent links. They must be passed in order,
+/// from the direct parent up to the root.
+pub fn load_escrow_ancestors<'b, 'a>(
+    program_id: &Pubkey,
+    escrow_info: &AccountInfo,
+    escrow: &TokenOwnedEscrow,
+    account_info_iter: &mut Iter<'b, AccountInfo<'a>>,
+) -> Result<Vec<LoadedEscrow<'b, 'a>>, ProgramError> {
+    let mut ancestors: Vec<LoadedEscrow> = vec![];
+    let mut parent = escrow.parent;
+
+    while let Some(parent_key) = parent {
+        if ancestors.len() >= MAX_ESCROW_DEPTH as usize {
+            return Err(MetadataError::EscrowTooDeep.into());
+        }
+
+        let ancestor_info = next_account_info(account_info_iter)?;
+        if *ancestor_info.key != parent_key || ancestor_info.key == escrow_info.key {
+            return Err(MetadataError::InvalidEscrowNesting.into());
+        }
+        assert_owned_by(ancestor_info, program_id)?;
+
+        let ancestor = TokenOwnedEscrow::from_account_info(ancestor_info)?;
+        parent = ancestor.parent;
+        ancestors.push((ancestor_info, ancestor));
+    }
+
+    Ok(ancestors)
+}
+
+/// Records the current height of `escrow` in each of its ancestors.
+pub fn propagate_escrow_height(
+    escrow: &TokenOwnedEscrow,
+    ancestors: &mut [LoadedEscrow],
+) -> ProgramResult {
+    let mut child_base_token = escrow.base_token;
+    let mut child_height = escrow.height();
+
+    for (ancestor_info, ancestor) in ancestors.iter_mut() {
+        if !ancestor.set_nested_height(&child_base_token, child_height) {
+            return Err(MetadataError::InvalidEscrowNesting.into());
+        }
+        // Heights are fixed size, so the account length is unchanged.
+        BorshSerialize::serialize(&*ancestor, &mut *ancestor_info.try_borrow_mut_data()?)?;
+
+        child_base_token = ancestor.base_token;
+        child_height = ancestor.height();
+    }
+
+    Ok(())
+}
+
+/// Writes `escrow`, resizing its account to the serialized length.
+pub fn write_escrow<'a>(
+    escrow_info: &AccountInfo<'a>,
+    escrow: &TokenOwnedEscrow,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let escrow_len = escrow.serialized_len();
+
+    if escrow_info.data_len() < escrow_len {
+        resize_or_reallocate_account_raw(escrow_info, payer_info, system_program_info, escrow_len)?;
+    } else if escrow_info.data_len() > escrow_len {
+        shrink_account_raw(escrow_info, payer_info, escrow_len)?;
+    }
+
+    BorshSerialize::serialize(escrow, &mut *escrow_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use crate::{
+    error::MetadataError,
+    escrow::{
+        load_escrow_ancestors, next_nested_escrow,
+        pda::{find_escrow_account, find_escrow_seeds},
+        propagate_escrow_height, write_escrow,
+    },
+    events::{emit_event, MetadataEventV1},
+    instruction::MetadataInstruction,
+    state::{EscrowAuthority, Metadata, TokenMetadataAccount, TokenOwnedEscrow, MAX_ESCROW_DEPTH},
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use spl_token::state::is_initialized_account;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TransferIntoEscrowArgs {
+    pub amount: u64,
+}
+
+///# Transfer Into Escrow
+///
+/// Transfers tokens of the escrow authority into the escrow and records them as holdings. When
+/// the transferred token owns an escrow itself, that escrow is nested under this one.
+///
+///### Accounts:
+///
+///   0. `[writable]` Escrow account
+///   1. `[]` Metadata account of the token the escrow is attached to
+///   2. `[writable, signer]` Escrow authority, owner of the source tokens and payer
+///   3. `[]` Mint of the transferred token
+///   4. `[writable]` Source token account
+///   5. `[writable]` Escrow associated token account, created when missing
+///   6. `[]` Mint of the token the escrow is attached to
+///   7. `[]` Token account holding the token the escrow is attached to
+///   8. `[]` System program
+///   9. `[]` Associated Token program
+///   10. `[]` Token program
+///   11. `[writable]` Token-owner escrow of the transferred mint (pda of ['metadata', program id, mint id, 0, 'escrow']), may be uninitialized
+///   12. Optional `[writable]` Escrows holding this escrow, from the direct parent up to the root
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_into_escrow(
+    program_id: Pubkey,
+    escrow: Pubkey,
+    metadata: Pubkey,
+    payer: Pubkey,
+    attribute_mint: Pubkey,
+    attribute_src: Pubkey,
+    attribute_dst: Pubkey,
+    escrow_mint: Pubkey,
+    escrow_account: Pubkey,
+    ancestor_escrows: Vec<Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(attribute_mint, false),
+        AccountMeta::new(attribute_src, false),
+        AccountMeta::new(attribute_dst, false),
+        AccountMeta::new_readonly(escrow_mint, false),
+        AccountMeta::new_readonly(escrow_account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    let (nested_escrow, _) = find_escrow_account(&attribute_mint, &EscrowAuthority::TokenOwner);
+    accounts.push(AccountMeta::new(nested_escrow, false));
+
+    for ancestor in ancestor_escrows {
+        accounts.push(AccountMeta::new(ancestor, false));
+    }
+
+    let data = MetadataInstruction::TransferIntoEscrow(TransferIntoEscrowArgs { amount })
+        .try_to_vec()
+        .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn process_transfer_into_escrow<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'a>],
+    args: TransferIntoEscrowArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let escrow_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let attribute_mint_info = next_account_info(account_info_iter)?;
+    let attribute_src_info = next_account_info(account_info_iter)?;
+    let attribute_dst_info = next_account_info(account_info_iter)?;
+    let escrow_mint_info = next_account_info(account_info_iter)?;
+    let escrow_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let ata_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    if *system_account_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+    assert_token_program_matches_package(token_program_info)?;
+    assert_signer(payer_info)?;
+
+    assert_owned_by(escrow_info, program_id)?;
+    let mut toe = TokenOwnedEscrow::from_account_info(escrow_info)?;
+
+    if toe.base_token != *escrow_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let escrow_seeds = find_escrow_seeds(escrow_mint_info.key, &toe.authority);
+    assert_derivation(&crate::id(), escrow_info, &escrow_seeds)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.mint != *escrow_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // Check that the authority matches based on the authority type.
+    match toe.authority {
+        EscrowAuthority::TokenOwner => {
+            assert_owned_by(escrow_account_info, &spl_token::id())?;
+            let escrow_account: spl_token::state::Account =
+                assert_initialized(escrow_account_info)?;
+            if escrow_account.mint != *escrow_mint_info.key {
+                return Err(MetadataError::MintMismatch.into());
+            }
+            if escrow_account.amount < 1 || escrow_account.owner != *payer_info.key {
+                return Err(MetadataError::MustBeEscrowAuthority.into());
+            }
+        }
+        EscrowAuthority::Creator(creator) => {
+            if creator != *payer_info.key {
+                return Err(MetadataError::MustBeEscrowAuthority.into());
+            }
+        }
+    }
+
+    if *attribute_mint_info.key == toe.base_token {
+        return Err(MetadataError::InvalidEscrowNesting.into());
+    }
+
+    // Allocate the escrow ATA if it doesn't exist.
+    if !is_initialized_account(*attribute_dst_info.data.borrow()) {
+        #[allow(deprecated)]
+        let create_escrow_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                payer_info.key,
+                escrow_info.key,
+                attribute_mint_info.key,
+            );
+
+        invoke(
+            &create_escrow_ata_ix,
+            &[
+                payer_info.clone(),
+                attribute_dst_info.clone(),
+                escrow_info.clone(),
+                attribute_mint_info.clone(),
+                system_account_info.clone(),
+                token_program_info.clone(),
+                ata_program_info.clone(),
+            ],
+        )?;
+    }
+
+    let attribute_dst = spl_token::state::Account::unpack(&attribute_dst_info.data.borrow())?;
+    if attribute_dst.mint != *attribute_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if attribute_dst.owner != *escrow_info.key {
+        return Err(MetadataError::IncorrectOwner.into());
+    }
+
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        attribute_src_info.key,
+        attribute_dst_info.key,
+        payer_info.key,
+        &[payer_info.key],
+        args.amount,
+    )?;
+
+    invoke(
+        &transfer_ix,
+        &[
+            attribute_src_info.clone(),
+            attribute_dst_info.clone(),
+            payer_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    let nested = next_nested_escrow(program_id, attribute_mint_info.key, account_info_iter)?;
+    let mut ancestors = load_escrow_ancestors(program_id, escrow_info, &toe, account_info_iter)?;
+
+    let nested_height = match &nested {
+        Some((nested_info, nested_escrow)) => {
+            if nested_info.key == escrow_info.key
+                || ancestors
+                    .iter()
+                    .any(|(ancestor_info, _)| ancestor_info.key == nested_info.key)
+            {
+                return Err(MetadataError::InvalidEscrowNesting.into());
+            }
+            nested_escrow.height()
+        }
+        None => 0,
+    };
+
+    let old_holding = toe.holding_amount(attribute_mint_info.key);
+    toe.add_holding(*attribute_mint_info.key, args.amount, nested_height)?;
+
+    if ancestors.len() + toe.height() as usize > MAX_ESCROW_DEPTH as usize {
+        return Err(MetadataError::EscrowTooDeep.into());
+    }
+
+    write_escrow(escrow_info, &toe, payer_info, system_account_info)?;
+
+    if let Some((nested_info, mut nested_escrow)) = nested {
+        nested_escrow.parent = Some(*escrow_info.key);
+        write_escrow(nested_info, &nested_escrow, payer_info, system_account_info)?;
+    }
+
+    propagate_escrow_height(&toe, &mut ancestors)?;
+
+    emit_event(MetadataEventV1::EscrowTransfer {
+        escrow: *escrow_info.key,
+        attribute_mint: *attribute_mint_info.key,
+        into_escrow: true,
+        amount: args.amount,
+        old_holding,
+        new_holding: toe.holding_amount(attribute_mint_info.key),
+    })
+}
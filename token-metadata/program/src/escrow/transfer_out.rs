@@ -1,6 +1,10 @@
 use crate::{
     error::MetadataError,
-    escrow::pda::find_escrow_seeds,
+    escrow::{
+        load_escrow_ancestors, next_nested_escrow, pda::find_escrow_seeds, propagate_escrow_height,
+        write_escrow,
+    },
+    events::{emit_event, MetadataEventV1},
     instruction::MetadataInstruction,
     state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow},
     utils::{assert_derivation, assert_owned_by, assert_signer},
@@ -27,6 +31,7 @@ pub struct TransferOutOfEscrowArgs {
     pub amount: u64,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_out_of_escrow(
     program_id: Pubkey,
     escrow: Pubkey,
@@ -38,10 +43,12 @@ pub fn transfer_out_of_escrow(
     escrow_mint: Pubkey,
     escrow_account: Pubkey,
     authority: Option<Pubkey>,
+    nested_escrow: Option<Pubkey>,
+    ancestor_escrows: Vec<Pubkey>,
     amount: u64,
 ) -> Instruction {
     let mut accounts = vec![
-        AccountMeta::new_readonly(escrow, false),
+        AccountMeta::new(escrow, false),
         AccountMeta::new(metadata, false),
         AccountMeta::new(payer, true),
         AccountMeta::new_readonly(attribute_mint, false),
@@ -59,6 +66,14 @@ pub fn transfer_out_of_escrow(
         accounts.push(AccountMeta::new_readonly(authority, true));
     }
 
+    if let Some(nested_escrow) = nested_escrow {
+        accounts.push(AccountMeta::new(nested_escrow, false));
+    }
+
+    for ancestor in ancestor_escrows {
+        accounts.push(AccountMeta::new(ancestor, false));
+    }
+
     let data = MetadataInstruction::TransferOutOfEscrow(TransferOutOfEscrowArgs { amount })
         .try_to_vec()
         .unwrap();
@@ -70,9 +85,9 @@ pub fn transfer_out_of_escrow(
     }
 }
 
-pub fn process_transfer_out_of_escrow(
+pub fn process_transfer_out_of_escrow<'a>(
     program_id: &Pubkey,
-    accounts: &[AccountInfo],
+    accounts: &[AccountInfo<'a>],
     args: TransferOutOfEscrowArgs,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
@@ -90,19 +105,19 @@ pub fn process_transfer_out_of_escrow(
     let token_program_info = next_account_info(account_info_iter)?;
     let _sysvar_ix_account_info = next_account_info(account_info_iter)?;
 
-    // Allow the option to set a different authority than the payer.
-    let is_using_authority = account_info_iter.len() == 1;
-    let maybe_authority_info: Option<&AccountInfo> = if is_using_authority {
-        let auth = next_account_info(account_info_iter)?;
-        assert_signer(auth)?;
-        Some(auth)
-    } else {
-        None
+    // Allow the option to set a different authority than the payer. Escrows passed after it
+    // are PDAs, so only the authority can be a signer.
+    let maybe_authority_info: Option<&AccountInfo> = match account_info_iter.clone().next() {
+        Some(auth) if auth.is_signer => {
+            account_info_iter.next();
+            Some(auth)
+        }
+        _ => None,
     };
     let authority = maybe_authority_info.unwrap_or(payer_info);
 
     assert_owned_by(escrow_info, program_id)?;
-    let toe = TokenOwnedEscrow::from_account_info(escrow_info)?;
+    let mut toe = TokenOwnedEscrow::from_account_info(escrow_info)?;
 
     // Derive the seeds for PDA signing.
     let escrow_seeds = find_escrow_seeds(escrow_mint_info.key, &toe.authority);
@@ -205,5 +220,29 @@ pub fn process_transfer_out_of_escrow(
         &[&escrow_authority_seeds],
     )?;
 
-    Ok(())
+    // Tokens deposited before holdings were tracked are not recorded.
+    let old_holding = toe.holding_amount(attribute_mint_info.key);
+    if let Some(nested_height) = toe.remove_holding(attribute_mint_info.key, args.amount) {
+        if nested_height > 0 {
+            let (nested_info, mut nested_escrow) =
+                next_nested_escrow(program_id, attribute_mint_info.key, account_info_iter)?
+                    .ok_or(MetadataError::InvalidEscrowNesting)?;
+            nested_escrow.parent = None;
+            write_escrow(nested_info, &nested_escrow, payer_info, system_account_info)?;
+        }
+
+        let mut ancestors =
+            load_escrow_ancestors(program_id, escrow_info, &toe, account_info_iter)?;
+        write_escrow(escrow_info, &toe, payer_info, system_account_info)?;
+        propagate_escrow_height(&toe, &mut ancestors)?;
+    }
+
+    emit_event(MetadataEventV1::EscrowTransfer {
+        escrow: *escrow_info.key,
+        attribute_mint: *attribute_mint_info.key,
+        into_escrow: false,
+        amount: args.amount,
+        old_holding,
+        new_holding: toe.holding_amount(attribute_mint_info.key),
+    })
 }
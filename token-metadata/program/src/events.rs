@@ -0,0 +1,171 @@
+//! Events logged by state-changing instructions through `sol_log_data`, so indexers can learn what
+//! changed without re-fetching accounts. Each log holds a single borsh-encoded [`MetadataEvent`].
+
+use crate::{
+    error::MetadataError,
+    state::{Collection, CollectionDetails, Data, Metadata, Uses},
+    utils::{get_mint_supply, unpuffed_string},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+/// Versioned envelope of the events, new versions are appended so older logs keep decoding.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum MetadataEvent {
+    V1(MetadataEventV1),
+}
+
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum MetadataEventV1 {
+    /// A metadata account was created.
+    Create {
+        metadata: Pubkey,
+        mint: Pubkey,
+        values: MetadataValues,
+    },
+    /// The values of a metadata account were changed.
+    Update {
+        metadata: Pubkey,
+        mint: Pubkey,
+        old: MetadataValues,
+        new: MetadataValues,
+    },
+    /// An item was verified or unverified as a member of a collection.
+    CollectionVerification {
+        metadata: Pubkey,
+        mint: Pubkey,
+        old_collection: Option<Collection>,
+        new_collection: Option<Collection>,
+        /// Size of the collection, `None` for unsized collections
+        old_collection_size: Option<u64>,
+        new_collection_size: Option<u64>,
+    },
+    /// A print edition was minted from a master edition.
+    MintEdition {
+        master_mint: Pubkey,
+        edition_mint: Pubkey,
+        edition: u64,
+        old_supply: u64,
+        new_supply: u64,
+    },
+    /// Uses of an asset were consumed.
+    Utilize {
+        mint: Pubkey,
+        user: Pubkey,
+        old_remaining: u64,
+        new_remaining: u64,
+    },
+    /// Tokens of an asset were burned.
+    Burn {
+        mint: Pubkey,
+        old_supply: u64,
+        new_supply: u64,
+    },
+    /// Tokens were transferred into or out of a token owned escrow.
+    EscrowTransfer {
+        escrow: Pubkey,
+        attribute_mint: Pubkey,
+        into_escrow: bool,
+        amount: u64,
+        /// Amount of the attribute mint tracked by the escrow
+        old_holding: u64,
+        new_holding: u64,
+    },
+}
+
+/// The values of a metadata account an update can change, with the string padding stripped.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MetadataValues {
+    pub update_authority: Pubkey,
+    pub data: Data,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+impl From<&Metadata> for MetadataValues {
+    fn from(metadata: &Metadata) -> Self {
+        MetadataValues {
+            update_authority: metadata.update_authority,
+            data: Data {
+                name: unpuffed_string(&metadata.data.name),
+                symbol: unpuffed_string(&metadata.data.symbol),
+                uri: unpuffed_string(&metadata.data.uri),
+                ..metadata.data.clone()
+            },
+            primary_sale_happened: metadata.primary_sale_happened,
+            is_mutable: metadata.is_mutable,
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        }
+    }
+}
+
+/// Size of a sized collection parent, `None` when unsized.
+pub fn collection_size(collection_metadata: &Metadata) -> Option<u64> {
+    collection_metadata
+        .collection_details
+        .as_ref()
+        .map(|details| match details {
+            CollectionDetails::V1 { size } => *size,
+        })
+}
+
+/// Logs `event` as the current event version.
+pub fn emit_event(event: MetadataEventV1) -> ProgramResult {
+    sol_log_data(&[&MetadataEvent::V1(event).try_to_vec()?]);
+    Ok(())
+}
+
+/// Logs an [`MetadataEventV1::Update`] from the metadata values before and after the update.
+pub fn emit_update(
+    metadata_key: &Pubkey,
+    old: MetadataValues,
+    metadata: &Metadata,
+) -> ProgramResult {
+    emit_event(MetadataEventV1::Update {
+        metadata: *metadata_key,
+        mint: metadata.mint,
+        old,
+        new: metadata.into(),
+    })
+}
+
+/// Logs a [`MetadataEventV1::CollectionVerification`] for `metadata` after its collection changed.
+pub fn emit_collection_verification(
+    metadata_key: &Pubkey,
+    old_collection: Option<Collection>,
+    metadata: &Metadata,
+    old_collection_size: Option<u64>,
+    collection_metadata: &Metadata,
+) -> ProgramResult {
+    emit_event(MetadataEventV1::CollectionVerification {
+        metadata: *metadata_key,
+        mint: metadata.mint,
+        old_collection,
+        new_collection: metadata.collection.clone(),
+        old_collection_size,
+        new_collection_size: collection_size(collection_metadata),
+    })
+}
+
+/// Logs a [`MetadataEventV1::Burn`] after `amount` tokens of `mint_info` were burned.
+pub fn emit_burn(mint_info: &AccountInfo, amount: u64) -> ProgramResult {
+    let new_supply = get_mint_supply(mint_info)?;
+    emit_event(MetadataEventV1::Burn {
+        mint: *mint_info.key,
+        old_supply: new_supply
+            .checked_add(amount)
+            .ok_or(MetadataError::NumericalOverflowError)?,
+        new_supply,
+    })
+}
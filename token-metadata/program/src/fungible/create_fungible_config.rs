@@ -0,0 +1,176 @@
+use crate::{
+    error::MetadataError,
+    fungible::fungible_config_seeds,
+    instruction::MetadataInstruction,
+    state::{
+        FungibleConfig, Key, Metadata, TokenMetadataAccount, TokenStandard, FUNGIBLE_CONFIG_SIZE,
+    },
+    utils::{
+        assert_derivation, assert_mint_authority_matches_mint, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, assert_update_authority_is_correct,
+        create_or_allocate_account_raw, get_mint_authority, get_mint_decimals, get_mint_supply,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_token::instruction::{set_authority, AuthorityType};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CreateFungibleConfigArgs {
+    /// Maximum supply in base units of the mint
+    pub max_supply: u64,
+}
+
+///# Create Fungible Config
+///
+/// Caps the supply of a fungible asset. The mint authority is handed off to the fungible config,
+/// after which the previous mint authority can only mint through `MintFungible`.
+///
+///### Accounts:
+///
+///   0. `[writable]` Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])
+///   1. `[]` Metadata account
+///   2. `[writable]` Mint of the fungible asset
+///   3. `[signer]` Mint authority
+///   4. `[signer]` Update authority
+///   5. `[writable, signer]` Payer
+///   6. `[]` System program
+///   7. `[]` Token program
+#[allow(clippy::too_many_arguments)]
+pub fn create_fungible_config(
+    program_id: Pubkey,
+    fungible_config: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    max_supply: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(fungible_config, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new_readonly(mint_authority, true),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::CreateFungibleConfig(CreateFungibleConfigArgs { max_supply })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_create_fungible_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateFungibleConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fungible_config_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+    assert_token_program_matches_package(token_program_info)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_signer(mint_authority_info)?;
+    assert_signer(payer_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if !matches!(
+        metadata.token_standard,
+        Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    assert_mint_authority_matches_mint(&get_mint_authority(mint_info)?, mint_authority_info)?;
+
+    if args.max_supply < get_mint_supply(mint_info)? {
+        return Err(MetadataError::MaxSupplyBelowCurrentSupply.into());
+    }
+
+    if !fungible_config_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    let config_path = fungible_config_seeds(program_id, mint_info.key);
+    let bump = assert_derivation(program_id, fungible_config_info, &config_path)?;
+    let bump_seed = &[bump];
+    let mut config_seeds = config_path.clone();
+    config_seeds.push(bump_seed);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        fungible_config_info,
+        system_program_info,
+        payer_info,
+        FUNGIBLE_CONFIG_SIZE,
+        &config_seeds,
+    )?;
+
+    let fungible_config = FungibleConfig {
+        key: Key::FungibleConfig,
+        mint: *mint_info.key,
+        bump,
+        mint_authority: *mint_authority_info.key,
+        max_supply: args.max_supply,
+        decimals: get_mint_decimals(mint_info)?,
+    };
+    BorshSerialize::serialize(
+        &fungible_config,
+        &mut *fungible_config_info.try_borrow_mut_data()?,
+    )?;
+
+    // From here on the cap can only be enforced if nobody else can mint.
+    invoke(
+        &set_authority(
+            token_program_info.key,
+            mint_info.key,
+            Some(fungible_config_info.key),
+            AuthorityType::MintTokens,
+            mint_authority_info.key,
+            &[],
+        )?,
+        &[
+            mint_info.clone(),
+            mint_authority_info.clone(),
+            token_program_info.clone(),
+        ],
+    )
+}
@@ -0,0 +1,112 @@
+use crate::{
+    error::MetadataError,
+    fungible::fungible_config_seeds,
+    instruction::MetadataInstruction,
+    state::{FungibleConfig, TokenMetadataAccount},
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_token_program_matches_package,
+        get_mint_supply, spl_token_mint_to, TokenMintToParams,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MintFungibleArgs {
+    /// Amount to mint in base units of the mint
+    pub amount: u64,
+}
+
+///# Mint Fungible
+///
+/// Mints tokens of a capped fungible asset, failing when the supply would exceed the max supply
+/// of its fungible config.
+///
+///### Accounts:
+///
+///   0. `[]` Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])
+///   1. `[writable]` Mint of the fungible asset
+///   2. `[writable]` Destination token account
+///   3. `[signer]` Mint authority of the fungible config
+///   4. `[]` Token program
+pub fn mint_fungible(
+    program_id: Pubkey,
+    fungible_config: Pubkey,
+    mint: Pubkey,
+    destination: Pubkey,
+    mint_authority: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(fungible_config, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new_readonly(mint_authority, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MintFungible(MintFungibleArgs { amount })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_mint_fungible<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MintFungibleArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fungible_config_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_token_program_matches_package(token_program_info)?;
+    assert_signer(mint_authority_info)?;
+    assert_owned_by(fungible_config_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+
+    let config_path = fungible_config_seeds(program_id, mint_info.key);
+    let bump = assert_derivation(program_id, fungible_config_info, &config_path)?;
+    let fungible_config = FungibleConfig::from_account_info(fungible_config_info)?;
+
+    if fungible_config.mint_authority != *mint_authority_info.key {
+        return Err(MetadataError::InvalidMintAuthority.into());
+    }
+
+    let new_supply = get_mint_supply(mint_info)?
+        .checked_add(args.amount)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    if new_supply > fungible_config.max_supply {
+        return Err(MetadataError::SupplyCapExceeded.into());
+    }
+
+    let bump_seed = &[bump];
+    let mut config_seeds = config_path.clone();
+    config_seeds.push(bump_seed);
+
+    spl_token_mint_to(TokenMintToParams {
+        mint: mint_info.clone(),
+        destination: destination_info.clone(),
+        amount: args.amount,
+        authority: fungible_config_info.clone(),
+        authority_signer_seeds: Some(&config_seeds),
+        token_program: token_program_info.clone(),
+    })
+}
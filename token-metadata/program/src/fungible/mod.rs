@@ -0,0 +1,19 @@
+pub mod create_fungible_config;
+pub mod mint_fungible;
+pub mod update_fungible_config;
+
+pub use create_fungible_config::*;
+pub use mint_fungible::*;
+pub use update_fungible_config::*;
+
+use crate::state::{FUNGIBLE_CONFIG, PREFIX};
+use solana_program::pubkey::Pubkey;
+
+pub fn fungible_config_seeds<'a>(program_id: &'a Pubkey, mint: &'a Pubkey) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        FUNGIBLE_CONFIG.as_bytes(),
+    ]
+}
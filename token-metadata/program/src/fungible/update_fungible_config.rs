@@ -0,0 +1,117 @@
+use crate::{
+    error::MetadataError,
+    fungible::fungible_config_seeds,
+    instruction::MetadataInstruction,
+    state::{FungibleConfig, Metadata, TokenMetadataAccount},
+    utils::{
+        assert_derivation, assert_owned_by, assert_update_authority_is_correct, get_mint_supply,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct UpdateFungibleConfigArgs {
+    /// Authority allowed to mint through `MintFungible` from now on
+    pub new_mint_authority: Option<Pubkey>,
+    /// Lower max supply in base units of the mint
+    pub max_supply: Option<u64>,
+}
+
+///# Update Fungible Config
+///
+/// Rotates the mint authority of a fungible config and optionally lowers its max supply, which
+/// can never be raised nor set below the current supply of the mint.
+///
+///### Accounts:
+///
+///   0. `[writable]` Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])
+///   1. `[]` Metadata account
+///   2. `[]` Mint of the fungible asset
+///   3. `[signer]` Update authority
+pub fn update_fungible_config(
+    program_id: Pubkey,
+    fungible_config: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    new_mint_authority: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(fungible_config, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::UpdateFungibleConfig(UpdateFungibleConfigArgs {
+            new_mint_authority,
+            max_supply,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_update_fungible_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UpdateFungibleConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fungible_config_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fungible_config_info, program_id)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let config_path = fungible_config_seeds(program_id, mint_info.key);
+    assert_derivation(program_id, fungible_config_info, &config_path)?;
+    let mut fungible_config = FungibleConfig::from_account_info(fungible_config_info)?;
+
+    if let Some(new_mint_authority) = args.new_mint_authority {
+        fungible_config.mint_authority = new_mint_authority;
+    }
+
+    if let Some(max_supply) = args.max_supply {
+        if max_supply > fungible_config.max_supply {
+            return Err(MetadataError::MaxSupplyCannotBeRaised.into());
+        }
+        if max_supply < get_mint_supply(mint_info)? {
+            return Err(MetadataError::MaxSupplyBelowCurrentSupply.into());
+        }
+        fungible_config.max_supply = max_supply;
+    }
+
+    BorshSerialize::serialize(
+        &fungible_config,
+        &mut *fungible_config_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -1,10 +1,23 @@
 use crate::{
+    attributes::{RemoveAttributeArgs, SetAttributeArgs},
+    burn::BurnArgs,
+    collection::{
+        SetCollectionCreatorsArgs, SetCollectionRoyaltiesArgs, SetCollectionValidationProfileArgs,
+    },
+    creators::{ApproveCreatorsProposalArgs, ProposeCreatorsArgs},
     deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs},
-    escrow::TransferOutOfEscrowArgs,
+    edition::{SetPrintConfigArgs, SetPrintTemplateArgs},
+    escrow::{TransferIntoEscrowArgs, TransferOutOfEscrowArgs},
+    fungible::{CreateFungibleConfigArgs, MintFungibleArgs, UpdateFungibleConfigArgs},
+    pda::{find_collection_validation_profile_account, find_rule_set_account},
+    programmable::TransferArgs,
+    rule_set::SetRuleSetArgs,
     state::{
         Collection, CollectionDetails, Creator, Data, DataV2, Uses, EDITION,
         EDITION_MARKER_BIT_SIZE, PREFIX,
     },
+    update::{MetadataDelegateArgs, ResizeArgs, UpdateArgs},
+    uses::SetUseScheduleArgs,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
@@ -105,6 +118,27 @@ pub struct ApproveUseAuthorityArgs {
     pub number_of_uses: u64,
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ApproveUseAuthorityV2Args {
+    pub number_of_uses: u64,
+    /// Unix timestamp after which the use authority can't use the asset
+    pub expiry: Option<i64>,
+    /// Program the use authority must be invoked through
+    pub caller_program: Option<Pubkey>,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ApproveCollectionAuthorityV2Args {
+    /// Bitmask of the `COLLECTION_PERMISSION_*` operations the authority may perform
+    pub permissions: u8,
+    /// Unix timestamp after which the authority can't act on the collection
+    pub expiry: Option<i64>,
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -274,6 +308,10 @@ pub enum MetadataInstruction {
     #[account(11, name="token_program", desc="Token program")]
     #[account(12, name="system_program", desc="System program")]
     #[account(13, optional, name="rent", desc="Rent info")]
+    #[account(14, optional, name="print_config", desc="Print config account, required when the master edition has one")]
+    #[account(15, optional, writable, name="print_record", desc="Print record of the payer (pda of ['metadata', program id, master metadata mint id, 'print_config', payer])")]
+    #[account(16, optional, writable, name="payment_destination", desc="Payment destination, when the print config has a price")]
+    #[account(17, optional, writable, name="payer_token_account", desc="Payer token account, when the price is paid in an SPL token")]
     MintNewEditionFromMasterEditionViaToken(MintNewEditionFromMasterEditionViaTokenArgs),
 
     /// Converts the Master Edition V1 to a Master Edition V2, draining lamports from the two printing mints
@@ -312,6 +350,9 @@ pub enum MetadataInstruction {
     /// Update a Metadata with is_mutable as a parameter
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, name="update_authority", desc="Update authority key")]
+    #[account(2, optional, signer, writable, name="payer", desc="Payer funding the growth of a resized metadata account")]
+    #[account(3, optional, name="system_program", desc="System program, required with the payer")]
+    #[account(4, optional, name="validation_profile", desc="Collection validation profile PDA, found by its address; required to change the name, symbol or uri of a verified collection item")]
     UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2),
 
     /// Create Metadata object.
@@ -362,6 +403,8 @@ pub enum MetadataInstruction {
     #[account(8, name="rent", desc="Rent info")]
     #[account(9, optional, writable, name="use_authority_record", desc="Use Authority Record PDA If present the program Assumes a delegated use authority")]
     #[account(10, optional, name="burner", desc="Program As Signer (Burner)")]
+    #[account(11, optional, name="use_schedule", desc="Use schedule PDA, required last when the use method is Expiring")]
+    #[account(12, optional, name="instructions", desc="Instructions sysvar, required after every other account when the use authority has a caller program")]
     Utilize(UtilizeArgs),
 
     /// Approve another account to call [utilize] on this NFT.
@@ -376,6 +419,7 @@ pub enum MetadataInstruction {
     #[account(8, name="token_program", desc="Token program")]
     #[account(9, name="system_program", desc="System program")]
     #[account(10, optional, name="rent", desc="Rent info")]
+    #[account(11, optional, name="rule_set", desc="Rule set PDA, required if the metadata has a rule set")]
     ApproveUseAuthority(ApproveUseAuthorityArgs),
 
     /// Revoke account to call [utilize] on this NFT.
@@ -408,6 +452,7 @@ pub enum MetadataInstruction {
     #[account(5, name="mint", desc="Mint of Collection Metadata")]
     #[account(6, name="system_program", desc="System program")]
     #[account(7, optional, name="rent", desc="Rent info")]
+    #[account(8, optional, name="rule_set", desc="Rule set PDA, required if the metadata has a rule set")]
     ApproveCollectionAuthority,
 
     /// Revoke account to call [verify_collection] on this NFT.
@@ -436,6 +481,7 @@ pub enum MetadataInstruction {
     #[account(2, name="edition", desc="Edition")]
     #[account(3, name="mint", desc="Token mint")]
     #[account(4, name="token_program", desc="Token Program")]
+    #[account(5, optional, name="rule_set", desc="Rule set PDA of the mint, may be uninitialized")]
     FreezeDelegatedAccount,
 
     /// Allow thawing of an NFT if this user is the delegate of the NFT.
@@ -470,6 +516,7 @@ pub enum MetadataInstruction {
     #[account(4, writable, name="collection", desc="Metadata Account of the Collection")]
     #[account(5, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
     #[account(6, optional, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(7, name="validation_profile", desc="Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile']), may be uninitialized")]
     VerifySizedCollectionItem,
 
     /// Unverify Collection V2, new in v1.3--supports Collection Details.
@@ -494,6 +541,7 @@ pub enum MetadataInstruction {
     #[account(5, writable, name="collection", desc="Metadata Account of the Collection")]
     #[account(6, writable, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
     #[account(7, optional, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(8, name="validation_profile", desc="Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile']), may be uninitialized")]
     SetAndVerifySizedCollectionItem,
 
     /// Create Metadata object.
@@ -504,6 +552,7 @@ pub enum MetadataInstruction {
     #[account(4, name="update_authority", desc="update authority info")]
     #[account(5, name="system_program", desc="System program")]
     #[account(6, optional, name="rent", desc="Rent info")]
+    #[account(7, optional, name="validation_profile", desc="Validation profile PDA of the collection of the item, may be uninitialized; checked when passed, otherwise when the item is verified")]
     CreateMetadataAccountV3(CreateMetadataAccountArgsV3),
 
     /// Set size of an existing collection.
@@ -566,7 +615,7 @@ pub enum MetadataInstruction {
     CloseEscrowAccount,
 
     /// Transfer the token out of Escrow.
-    #[account(0, name="escrow", desc="Escrow account")]
+    #[account(0, writable, name="escrow", desc="Escrow account")]
     #[account(1, writable, name="metadata", desc="Metadata account")]
     #[account(2, writable, signer, name="payer", desc="Wallet paying for the transaction and new account")]
     #[account(3, name="attribute_mint", desc="Mint account for the new attribute")]
@@ -579,7 +628,336 @@ pub enum MetadataInstruction {
     #[account(10, name="token_program", desc="Token program")]
     #[account(11, name="sysvar_instructions", desc="Instructions sysvar account")]
     #[account(12, optional, signer, name="authority", desc="Authority/creator of the escrow account")]
+    #[account(13, optional, writable, name="nested_escrow", desc="Escrow owned by the transferred token, when it is nested")]
+    #[account(14, optional, writable, name="ancestor_escrows", desc="Escrows holding the escrow, from the direct parent up to the root")]
     TransferOutOfEscrow(TransferOutOfEscrowArgs),
+
+    /// Convert a NonFungible master edition into a ProgrammableNonFungible, freezing the holder's token account with the edition PDA.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="update_authority", desc="Update authority")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, name="edition", desc="Master edition account")]
+    #[account(4, writable, name="token", desc="Token account holding the NFT")]
+    #[account(5, signer, name="token_owner", desc="Owner of the token account")]
+    #[account(6, name="token_program", desc="Token program")]
+    MigrateToProgrammable,
+
+    /// Transfer a ProgrammableNonFungible token, keeping it frozen and paying creator royalties on the sale price.
+    #[account(0, writable, name="source", desc="Source token account")]
+    #[account(1, signer, name="authority", desc="Owner or delegate of the source token account")]
+    #[account(2, writable, name="destination", desc="Destination token account")]
+    #[account(3, name="destination_owner", desc="Owner of the destination token account")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, name="mint", desc="Mint account")]
+    #[account(6, name="edition", desc="Master edition account")]
+    #[account(7, writable, signer, name="payer", desc="Payer of royalties and of the destination account rent")]
+    #[account(8, name="token_program", desc="Token program")]
+    #[account(9, name="ata_program", desc="Associated Token program")]
+    #[account(10, name="system_program", desc="System program")]
+    Transfer(TransferArgs),
+
+    /// Create or replace the rule set restricting which programs and wallets may act as delegates of an asset.
+    #[account(0, writable, name="rule_set", desc="Rule set PDA")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="update_authority", desc="Update authority")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetRuleSet(SetRuleSetArgs),
+
+    /// Detach and close the rule set of an asset.
+    #[account(0, writable, name="rule_set", desc="Rule set PDA")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="update_authority", desc="Update authority")]
+    #[account(4, writable, name="receiver", desc="Account receiving the rule set rent")]
+    RemoveRuleSet,
+
+    /// Update individual metadata fields, as the update authority or as a metadata delegate.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="authority", desc="Update authority or metadata delegate")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, name="edition", desc="Edition PDA of the mint, may be uninitialized")]
+    #[account(4, optional, name="delegate_record", desc="Metadata delegate record, when the authority is a delegate")]
+    #[account(5, optional, signer, writable, name="payer", desc="Payer funding the growth of a resized metadata account")]
+    #[account(6, optional, name="system_program", desc="System program, required with the payer")]
+    #[account(7, optional, name="validation_profile", desc="Collection validation profile PDA, found by its address; required to change the name or uri of a verified collection item")]
+    Update(UpdateArgs),
+
+    /// Allow another account to update the metadata fields covered by a role.
+    #[account(0, writable, name="delegate_record", desc="Metadata delegate record PDA")]
+    #[account(1, name="delegate", desc="Delegate")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, signer, name="update_authority", desc="Update authority")]
+    #[account(5, writable, signer, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    ApproveMetadataDelegate(MetadataDelegateArgs),
+
+    /// Close a metadata delegate record.
+    #[account(0, writable, name="delegate_record", desc="Metadata delegate record PDA")]
+    #[account(1, name="delegate", desc="Delegate")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, signer, name="authority", desc="Update authority or delegate")]
+    #[account(5, writable, name="payer", desc="Payer recorded at approval, receives the record rent")]
+    RevokeMetadataDelegate(MetadataDelegateArgs),
+
+    /// Reallocate a metadata account to fit its content or to a given size, refunding excess rent.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="update_authority", desc="Update authority")]
+    #[account(2, writable, signer, name="payer", desc="Payer, funds growth and receives refunded rent")]
+    #[account(3, name="system_program", desc="System program")]
+    Resize(ResizeArgs),
+
+    /// Set an on-chain attribute of an asset, creating its attributes account on first use.
+    #[account(0, writable, name="attributes", desc="Attributes account (pda of ['metadata', program id, mint id, 'attributes'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority or attributes delegate")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, optional, name="delegate_record", desc="Metadata delegate record, when the authority is a delegate")]
+    SetAttribute(SetAttributeArgs),
+
+    /// Remove an on-chain attribute of an asset, closing the attributes account once it is empty.
+    #[account(0, writable, name="attributes", desc="Attributes account (pda of ['metadata', program id, mint id, 'attributes'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority or attributes delegate")]
+    #[account(4, writable, name="receiver", desc="Update authority of the metadata, receives the freed rent")]
+    #[account(5, optional, name="delegate_record", desc="Metadata delegate record, when the authority is a delegate")]
+    RemoveAttribute(RemoveAttributeArgs),
+
+    /// Verify many items of a sized collection at once, increasing the collection size once.
+    #[account(0, signer, name="collection_authority", desc="Collection update authority or delegated collection authority")]
+    #[account(1, name="collection_mint", desc="Mint of the Collection")]
+    #[account(2, writable, name="collection", desc="Metadata Account of the Collection")]
+    #[account(3, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
+    #[account(4, name="collection_authority_record", desc="Collection Authority Record PDA, may be uninitialized")]
+    #[account(5, name="validation_profile", desc="Collection validation profile PDA, may be uninitialized")]
+    BatchVerifySizedCollectionItems,
+
+    /// Unverify many items of a sized collection at once, decreasing the collection size once.
+    #[account(0, signer, name="collection_authority", desc="Collection update authority or delegated collection authority")]
+    #[account(1, name="collection_mint", desc="Mint of the Collection")]
+    #[account(2, writable, name="collection", desc="Metadata Account of the Collection")]
+    #[account(3, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
+    #[account(4, name="collection_authority_record", desc="Collection Authority Record PDA, may be uninitialized")]
+    BatchUnverifySizedCollectionItems,
+
+    /// Set the default royalties of a sized collection, inherited by verified items without creators.
+    #[account(0, writable, name="collection_royalties", desc="Collection royalties account (pda of ['metadata', program id, collection mint id, 'collection_royalties'])")]
+    #[account(1, name="collection", desc="Metadata Account of the Collection")]
+    #[account(2, name="collection_mint", desc="Mint of the Collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the Collection")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetCollectionRoyalties(SetCollectionRoyaltiesArgs),
+
+    /// Remove the default royalties of a collection, closing the collection royalties account.
+    #[account(0, writable, name="collection_royalties", desc="Collection royalties account (pda of ['metadata', program id, collection mint id, 'collection_royalties'])")]
+    #[account(1, name="collection", desc="Metadata Account of the Collection")]
+    #[account(2, name="collection_mint", desc="Mint of the Collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the Collection")]
+    #[account(4, writable, name="receiver", desc="Account receiving the collection royalties rent")]
+    RemoveCollectionRoyalties,
+
+    /// Set or clear the print template numbering the name and uri of prints of a master edition.
+    #[account(0, writable, name="edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    SetPrintTemplate(SetPrintTemplateArgs),
+
+    /// Create or replace the print config enforcing a time window, per wallet limit and price on prints.
+    #[account(0, writable, name="print_config", desc="Print config account (pda of ['metadata', program id, mint id, 'print_config'])")]
+    #[account(1, writable, name="edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="update_authority", desc="Update authority")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetPrintConfig(SetPrintConfigArgs),
+
+    /// Detach the print config from a master edition and close the print config account.
+    #[account(0, writable, name="print_config", desc="Print config account (pda of ['metadata', program id, mint id, 'print_config'])")]
+    #[account(1, writable, name="edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="update_authority", desc="Update authority")]
+    #[account(4, writable, name="receiver", desc="Account receiving the print config rent")]
+    RemovePrintConfig,
+
+    /// Burn an asset of any token standard, closing its accounts and any delegate records passed in.
+    #[account(0, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    #[account(1, signer, writable, name="owner", desc="Asset owner")]
+    #[account(2, writable, name="mint", desc="Mint of the asset")]
+    #[account(3, writable, name="token_account", desc="Token account of the asset")]
+    #[account(4, writable, name="edition", desc="Edition of the asset, may be uninitialized for fungible assets")]
+    #[account(5, name="spl_token_program", desc="SPL Token Program")]
+    #[account(6, optional, name="master_edition_mint", desc="Mint of the master edition, for print editions")]
+    #[account(7, optional, name="master_edition_token_account", desc="Token account of the master edition, for print editions")]
+    #[account(8, optional, writable, name="master_edition", desc="Master edition account, for print editions")]
+    #[account(9, optional, writable, name="edition_marker", desc="Edition marker account, for print editions")]
+    #[account(10, optional, writable, name="collection_metadata", desc="Metadata of the collection, for verified collection items")]
+    #[account(11, optional, writable, name="update_authority", desc="Update authority receiving the metadata rent, for fungible assets")]
+    Burn(BurnArgs),
+
+    /// Transfer tokens into an escrow, recording them as holdings and nesting the token's own escrow.
+    #[account(0, writable, name="escrow", desc="Escrow account")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, writable, signer, name="payer", desc="Escrow authority, owner of the source tokens and payer")]
+    #[account(3, name="attribute_mint", desc="Mint account for the new attribute")]
+    #[account(4, writable, name="attribute_src", desc="Token account source for the new attribute")]
+    #[account(5, writable, name="attribute_dst", desc="Associated token account of the escrow, destination for the new attribute")]
+    #[account(6, name="escrow_mint", desc="Mint account that the escrow is attached")]
+    #[account(7, name="escrow_account", desc="Token account that holds the token the escrow is attached to")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="ata_program", desc="Associated Token program")]
+    #[account(10, name="token_program", desc="Token program")]
+    #[account(11, writable, name="nested_escrow", desc="Token-owner escrow of the transferred mint, may be uninitialized")]
+    #[account(12, optional, writable, name="ancestor_escrows", desc="Escrows holding the escrow, from the direct parent up to the root")]
+    TransferIntoEscrow(TransferIntoEscrowArgs),
+
+    /// Create or replace the use schedule of an asset with recharging or expiring uses.
+    #[account(0, writable, name="use_schedule", desc="Use schedule account (pda of ['metadata', program id, mint id, 'use_schedule'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    #[account(3, writable, signer, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    SetUseSchedule(SetUseScheduleArgs),
+
+    /// Credit the uses of every elapsed period of an asset with recharging uses.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, writable, name="use_schedule", desc="Use schedule account (pda of ['metadata', program id, mint id, 'use_schedule'])")]
+    #[account(2, name="mint", desc="Mint of the metadata")]
+    #[account(3, signer, name="authority", desc="Update authority or use authority")]
+    #[account(4, optional, name="use_authority_record", desc="Use authority record PDA, required when a use authority recharges")]
+    #[account(5, optional, name="instructions", desc="Instructions sysvar, required when the use authority has a caller program")]
+    Recharge,
+
+    /// Approve another account to call [utilize] on this NFT until an expiry and/or only through a caller program.
+    #[account(0, writable, name="use_authority_record", desc="Use Authority Record PDA")]
+    #[account(1, signer, writable, name="owner", desc="Owner")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="user", desc="A Use Authority")]
+    #[account(4, writable, name="owner_token_account", desc="Owned Token Account Of Mint")]
+    #[account(5, name="metadata", desc="Metadata account")]
+    #[account(6, name="mint", desc="Mint of Metadata")]
+    #[account(7, name="burner", desc="Program As Signer (Burner)")]
+    #[account(8, name="token_program", desc="Token program")]
+    #[account(9, name="system_program", desc="System program")]
+    #[account(10, optional, name="rent", desc="Rent info")]
+    #[account(11, optional, name="rule_set", desc="Rule set PDA, required if the metadata has a rule set")]
+    ApproveUseAuthorityV2(ApproveUseAuthorityV2Args),
+
+    /// Approve another account to act on NFTs belonging to a collection, limited to a set of permissions and optionally until an expiry.
+    #[account(0, writable, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(1, name="new_collection_authority", desc="A Collection Authority")]
+    #[account(2, signer, writable, name="update_authority", desc="Update Authority of Collection NFT")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="metadata", desc="Collection Metadata account")]
+    #[account(5, name="mint", desc="Mint of Collection Metadata")]
+    #[account(6, name="system_program", desc="System program")]
+    #[account(7, optional, name="rent", desc="Rent info")]
+    #[account(8, optional, name="rule_set", desc="Rule set PDA, required if the metadata has a rule set")]
+    ApproveCollectionAuthorityV2(ApproveCollectionAuthorityV2Args),
+
+    /// Set the verified creators of a collection allowed to verify the items they are verified creators of.
+    #[account(0, writable, name="collection_creators", desc="Collection creators account (pda of ['metadata', program id, collection mint id, 'collection_creators'])")]
+    #[account(1, name="collection", desc="Metadata account of the collection")]
+    #[account(2, name="collection_mint", desc="Mint of the collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the collection")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetCollectionCreators(SetCollectionCreatorsArgs),
+
+    /// Close the collection creators account of a collection.
+    #[account(0, writable, name="collection_creators", desc="Collection creators account (pda of ['metadata', program id, collection mint id, 'collection_creators'])")]
+    #[account(1, name="collection", desc="Metadata account of the collection")]
+    #[account(2, name="collection_mint", desc="Mint of the collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the collection")]
+    #[account(4, writable, name="receiver", desc="Account receiving the collection creators rent")]
+    RemoveCollectionCreators,
+
+    /// Verify an item of a collection as a collection creator who is a verified creator of the item.
+    #[account(0, writable, name="metadata", desc="Metadata account of the item")]
+    #[account(1, signer, name="creator", desc="Collection creator")]
+    #[account(2, name="collection_mint", desc="Mint of the collection")]
+    #[account(3, writable, name="collection", desc="Metadata account of the collection")]
+    #[account(4, name="collection_master_edition_account", desc="MasterEdition2 account of the collection")]
+    #[account(5, name="collection_creators", desc="Collection creators account (pda of ['metadata', program id, collection mint id, 'collection_creators'])")]
+    #[account(6, name="validation_profile", desc="Collection validation profile PDA, may be uninitialized")]
+    VerifyCollectionItemAsCreator,
+
+    /// Propose new creators for a metadata account, replacing any previous proposal.
+    #[account(0, writable, name="creators_proposal", desc="Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    #[account(3, writable, signer, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    ProposeCreators(ProposeCreatorsArgs),
+
+    /// Approve the proposed creators as a verified creator of the current or proposed creators.
+    #[account(0, writable, name="creators_proposal", desc="Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="creator", desc="Creator")]
+    ApproveCreatorsProposal(ApproveCreatorsProposalArgs),
+
+    /// Apply a creators proposal approved by every verified creator of the current and proposed creators.
+    #[account(0, writable, name="creators_proposal", desc="Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    #[account(3, writable, name="receiver", desc="Account receiving the creators proposal rent")]
+    ApplyCreatorsProposal,
+
+    /// Close a creators proposal without applying it.
+    #[account(0, writable, name="creators_proposal", desc="Creators proposal account (pda of ['metadata', program id, mint id, 'creators_proposal'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="update_authority", desc="Update authority")]
+    #[account(3, writable, name="receiver", desc="Account receiving the creators proposal rent")]
+    CancelCreatorsProposal,
+
+    /// Cap the supply of a fungible asset, handing its mint authority off to the fungible config.
+    #[account(0, writable, name="fungible_config", desc="Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, writable, name="mint", desc="Mint of the fungible asset")]
+    #[account(3, signer, name="mint_authority", desc="Mint authority")]
+    #[account(4, signer, name="update_authority", desc="Update authority")]
+    #[account(5, writable, signer, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    #[account(7, name="token_program", desc="Token program")]
+    CreateFungibleConfig(CreateFungibleConfigArgs),
+
+    /// Mint tokens of a capped fungible asset within its max supply.
+    #[account(0, name="fungible_config", desc="Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])")]
+    #[account(1, writable, name="mint", desc="Mint of the fungible asset")]
+    #[account(2, writable, name="destination", desc="Destination token account")]
+    #[account(3, signer, name="mint_authority", desc="Mint authority of the fungible config")]
+    #[account(4, name="token_program", desc="Token program")]
+    MintFungible(MintFungibleArgs),
+
+    /// Set the rules a sized collection requires of the name, symbol and uri of its items.
+    #[account(0, writable, name="validation_profile", desc="Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile'])")]
+    #[account(1, name="collection", desc="Metadata Account of the Collection")]
+    #[account(2, name="collection_mint", desc="Mint of the Collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the Collection")]
+    #[account(4, writable, signer, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetCollectionValidationProfile(SetCollectionValidationProfileArgs),
+
+    /// Remove the validation profile of a collection, closing its account.
+    #[account(0, writable, name="validation_profile", desc="Collection validation profile account (pda of ['metadata', program id, collection mint id, 'validation_profile'])")]
+    #[account(1, name="collection", desc="Metadata Account of the Collection")]
+    #[account(2, name="collection_mint", desc="Mint of the Collection")]
+    #[account(3, signer, name="update_authority", desc="Update authority of the Collection")]
+    #[account(4, writable, name="receiver", desc="Account receiving the validation profile rent")]
+    RemoveCollectionValidationProfile,
+
+    /// Rotate the mint authority of a fungible config and optionally lower its max supply.
+    #[account(0, writable, name="fungible_config", desc="Fungible config account (pda of ['metadata', program id, mint id, 'fungible_config'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint of the fungible asset")]
+    #[account(3, signer, name="update_authority", desc="Update authority")]
+    UpdateFungibleConfig(UpdateFungibleConfigArgs),
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -1166,6 +1544,7 @@ pub fn utilize(
 ///   8. `[]` Token program
 ///   9. `[]` System program
 ///   10. Optional `[]` Rent info
+///   11. Optional `[]` Rule set PDA, required if the metadata has a rule set
 #[allow(clippy::too_many_arguments)]
 pub fn approve_use_authority(
     program_id: Pubkey,
@@ -1192,6 +1571,7 @@ pub fn approve_use_authority(
             AccountMeta::new_readonly(burner, false),
             AccountMeta::new_readonly(spl_token::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(find_rule_set_account(&mint).0, false),
         ],
         data: MetadataInstruction::ApproveUseAuthority(ApproveUseAuthorityArgs { number_of_uses })
             .try_to_vec()
@@ -1199,6 +1579,70 @@ pub fn approve_use_authority(
     }
 }
 
+///# Approve Use Authority V2
+///
+///Approve another account to call [utilize] on this NFT, optionally until an expiry and only
+///through a caller program. A use authority with a caller program must pass the instructions
+///sysvar last to [utilize].
+///
+///### Args:
+///
+///See: [ApproveUseAuthorityV2Args]
+///
+///### Accounts:
+///
+///   0. `[writable]` Use Authority Record PDA
+///   1. `[writable]` Owned Token Account Of Mint
+///   2. `[signer]` Owner
+///   3. `[signer]` Payer
+///   4. `[]` A Use Authority
+///   5. `[]` Metadata account
+///   6. `[]` Mint of Metadata
+///   7. `[]` Program As Signer (Burner)
+///   8. `[]` Token program
+///   9. `[]` System program
+///   10. Optional `[]` Rent info
+///   11. Optional `[]` Rule set PDA, required if the metadata has a rule set
+#[allow(clippy::too_many_arguments)]
+pub fn approve_use_authority_v2(
+    program_id: Pubkey,
+    use_authority_record: Pubkey,
+    user: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    owner_token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    burner: Pubkey,
+    number_of_uses: u64,
+    expiry: Option<i64>,
+    caller_program: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(use_authority_record, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(user, false),
+            AccountMeta::new(owner_token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(burner, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(find_rule_set_account(&mint).0, false),
+        ],
+        data: MetadataInstruction::ApproveUseAuthorityV2(ApproveUseAuthorityV2Args {
+            number_of_uses,
+            expiry,
+            caller_program,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 //# Revoke Use Authority
 ///
 ///Revoke account to call [utilize] on this NFT
@@ -1257,6 +1701,7 @@ pub fn revoke_use_authority(
 ///   6. `[]` Token program
 ///   7. `[]` System program
 ///   8. Optional `[]` Rent info
+///   9. Optional `[]` Rule set PDA, required if the metadata has a rule set
 #[allow(clippy::too_many_arguments)]
 pub fn approve_collection_authority(
     program_id: Pubkey,
@@ -1277,6 +1722,7 @@ pub fn approve_collection_authority(
             AccountMeta::new_readonly(metadata, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(find_rule_set_account(&mint).0, false),
         ],
         data: MetadataInstruction::ApproveCollectionAuthority
             .try_to_vec()
@@ -1284,6 +1730,58 @@ pub fn approve_collection_authority(
     }
 }
 
+///# Approve Collection Authority V2
+///
+///Approve another account to act on NFTs belonging to a collection, limited to the
+///`COLLECTION_PERMISSION_*` bits of `permissions` and optionally until an expiry
+///
+///### Args:
+///
+///See: [ApproveCollectionAuthorityV2Args]
+///
+///### Accounts:
+///   0. `[writable]` Collection Authority Record PDA
+///   1. `[signer]` Update Authority of Collection NFT
+///   2. `[signer]` Payer
+///   3. `[]` A Collection Authority
+///   4. `[]` Collection Metadata account
+///   5. `[]` Mint of Collection Metadata
+///   6. `[]` System program
+///   7. Optional `[]` Rent info
+///   8. Optional `[]` Rule set PDA, required if the metadata has a rule set
+#[allow(clippy::too_many_arguments)]
+pub fn approve_collection_authority_v2(
+    program_id: Pubkey,
+    collection_authority_record: Pubkey,
+    new_collection_authority: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    permissions: u8,
+    expiry: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(collection_authority_record, false),
+            AccountMeta::new_readonly(new_collection_authority, false),
+            AccountMeta::new(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(find_rule_set_account(&mint).0, false),
+        ],
+        data: MetadataInstruction::ApproveCollectionAuthorityV2(ApproveCollectionAuthorityV2Args {
+            permissions,
+            expiry,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 //# Revoke Collection Authority
 ///
 ///Revoke account to call [verify_collection] on this NFT
@@ -1382,6 +1880,8 @@ pub fn set_and_verify_collection(
 ///   1. `[writable]` Token account to freeze
 ///   2. `[]` Edition
 ///   3. `[]` Token mint
+///   4. `[]` Token program
+///   5. `[]` (Optional) Rule set PDA of the mint, may be uninitialized
 #[allow(clippy::too_many_arguments)]
 pub fn freeze_delegated_account(
     program_id: Pubkey,
@@ -1398,6 +1898,7 @@ pub fn freeze_delegated_account(
             AccountMeta::new_readonly(edition, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(find_rule_set_account(&mint).0, false),
         ],
         data: MetadataInstruction::FreezeDelegatedAccount
             .try_to_vec()
@@ -1539,6 +2040,8 @@ pub fn burn_edition_nft(
 ///   3. `[]` Mint of the Collection
 ///   4. `[writable]` Metadata Account of the Collection
 ///   5. `[]` MasterEdition2 Account of the Collection Token
+///   6. Optional `[]` Collection Authority Record PDA
+///   7. `[]` Collection validation profile PDA, may be uninitialized
 #[allow(clippy::too_many_arguments)]
 pub fn verify_sized_collection_item(
     program_id: Pubkey,
@@ -1563,6 +2066,11 @@ pub fn verify_sized_collection_item(
         accounts.push(AccountMeta::new_readonly(record, false));
     }
 
+    accounts.push(AccountMeta::new_readonly(
+        find_collection_validation_profile_account(&collection_mint).0,
+        false,
+    ));
+
     Instruction {
         program_id,
         accounts,
@@ -1636,6 +2144,8 @@ pub fn unverify_sized_collection_item(
 ///   3. `[]` Mint of the Collection
 ///   4. `[writable]` Metadata Account of the Collection
 ///   5. `[]` MasterEdition2 Account of the Collection Token
+///   6. Optional `[]` Collection Authority Record PDA
+///   7. `[]` Collection validation profile PDA, may be uninitialized
 #[allow(clippy::too_many_arguments)]
 pub fn set_and_verify_sized_collection_item(
     program_id: Pubkey,
@@ -1668,6 +2178,11 @@ pub fn set_and_verify_sized_collection_item(
         None => (),
     }
 
+    accounts.push(AccountMeta::new_readonly(
+        find_collection_validation_profile_account(&collection_mint).0,
+        false,
+    ));
+
     Instruction {
         program_id,
         accounts,
@@ -1690,6 +2205,8 @@ pub fn set_and_verify_sized_collection_item(
 ///   4. `[signer]` Update authority
 ///   5. `[]` System program
 ///   6. Optional `[]` Rent sysvar
+///   7. Optional `[]` Validation profile PDA of the collection of the item, may be uninitialized;
+///      checked when passed, otherwise when the item is verified
 ///
 /// Creates an CreateMetadataAccounts instruction
 #[allow(clippy::too_many_arguments)]
@@ -1711,16 +2228,25 @@ pub fn create_metadata_accounts_v3(
     uses: Option<Uses>,
     collection_details: Option<CollectionDetails>,
 ) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata_account, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(mint_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(update_authority, update_authority_is_signer),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    if let Some(collection) = &collection {
+        accounts.push(AccountMeta::new_readonly(
+            find_collection_validation_profile_account(&collection.key).0,
+            false,
+        ));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(metadata_account, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(mint_authority, true),
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(update_authority, update_authority_is_signer),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
+        accounts,
         data: MetadataInstruction::CreateMetadataAccountV3(CreateMetadataAccountArgsV3 {
             data: DataV2 {
                 name,
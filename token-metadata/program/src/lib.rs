@@ -1,17 +1,30 @@
 //! A Token Metadata program for the Solana blockchain.
 
 pub mod assertions;
+pub mod attributes;
+pub mod burn;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod collection;
+pub mod creators;
 pub mod deprecated_instruction;
 pub mod deprecated_processor;
 mod deser;
+pub mod edition;
 pub mod entrypoint;
 pub mod error;
 pub mod escrow;
+pub mod events;
+pub mod fungible;
 pub mod instruction;
 pub mod pda;
 pub mod processor;
+pub mod programmable;
+pub mod rule_set;
 pub mod state;
 pub mod state_test;
+pub mod update;
+pub mod uses;
 pub mod utils;
 pub mod utils_test;
 // Export current sdk types for downstream users building with a different sdk version
@@ -1,6 +1,11 @@
 use solana_program::pubkey::Pubkey;
 
-use crate::state::{BURN, COLLECTION_AUTHORITY, EDITION, PREFIX, USER};
+use crate::state::{
+    MetadataDelegateRole, ATTRIBUTES, BURN, COLLECTION_AUTHORITY, COLLECTION_CREATORS,
+    COLLECTION_ROYALTIES, COLLECTION_VALIDATION_PROFILE, CREATORS_PROPOSAL, EDITION,
+    EDITION_MARKER_BIT_SIZE, FUNGIBLE_CONFIG, METADATA_DELEGATE, PREFIX, PRINT_CONFIG, RULE_SET,
+    USER, USE_SCHEDULE,
+};
 
 pub fn find_edition_account(mint: &Pubkey, edition_number: String) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -26,6 +31,11 @@ pub fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
     )
 }
 
+/// Edition marker tracking which print editions of `mint` in the range of `edition` were minted.
+pub fn find_edition_marker_account(mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    find_edition_account(mint, (edition / EDITION_MARKER_BIT_SIZE).to_string())
+}
+
 pub fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[PREFIX.as_bytes(), crate::id().as_ref(), mint.as_ref()],
@@ -65,3 +75,142 @@ pub fn find_program_as_burner_account() -> (Pubkey, u8) {
         &crate::id(),
     )
 }
+
+pub fn find_rule_set_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            RULE_SET.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_metadata_delegate_record_account(
+    mint: &Pubkey,
+    role: MetadataDelegateRole,
+    delegate: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            METADATA_DELEGATE.as_bytes(),
+            role.seed().as_bytes(),
+            delegate.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_attributes_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            ATTRIBUTES.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_collection_royalties_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            collection_mint.as_ref(),
+            COLLECTION_ROYALTIES.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_print_config_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            PRINT_CONFIG.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_print_record_account(mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            PRINT_CONFIG.as_bytes(),
+            wallet.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_use_schedule_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            USE_SCHEDULE.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_collection_creators_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            collection_mint.as_ref(),
+            COLLECTION_CREATORS.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_creators_proposal_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            CREATORS_PROPOSAL.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_fungible_config_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            FUNGIBLE_CONFIG.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_collection_validation_profile_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            collection_mint.as_ref(),
+            COLLECTION_VALIDATION_PROFILE.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
@@ -6,25 +6,69 @@ use crate::{
             assert_collection_update_is_valid, assert_collection_verify_is_valid,
             assert_has_collection_authority, assert_is_collection_delegated_authority,
         },
-        uses::{assert_valid_use, process_use_authority_validation},
+        rule_set::{assert_rule_set_allows, assert_rule_set_pda_allows},
+        uses::{assert_use_authority_scope, assert_valid_use, process_use_authority_validation},
+        validation::{
+            assert_collection_validation_profile, assert_metadata_validation_profile,
+            assert_update_meets_validation_profile, find_validation_profile_info,
+            split_validation_profile,
+        },
+    },
+    attributes::{process_remove_attribute, process_set_attribute},
+    burn::process_burn,
+    collection::{
+        process_batch_unverify_sized_collection_items, process_batch_verify_sized_collection_items,
+        process_remove_collection_creators, process_remove_collection_royalties,
+        process_remove_collection_validation_profile, process_set_collection_creators,
+        process_set_collection_royalties, process_set_collection_validation_profile,
+        process_verify_collection_item_as_creator,
+    },
+    creators::{
+        process_apply_creators_proposal, process_approve_creators_proposal,
+        process_cancel_creators_proposal, process_propose_creators,
     },
     deprecated_processor::{
         process_deprecated_create_metadata_accounts, process_deprecated_update_metadata_accounts,
     },
-    deser::clean_write_metadata,
+    deser::{clean_write_metadata, realloc_and_write_metadata},
+    edition::{
+        apply_print_config, process_remove_print_config, process_set_print_config,
+        process_set_print_template,
+    },
     error::MetadataError,
     escrow::{
-        process_close_escrow_account, process_create_escrow_account, process_transfer_out_of_escrow,
+        process_close_escrow_account, process_create_escrow_account, process_transfer_into_escrow,
+        process_transfer_out_of_escrow,
+    },
+    events::{
+        collection_size, emit_burn, emit_collection_verification, emit_event, emit_update,
+        MetadataEventV1, MetadataValues,
+    },
+    fungible::{
+        process_create_fungible_config, process_mint_fungible, process_update_fungible_config,
+    },
+    instruction::{
+        ApproveCollectionAuthorityV2Args, ApproveUseAuthorityV2Args, MetadataInstruction,
+        SetCollectionSizeArgs,
     },
-    instruction::{MetadataInstruction, SetCollectionSizeArgs},
+    programmable::{process_migrate_to_programmable, process_transfer, thaw_with_edition},
+    rule_set::{process_remove_rule_set, process_set_rule_set},
     solana_program::program_memory::sol_memset,
     state::{
         Collection, CollectionAuthorityRecord, CollectionDetails, DataV2, Edition, EditionMarker,
         Key, MasterEditionV1, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard,
-        UseAuthorityRecord, UseMethod, Uses, BURN, COLLECTION_AUTHORITY,
-        COLLECTION_AUTHORITY_RECORD_SIZE, EDITION, EDITION_MARKER_BIT_SIZE, MAX_MASTER_EDITION_LEN,
-        MAX_METADATA_LEN, PREFIX, USER, USE_AUTHORITY_RECORD_SIZE,
+        UseAuthorityRecord, UseMethod, UseScheduleKind, Uses, BURN, COLLECTION_AUTHORITY,
+        COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE, COLLECTION_PERMISSION_ALL,
+        COLLECTION_PERMISSION_SET_AND_VERIFY, COLLECTION_PERMISSION_SET_SIZE,
+        COLLECTION_PERMISSION_UNVERIFY, COLLECTION_PERMISSION_VERIFY, EDITION,
+        EDITION_MARKER_BIT_SIZE, MAX_MASTER_EDITION_LEN, PREFIX, USER,
+        USE_AUTHORITY_RECORD_WITH_PAYER_SIZE,
     },
+    update::{
+        process_approve_metadata_delegate, process_resize, process_revoke_metadata_delegate,
+        process_update,
+    },
+    uses::{load_use_schedule, process_recharge, process_set_use_schedule},
     utils::{
         assert_currently_holding, assert_data_valid, assert_delegated_tokens, assert_derivation,
         assert_freeze_authority_matches_mint, assert_initialized,
@@ -44,9 +88,10 @@ use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_vault::{error::VaultError, state::VaultState};
 use solana_program::rent::Rent;
-use solana_program::sysvar::SysvarId;
+use solana_program::sysvar::{self, Sysvar, SysvarId};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
@@ -198,7 +243,15 @@ pub fn process_instruction<'a>(
         }
         MetadataInstruction::ApproveUseAuthority(args) => {
             msg!("Instruction: Approve Use Authority");
-            process_approve_use_authority(program_id, accounts, args.number_of_uses)
+            process_approve_use_authority(
+                program_id,
+                accounts,
+                ApproveUseAuthorityV2Args {
+                    number_of_uses: args.number_of_uses,
+                    expiry: None,
+                    caller_program: None,
+                },
+            )
         }
         MetadataInstruction::RevokeUseAuthority => {
             msg!("Instruction: Revoke Use Authority");
@@ -206,7 +259,14 @@ pub fn process_instruction<'a>(
         }
         MetadataInstruction::ApproveCollectionAuthority => {
             msg!("Instruction: Approve Collection Authority");
-            process_approve_collection_authority(program_id, accounts)
+            process_approve_collection_authority(
+                program_id,
+                accounts,
+                ApproveCollectionAuthorityV2Args {
+                    permissions: COLLECTION_PERMISSION_ALL,
+                    expiry: None,
+                },
+            )
         }
         MetadataInstruction::RevokeCollectionAuthority => {
             msg!("Instruction: Revoke Collection Authority");
@@ -264,6 +324,146 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Transfer Out Of Escrow");
             process_transfer_out_of_escrow(program_id, accounts, args)
         }
+        MetadataInstruction::MigrateToProgrammable => {
+            msg!("Instruction: Migrate To Programmable");
+            process_migrate_to_programmable(program_id, accounts)
+        }
+        MetadataInstruction::Transfer(args) => {
+            msg!("Instruction: Transfer");
+            process_transfer(program_id, accounts, args)
+        }
+        MetadataInstruction::SetRuleSet(args) => {
+            msg!("Instruction: Set Rule Set");
+            process_set_rule_set(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveRuleSet => {
+            msg!("Instruction: Remove Rule Set");
+            process_remove_rule_set(program_id, accounts)
+        }
+        MetadataInstruction::Update(args) => {
+            msg!("Instruction: Update");
+            process_update(program_id, accounts, args)
+        }
+        MetadataInstruction::ApproveMetadataDelegate(args) => {
+            msg!("Instruction: Approve Metadata Delegate");
+            process_approve_metadata_delegate(program_id, accounts, args)
+        }
+        MetadataInstruction::RevokeMetadataDelegate(args) => {
+            msg!("Instruction: Revoke Metadata Delegate");
+            process_revoke_metadata_delegate(program_id, accounts, args)
+        }
+        MetadataInstruction::Resize(args) => {
+            msg!("Instruction: Resize");
+            process_resize(program_id, accounts, args)
+        }
+        MetadataInstruction::SetAttribute(args) => {
+            msg!("Instruction: Set Attribute");
+            process_set_attribute(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveAttribute(args) => {
+            msg!("Instruction: Remove Attribute");
+            process_remove_attribute(program_id, accounts, args)
+        }
+        MetadataInstruction::BatchVerifySizedCollectionItems => {
+            msg!("Instruction: Batch Verify Sized Collection Items");
+            process_batch_verify_sized_collection_items(program_id, accounts)
+        }
+        MetadataInstruction::BatchUnverifySizedCollectionItems => {
+            msg!("Instruction: Batch Unverify Sized Collection Items");
+            process_batch_unverify_sized_collection_items(program_id, accounts)
+        }
+        MetadataInstruction::SetCollectionRoyalties(args) => {
+            msg!("Instruction: Set Collection Royalties");
+            process_set_collection_royalties(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveCollectionRoyalties => {
+            msg!("Instruction: Remove Collection Royalties");
+            process_remove_collection_royalties(program_id, accounts)
+        }
+        MetadataInstruction::SetPrintTemplate(args) => {
+            msg!("Instruction: Set Print Template");
+            process_set_print_template(program_id, accounts, args)
+        }
+        MetadataInstruction::SetPrintConfig(args) => {
+            msg!("Instruction: Set Print Config");
+            process_set_print_config(program_id, accounts, args)
+        }
+        MetadataInstruction::RemovePrintConfig => {
+            msg!("Instruction: Remove Print Config");
+            process_remove_print_config(program_id, accounts)
+        }
+        MetadataInstruction::Burn(args) => {
+            msg!("Instruction: Burn");
+            process_burn(program_id, accounts, args)
+        }
+        MetadataInstruction::TransferIntoEscrow(args) => {
+            msg!("Instruction: Transfer Into Escrow");
+            process_transfer_into_escrow(program_id, accounts, args)
+        }
+        MetadataInstruction::SetUseSchedule(args) => {
+            msg!("Instruction: Set Use Schedule");
+            process_set_use_schedule(program_id, accounts, args)
+        }
+        MetadataInstruction::Recharge => {
+            msg!("Instruction: Recharge");
+            process_recharge(program_id, accounts)
+        }
+        MetadataInstruction::ApproveUseAuthorityV2(args) => {
+            msg!("Instruction: Approve Use Authority V2");
+            process_approve_use_authority(program_id, accounts, args)
+        }
+        MetadataInstruction::ApproveCollectionAuthorityV2(args) => {
+            msg!("Instruction: Approve Collection Authority V2");
+            process_approve_collection_authority(program_id, accounts, args)
+        }
+        MetadataInstruction::SetCollectionCreators(args) => {
+            msg!("Instruction: Set Collection Creators");
+            process_set_collection_creators(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveCollectionCreators => {
+            msg!("Instruction: Remove Collection Creators");
+            process_remove_collection_creators(program_id, accounts)
+        }
+        MetadataInstruction::VerifyCollectionItemAsCreator => {
+            msg!("Instruction: Verify Collection Item As Creator");
+            process_verify_collection_item_as_creator(program_id, accounts)
+        }
+        MetadataInstruction::ProposeCreators(args) => {
+            msg!("Instruction: Propose Creators");
+            process_propose_creators(program_id, accounts, args)
+        }
+        MetadataInstruction::ApproveCreatorsProposal(args) => {
+            msg!("Instruction: Approve Creators Proposal");
+            process_approve_creators_proposal(program_id, accounts, args)
+        }
+        MetadataInstruction::ApplyCreatorsProposal => {
+            msg!("Instruction: Apply Creators Proposal");
+            process_apply_creators_proposal(program_id, accounts)
+        }
+        MetadataInstruction::CancelCreatorsProposal => {
+            msg!("Instruction: Cancel Creators Proposal");
+            process_cancel_creators_proposal(program_id, accounts)
+        }
+        MetadataInstruction::CreateFungibleConfig(args) => {
+            msg!("Instruction: Create Fungible Config");
+            process_create_fungible_config(program_id, accounts, args)
+        }
+        MetadataInstruction::MintFungible(args) => {
+            msg!("Instruction: Mint Fungible");
+            process_mint_fungible(program_id, accounts, args)
+        }
+        MetadataInstruction::SetCollectionValidationProfile(args) => {
+            msg!("Instruction: Set Collection Validation Profile");
+            process_set_collection_validation_profile(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveCollectionValidationProfile => {
+            msg!("Instruction: Remove Collection Validation Profile");
+            process_remove_collection_validation_profile(program_id, accounts)
+        }
+        MetadataInstruction::UpdateFungibleConfig(args) => {
+            msg!("Instruction: Update Fungible Config");
+            process_update_fungible_config(program_id, accounts, args)
+        }
     }
 }
 
@@ -315,6 +515,24 @@ pub fn process_create_metadata_accounts_v3<'a>(
     let update_authority_info = next_account_info(account_info_iter)?;
     let system_account_info = next_account_info(account_info_iter)?;
 
+    // The validation profile of the collection is found by its address since it may follow the
+    // optional rent sysvar. Callers from before validation profiles don't pass it, which is fine
+    // since the item is checked again when it gets verified.
+    if let Some(collection) = &data.collection {
+        if let Ok(validation_profile_info) =
+            find_validation_profile_info(program_id, &collection.key, account_info_iter.as_slice())
+        {
+            assert_collection_validation_profile(
+                program_id,
+                &collection.key,
+                validation_profile_info,
+                &data.name,
+                &data.symbol,
+                &data.uri,
+            )?;
+        }
+    }
+
     process_create_metadata_accounts_logic(
         program_id,
         CreateMetadataAccountsLogicArgs {
@@ -335,9 +553,9 @@ pub fn process_create_metadata_accounts_v3<'a>(
 }
 
 // Update existing account instruction
-pub fn process_update_metadata_accounts_v2(
+pub fn process_update_metadata_accounts_v2<'a>(
     program_id: &Pubkey,
-    accounts: &[AccountInfo],
+    accounts: &'a [AccountInfo<'a>],
     optional_data: Option<DataV2>,
     update_authority: Option<Pubkey>,
     primary_sale_happened: Option<bool>,
@@ -348,6 +566,7 @@ pub fn process_update_metadata_accounts_v2(
     let metadata_account_info = next_account_info(account_info_iter)?;
     let update_authority_info = next_account_info(account_info_iter)?;
     let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    let old_values = MetadataValues::from(&metadata);
 
     assert_owned_by(metadata_account_info, program_id)?;
     assert_update_authority_is_correct(&metadata, update_authority_info)?;
@@ -407,9 +626,25 @@ pub fn process_update_metadata_accounts_v2(
         }
     }
 
+    assert_update_meets_validation_profile(
+        program_id,
+        &old_values.data,
+        &metadata,
+        account_info_iter.as_slice(),
+    )?;
+
+    let payer_info = account_info_iter.next();
+    let system_program_info = account_info_iter.next();
+
     puff_out_data_fields(&mut metadata);
-    clean_write_metadata(&mut metadata, metadata_account_info)?;
-    Ok(())
+    realloc_and_write_metadata(
+        &mut metadata,
+        metadata_account_info,
+        payer_info,
+        system_program_info,
+    )?;
+
+    emit_update(metadata_account_info.key, old_values, &metadata)
 }
 
 pub fn process_update_primary_sale_happened_via_token(
@@ -444,10 +679,11 @@ pub fn process_update_primary_sale_happened_via_token(
         return Err(MetadataError::MintMismatch.into());
     }
 
+    let old_values = MetadataValues::from(&metadata);
     metadata.primary_sale_happened = true;
     metadata.serialize(&mut *metadata_account_info.try_borrow_mut_data()?)?;
 
-    Ok(())
+    emit_update(metadata_account_info.key, old_values, &metadata)
 }
 
 pub fn process_sign_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -460,6 +696,7 @@ pub fn process_sign_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     assert_owned_by(metadata_info, program_id)?;
 
     let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let old_values = MetadataValues::from(&metadata);
 
     if let Some(creators) = &mut metadata.data.creators {
         let mut found = false;
@@ -478,7 +715,7 @@ pub fn process_sign_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     }
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
 
-    Ok(())
+    emit_update(metadata_info.key, old_values, &metadata)
 }
 
 pub fn process_remove_creator_verification(
@@ -494,6 +731,7 @@ pub fn process_remove_creator_verification(
     assert_owned_by(metadata_info, program_id)?;
 
     let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let old_values = MetadataValues::from(&metadata);
 
     if let Some(creators) = &mut metadata.data.creators {
         let mut found = false;
@@ -512,7 +750,7 @@ pub fn process_remove_creator_verification(
     }
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
 
-    Ok(())
+    emit_update(metadata_info.key, old_values, &metadata)
 }
 
 /// Create master edition
@@ -629,6 +867,16 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     let token_program_account_info = next_account_info(account_info_iter)?;
     let system_account_info = next_account_info(account_info_iter)?;
 
+    apply_print_config(
+        program_id,
+        master_edition_account_info,
+        master_metadata_account_info,
+        payer_account_info,
+        token_program_account_info,
+        system_account_info,
+        account_info_iter,
+    )?;
+
     process_mint_new_edition_from_master_edition_via_token_logic(
         program_id,
         MintNewEditionFromMasterEditionViaTokenLogicArgs {
@@ -683,12 +931,18 @@ pub fn process_convert_master_edition_v1_to_v2(
         return Err(MetadataError::OneTimeAuthMintSupplyMustBeZeroForConversion.into());
     }
 
+    // Clear the V1 printing mints so they are not read back as a print template.
+    let mut master_edition_data = master_edition_info.try_borrow_mut_data()?;
+    sol_memset(&mut master_edition_data, 0, master_edition_info.data_len());
+
     MasterEditionV2 {
         key: Key::MasterEditionV2,
         supply: master_edition.supply,
         max_supply: master_edition.max_supply,
+        print_template: None,
+        print_config: None,
     }
-    .serialize(&mut *master_edition_info.try_borrow_mut_data()?)?;
+    .serialize(&mut *master_edition_data)?;
 
     Ok(())
 }
@@ -787,6 +1041,16 @@ pub fn process_deprecated_mint_new_edition_from_master_edition_via_vault_proxy<'
         return Err(VaultError::StoreDoesNotMatchSafetyDepositBox.into());
     }
 
+    apply_print_config(
+        program_id,
+        master_edition_account_info,
+        master_metadata_account_info,
+        payer_info,
+        token_program_account_info,
+        system_account_info,
+        account_info_iter,
+    )?;
+
     let args = MintNewEditionFromMasterEditionViaTokenLogicArgs {
         new_metadata_account_info,
         new_edition_account_info,
@@ -830,7 +1094,7 @@ pub fn process_puff_metadata_account(
     let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
     metadata.edition_nonce = Some(edition_bump_seed);
 
-    metadata.serialize(&mut *metadata_account_info.try_borrow_mut_data()?)?;
+    clean_write_metadata(&mut metadata, metadata_account_info)?;
     Ok(())
 }
 
@@ -868,6 +1132,7 @@ pub fn verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
             &collection_metadata,
             collection_mint.key,
             Some(collection_authority_record),
+            COLLECTION_PERMISSION_VERIFY,
         )?;
     } else {
         assert_has_collection_authority(
@@ -875,6 +1140,7 @@ pub fn verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
             &collection_metadata,
             collection_mint.key,
             None,
+            COLLECTION_PERMISSION_VERIFY,
         )?;
     }
 
@@ -884,9 +1150,17 @@ pub fn verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     }
 
     // If the NFT has collection data, we set it to be verified
+    let old_collection = metadata.collection.clone();
     if let Some(collection) = &mut metadata.collection {
         collection.verified = true;
         metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+        emit_collection_verification(
+            metadata_info.key,
+            old_collection,
+            &metadata,
+            None,
+            &collection_metadata,
+        )?;
     }
     Ok(())
 }
@@ -903,7 +1177,13 @@ pub fn verify_sized_collection_item(
     let collection_info = next_account_info(account_info_iter)?;
     let edition_account_info = next_account_info(account_info_iter)?;
 
-    let using_delegated_collection_authority = accounts.len() == 7;
+    let (collection_authority_record, validation_profile_info) = split_validation_profile(
+        program_id,
+        collection_mint.key,
+        account_info_iter.as_slice(),
+    );
+    let validation_profile_info =
+        validation_profile_info.ok_or(MetadataError::MissingValidationProfile)?;
 
     assert_signer(collection_authority_info)?;
     assert_signer(payer_info)?;
@@ -930,25 +1210,25 @@ pub fn verify_sized_collection_item(
         edition_account_info,
     )?;
 
-    if using_delegated_collection_authority {
-        let collection_authority_record = next_account_info(account_info_iter)?;
-        assert_has_collection_authority(
-            collection_authority_info,
-            &collection_metadata,
-            collection_mint.key,
-            Some(collection_authority_record),
-        )?;
-    } else {
-        assert_has_collection_authority(
-            collection_authority_info,
-            &collection_metadata,
-            collection_mint.key,
-            None,
-        )?;
-    }
+    assert_has_collection_authority(
+        collection_authority_info,
+        &collection_metadata,
+        collection_mint.key,
+        collection_authority_record,
+        COLLECTION_PERMISSION_VERIFY,
+    )?;
+
+    assert_metadata_validation_profile(
+        program_id,
+        collection_mint.key,
+        validation_profile_info,
+        &metadata,
+    )?;
 
     // If the NFT has unverified collection data, we set it to be verified and then update the collection
     // size on the Collection Parent.
+    let old_collection = metadata.collection.clone();
+    let old_collection_size = collection_size(&collection_metadata);
     if let Some(collection) = &mut metadata.collection {
         msg!("Verifying sized collection item");
         increment_collection_size(&mut collection_metadata, collection_info)?;
@@ -958,7 +1238,14 @@ pub fn verify_sized_collection_item(
     } else {
         return Err(MetadataError::CollectionNotFound.into());
     }
-    Ok(())
+
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        old_collection_size,
+        &collection_metadata,
+    )
 }
 
 pub fn unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -992,6 +1279,7 @@ pub fn unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
             &collection_data,
             collection_mint.key,
             Some(collection_authority_record),
+            COLLECTION_PERMISSION_UNVERIFY,
         )?;
     } else {
         assert_has_collection_authority(
@@ -999,6 +1287,7 @@ pub fn unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
             &collection_data,
             collection_mint.key,
             None,
+            COLLECTION_PERMISSION_UNVERIFY,
         )?;
     }
 
@@ -1009,11 +1298,19 @@ pub fn unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
 
     // If the NFT has collection data, we set it to be unverified and then update the collection
     // size on the Collection Parent.
+    let old_collection = metadata.collection.clone();
     if let Some(collection) = &mut metadata.collection {
         collection.verified = false;
     }
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
-    Ok(())
+
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        None,
+        &collection_data,
+    )
 }
 
 pub fn unverify_sized_collection_item(
@@ -1061,6 +1358,7 @@ pub fn unverify_sized_collection_item(
             &collection_metadata,
             collection_mint.key,
             Some(collection_authority_record),
+            COLLECTION_PERMISSION_UNVERIFY,
         )?;
     } else {
         assert_has_collection_authority(
@@ -1068,11 +1366,14 @@ pub fn unverify_sized_collection_item(
             &collection_metadata,
             collection_mint.key,
             None,
+            COLLECTION_PERMISSION_UNVERIFY,
         )?;
     }
 
     // If the NFT has collection data, we set it to be unverified and then update the collection
     // size on the Collection Parent.
+    let old_collection = metadata.collection.clone();
+    let old_collection_size = collection_size(&collection_metadata);
     if let Some(collection) = &mut metadata.collection {
         decrement_collection_size(&mut collection_metadata, collection_info)?;
 
@@ -1081,14 +1382,26 @@ pub fn unverify_sized_collection_item(
     } else {
         return Err(MetadataError::CollectionNotFound.into());
     }
-    Ok(())
+
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        old_collection_size,
+        &collection_metadata,
+    )
 }
 
 pub fn process_approve_use_authority(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    number_of_uses: u64,
+    args: ApproveUseAuthorityV2Args,
 ) -> ProgramResult {
+    let ApproveUseAuthorityV2Args {
+        number_of_uses,
+        expiry,
+        caller_program,
+    } = args;
     let account_info_iter = &mut accounts.iter();
     let use_authority_record_info = next_account_info(account_info_iter)?;
     let owner_info = next_account_info(account_info_iter)?;
@@ -1118,6 +1431,12 @@ pub fn process_approve_use_authority(
         mint_info,
         token_account_info,
     )?;
+    assert_rule_set_allows(
+        program_id,
+        &metadata,
+        user_info,
+        account_info_iter.as_slice(),
+    )?;
     let metadata_uses = metadata.uses.unwrap();
     let bump_seed = assert_use_authority_derivation(
         program_id,
@@ -1134,12 +1453,17 @@ pub fn process_approve_use_authority(
         &[bump_seed],
     ];
     process_use_authority_validation(use_authority_record_info.data_len(), true)?;
+    if let Some(expiry) = expiry {
+        if Clock::get()?.unix_timestamp > expiry {
+            return Err(MetadataError::UseAuthorityExpired.into());
+        }
+    }
     create_or_allocate_account_raw(
         *program_id,
         use_authority_record_info,
         system_account_info,
         payer,
-        USE_AUTHORITY_RECORD_SIZE,
+        USE_AUTHORITY_RECORD_WITH_PAYER_SIZE,
         use_authority_seeds,
     )?;
     if number_of_uses > metadata_uses.remaining {
@@ -1170,6 +1494,9 @@ pub fn process_approve_use_authority(
     record.key = Key::UseAuthorityRecord;
     record.allowed_uses = number_of_uses;
     record.bump = bump_seed;
+    record.expiry = expiry;
+    record.caller_program = caller_program;
+    record.payer = Some(*payer.key);
     record.serialize(mutable_data)?;
     Ok(())
 }
@@ -1239,7 +1566,8 @@ pub fn process_revoke_use_authority(
         .lamports()
         .checked_add(lamports)
         .ok_or(MetadataError::NumericalOverflowError)?;
-    sol_memset(data, 0, USE_AUTHORITY_RECORD_SIZE);
+    let data_len = data.len();
+    sol_memset(data, 0, data_len);
     Ok(())
 }
 
@@ -1248,6 +1576,11 @@ pub fn process_utilize(
     accounts: &[AccountInfo],
     number_of_uses: u64,
 ) -> ProgramResult {
+    // The instructions sysvar is only passed for use authorities with a caller program, always last.
+    let (accounts, instructions_sysvar_info) = match accounts.split_last() {
+        Some((last, rest)) if *last.key == sysvar::instructions::id() => (rest, Some(last)),
+        _ => (accounts, None),
+    };
     let account_info_iter = &mut accounts.iter().peekable();
     let metadata_info = next_account_info(account_info_iter)?;
     let token_account_info = next_account_info(account_info_iter)?;
@@ -1258,20 +1591,34 @@ pub fn process_utilize(
     let _ata_program_account_info = next_account_info(account_info_iter)?;
     let _system_account_info = next_account_info(account_info_iter)?;
     // consume the next account only if it is Rent
-    let approved_authority_is_using = if account_info_iter
+    let rent_passed = account_info_iter
         .next_if(|info| info.key == &Rent::id())
-        .is_some()
-    {
-        // rent was passed in
-        accounts.len() == 11
-    } else {
-        // necessary accounts is one less if rent isn't passed in.
-        accounts.len() == 10
-    };
+        .is_some();
 
     let metadata: Metadata = Metadata::from_account_info(metadata_info)?;
-    if metadata.uses.is_none() {
-        return Err(MetadataError::Unusable.into());
+    let expiring = match &metadata.uses {
+        Some(uses) => uses.use_method == UseMethod::Expiring,
+        None => return Err(MetadataError::Unusable.into()),
+    };
+
+    // Expiring uses take the use schedule as the last account, it doesn't count towards the
+    // accounts of an approved authority any more than rent does.
+    let approved_authority_is_using =
+        accounts.len() - rent_passed as usize - expiring as usize == 10;
+
+    if expiring {
+        let use_schedule_info = accounts.last().ok_or(MetadataError::InvalidUseSchedule)?;
+        let use_schedule = load_use_schedule(program_id, use_schedule_info, &metadata.mint)?;
+        match use_schedule.kind {
+            UseScheduleKind::Expiring { deadline } => {
+                if Clock::get()?.unix_timestamp > deadline {
+                    return Err(MetadataError::UsesExpired.into());
+                }
+            }
+            UseScheduleKind::Recharging { .. } => {
+                return Err(MetadataError::InvalidUseSchedule.into())
+            }
+        }
     }
     if *token_program_account_info.key != spl_token::id() {
         return Err(MetadataError::InvalidTokenProgram.into());
@@ -1317,6 +1664,7 @@ pub fn process_utilize(
             record.bump = canonical_bump;
         }
         assert_valid_bump(canonical_bump, &record)?;
+        assert_use_authority_scope(&record, instructions_sysvar_info)?;
         record.allowed_uses = record
             .allowed_uses
             .checked_sub(number_of_uses)
@@ -1326,6 +1674,12 @@ pub fn process_utilize(
         return Err(MetadataError::InvalidUser.into());
     }
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    emit_event(MetadataEventV1::Utilize {
+        mint: metadata.mint,
+        user: *user_info.key,
+        old_remaining: metadata_uses.remaining,
+        new_remaining: remaining_uses,
+    })?;
     if remaining_uses == 0 && must_burn {
         if approved_authority_is_using {
             let burn_authority_info = next_account_info(account_info_iter)?;
@@ -1361,6 +1715,7 @@ pub fn process_utilize(
 pub fn process_approve_collection_authority(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    args: ApproveCollectionAuthorityV2Args,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let collection_authority_record = next_account_info(account_info_iter)?;
@@ -1386,6 +1741,20 @@ pub fn process_approve_collection_authority(
     if !collection_authority_info_empty {
         return Err(MetadataError::CollectionAuthorityRecordAlreadyExists.into());
     }
+    if args.permissions == 0 || args.permissions & !COLLECTION_PERMISSION_ALL != 0 {
+        return Err(MetadataError::InvalidCollectionPermissions.into());
+    }
+    if let Some(expiry) = args.expiry {
+        if Clock::get()?.unix_timestamp > expiry {
+            return Err(MetadataError::CollectionAuthorityExpired.into());
+        }
+    }
+    assert_rule_set_allows(
+        program_id,
+        &metadata,
+        new_collection_authority,
+        account_info_iter.as_slice(),
+    )?;
     let collection_authority_path = Vec::from([
         PREFIX.as_bytes(),
         program_id.as_ref(),
@@ -1405,7 +1774,7 @@ pub fn process_approve_collection_authority(
         collection_authority_record,
         system_account_info,
         payer,
-        COLLECTION_AUTHORITY_RECORD_SIZE,
+        COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE,
         &collection_authority_seeds,
     )?;
 
@@ -1413,6 +1782,9 @@ pub fn process_approve_collection_authority(
     record.key = Key::CollectionAuthorityRecord;
     record.bump = collection_authority_bump_seed[0];
     record.update_authority = Some(*update_authority.key);
+    record.permissions = args.permissions;
+    record.expiry = args.expiry;
+    record.payer = Some(*payer.key);
     record.serialize(&mut *collection_authority_record.try_borrow_mut_data()?)?;
     Ok(())
 }
@@ -1456,10 +1828,11 @@ pub fn process_revoke_collection_authority(
         .lamports()
         .checked_add(lamports)
         .ok_or(MetadataError::NumericalOverflowError)?;
+    let data_len = collection_authority_record.data_len();
     sol_memset(
         *collection_authority_record.try_borrow_mut_data()?,
         0,
-        USE_AUTHORITY_RECORD_SIZE,
+        data_len,
     );
 
     Ok(())
@@ -1485,6 +1858,7 @@ pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo])
 
     let mut metadata = Metadata::from_account_info(metadata_info)?;
     let collection_data = Metadata::from_account_info(collection_info)?;
+    let old_collection = metadata.collection.clone();
     if metadata.update_authority != *update_authority.key
         || metadata.update_authority != collection_data.update_authority
     {
@@ -1506,6 +1880,7 @@ pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo])
             &collection_data,
             collection_mint.key,
             Some(collection_authority_record),
+            COLLECTION_PERMISSION_SET_AND_VERIFY,
         )?;
     } else {
         assert_has_collection_authority(
@@ -1513,6 +1888,7 @@ pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo])
             &collection_data,
             collection_mint.key,
             None,
+            COLLECTION_PERMISSION_SET_AND_VERIFY,
         )?;
     }
     metadata.collection = Some(Collection {
@@ -1532,7 +1908,14 @@ pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo])
     }
 
     metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
-    Ok(())
+
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        None,
+        &collection_data,
+    )
 }
 
 pub fn set_and_verify_sized_collection_item(
@@ -1547,7 +1930,13 @@ pub fn set_and_verify_sized_collection_item(
     let collection_mint = next_account_info(account_info_iter)?;
     let collection_info = next_account_info(account_info_iter)?;
     let edition_account_info = next_account_info(account_info_iter)?;
-    let using_delegated_collection_authority = accounts.len() == 8;
+    let (collection_authority_record, validation_profile_info) = split_validation_profile(
+        program_id,
+        collection_mint.key,
+        account_info_iter.as_slice(),
+    );
+    let validation_profile_info =
+        validation_profile_info.ok_or(MetadataError::MissingValidationProfile)?;
 
     assert_signer(collection_authority_info)?;
     assert_signer(payer_info)?;
@@ -1559,9 +1948,11 @@ pub fn set_and_verify_sized_collection_item(
 
     let mut metadata = Metadata::from_account_info(metadata_info)?;
     let mut collection_metadata = Metadata::from_account_info(collection_info)?;
+    let old_collection = metadata.collection.clone();
+    let old_collection_size = collection_size(&collection_metadata);
 
     // Don't verify already verified items, otherwise we end up with invalid size data.
-    if let Some(collection) = metadata.collection {
+    if let Some(collection) = &metadata.collection {
         if collection.verified {
             return Err(MetadataError::MustUnverify.into());
         }
@@ -1573,22 +1964,19 @@ pub fn set_and_verify_sized_collection_item(
         return Err(MetadataError::UpdateAuthorityIncorrect.into());
     }
 
-    if using_delegated_collection_authority {
-        let collection_authority_record = next_account_info(account_info_iter)?;
-        assert_has_collection_authority(
-            collection_authority_info,
-            &collection_metadata,
-            collection_mint.key,
-            Some(collection_authority_record),
-        )?;
-    } else {
-        assert_has_collection_authority(
-            collection_authority_info,
-            &collection_metadata,
-            collection_mint.key,
-            None,
-        )?;
-    }
+    assert_has_collection_authority(
+        collection_authority_info,
+        &collection_metadata,
+        collection_mint.key,
+        collection_authority_record,
+        COLLECTION_PERMISSION_SET_AND_VERIFY,
+    )?;
+    assert_metadata_validation_profile(
+        program_id,
+        collection_mint.key,
+        validation_profile_info,
+        &metadata,
+    )?;
     metadata.collection = Some(Collection {
         key: *collection_mint.key,
         verified: true,
@@ -1605,7 +1993,13 @@ pub fn set_and_verify_sized_collection_item(
 
     clean_write_metadata(&mut metadata, metadata_info)?;
 
-    Ok(())
+    emit_collection_verification(
+        metadata_info.key,
+        old_collection,
+        &metadata,
+        old_collection_size,
+        &collection_metadata,
+    )
 }
 
 pub fn process_freeze_delegated_account(
@@ -1618,6 +2012,12 @@ pub fn process_freeze_delegated_account(
     let edition_info = next_account_info(account_info_iter)?;
     let mint_info = next_account_info(account_info_iter)?;
     let token_program_account_info = next_account_info(account_info_iter)?;
+    // Optional so that callers predating rule sets keep working.
+    let rule_set_info = if accounts.len() == 6 {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
 
     if *token_program_account_info.key != spl_token::id() {
         return Err(MetadataError::InvalidTokenProgram.into());
@@ -1631,6 +2031,9 @@ pub fn process_freeze_delegated_account(
     // assert delegate is signer and delegated tokens
     assert_signer(delegate_info)?;
     assert_delegated_tokens(delegate_info, mint_info, token_account_info)?;
+    if let Some(rule_set_info) = rule_set_info {
+        assert_rule_set_pda_allows(program_id, mint_info, rule_set_info, delegate_info)?;
+    }
 
     let edition_info_path = Vec::from([
         PREFIX.as_bytes(),
@@ -1798,7 +2201,18 @@ pub fn process_burn_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
         mint_info.key.as_ref(),
         EDITION.as_bytes(),
     ]);
-    assert_derivation(program_id, edition_info, &edition_info_path)?;
+    let edition_bump = assert_derivation(program_id, edition_info, &edition_info_path)?;
+
+    // Programmable NFTs stay frozen by the edition, so thaw before burning.
+    if metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible) {
+        thaw_with_edition(
+            token_info,
+            mint_info,
+            edition_info,
+            spl_token_program_info,
+            edition_bump,
+        )?;
+    }
 
     // Burn the SPL token
     let params = TokenBurnParams {
@@ -1810,6 +2224,7 @@ pub fn process_burn_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
         authority_signer_seeds: None,
     };
     spl_token_burn(params)?;
+    emit_burn(mint_info, 1)?;
 
     // Close token account.
     let params = TokenCloseParams {
@@ -1839,10 +2254,11 @@ pub fn process_burn_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
 
     let metadata_data = &mut metadata_info.try_borrow_mut_data()?;
     let edition_data = &mut edition_info.try_borrow_mut_data()?;
+    let metadata_data_len = metadata_data.len();
     let edition_data_len = edition_data.len();
 
-    // Use MAX_METADATA_LEN because it has unused padding, making it longer than current metadata len.
-    sol_memset(metadata_data, 0, MAX_METADATA_LEN);
+    // Resized metadata accounts are shorter than MAX_METADATA_LEN, so clear the actual length.
+    sol_memset(metadata_data, 0, metadata_data_len);
     sol_memset(edition_data, 0, edition_data_len);
 
     if collection_nft_provided {
@@ -2013,6 +2429,7 @@ pub fn process_burn_edition_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -
         authority_signer_seeds: None,
     };
     spl_token_burn(params)?;
+    emit_burn(print_edition_mint_info, 1)?;
 
     // Close token account.
     let params = TokenCloseParams {
@@ -2042,10 +2459,11 @@ pub fn process_burn_edition_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -
 
     let metadata_data = &mut metadata_info.try_borrow_mut_data()?;
     let edition_data = &mut print_edition_info.try_borrow_mut_data()?;
+    let metadata_data_len = metadata_data.len();
     let edition_data_len = edition_data.len();
 
-    // Use MAX_METADATA_LEN because it has unused padding, making it longer than current metadata len.
-    sol_memset(metadata_data, 0, MAX_METADATA_LEN);
+    // Resized metadata accounts are shorter than MAX_METADATA_LEN, so clear the actual length.
+    sol_memset(metadata_data, 0, metadata_data_len);
     sol_memset(edition_data, 0, edition_data_len);
 
     //       **EDITION HOUSEKEEPING**
@@ -2138,6 +2556,7 @@ pub fn set_collection_size(
             &metadata,
             collection_mint_account_info.key,
             Some(collection_authority_record),
+            COLLECTION_PERMISSION_SET_SIZE,
         )?;
     } else {
         assert_has_collection_authority(
@@ -2145,6 +2564,7 @@ pub fn set_collection_size(
             &metadata,
             collection_mint_account_info.key,
             None,
+            COLLECTION_PERMISSION_SET_SIZE,
         )?;
     }
 
@@ -2207,6 +2627,7 @@ pub fn bubblegum_set_collection_size(
         &metadata,
         collection_mint_account_info.key,
         delegated_collection_auth_opt,
+        COLLECTION_PERMISSION_SET_SIZE,
     )?;
 
     // Ensure new size is + or - 1 of the current size.
@@ -2270,6 +2691,13 @@ pub fn set_token_standard(program_id: &Pubkey, accounts: &[AccountInfo]) -> Prog
         check_token_standard(mint_account_info, None)?
     };
 
+    // A programmable NFT is a master edition too; don't let this downgrade it.
+    if metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible)
+        && token_standard == TokenStandard::NonFungible
+    {
+        return Ok(());
+    }
+
     metadata.token_standard = Some(token_standard);
     clean_write_metadata(&mut metadata, metadata_account_info)?;
     Ok(())
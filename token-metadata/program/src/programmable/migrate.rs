@@ -0,0 +1,159 @@
+use crate::{
+    deser::clean_write_metadata,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    programmable::freeze_with_edition,
+    state::{Metadata, TokenMetadataAccount, TokenStandard, EDITION, PREFIX},
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, assert_update_authority_is_correct,
+        check_token_standard,
+    },
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    program_option::COption,
+    pubkey::Pubkey,
+};
+use spl_token::{instruction::revoke, state::Account};
+
+///# Migrate To Programmable
+///
+/// Turns a NonFungible master edition into a ProgrammableNonFungible: the holder's token
+/// account is frozen by the edition PDA and can then only be moved through [transfer].
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[]` Mint account
+///   3. `[]` Master edition account
+///   4. `[writable]` Token account holding the NFT
+///   5. `[signer]` Owner of the token account
+///   6. `[]` SPL Token program
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_to_programmable(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    token: Pubkey,
+    token_owner: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(edition, false),
+        AccountMeta::new(token, false),
+        AccountMeta::new_readonly(token_owner, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MigrateToProgrammable
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_migrate_to_programmable(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_info = next_account_info(account_info_iter)?;
+    let token_owner_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_info, &spl_token::id())?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_token_program_matches_package(token_program_info)?;
+    assert_signer(token_owner_info)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let edition_bump = assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    // Only unique master editions can be made programmable.
+    if check_token_standard(mint_info, Some(edition_info))? != TokenStandard::NonFungible
+        || metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible)
+    {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    let token_account: Account = assert_initialized(token_info)?;
+
+    if token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token_account.owner != *token_owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    // A pre-existing delegate could otherwise thaw the account through
+    // `ThawDelegatedAccount` and move the token without going through `Transfer`.
+    if token_account.delegate != COption::None {
+        invoke(
+            &revoke(
+                token_program_info.key,
+                token_info.key,
+                token_owner_info.key,
+                &[],
+            )?,
+            &[
+                token_program_info.clone(),
+                token_info.clone(),
+                token_owner_info.clone(),
+            ],
+        )?;
+    }
+
+    if !token_account.is_frozen() {
+        freeze_with_edition(
+            token_info,
+            mint_info,
+            edition_info,
+            token_program_info,
+            edition_bump,
+        )?;
+    }
+
+    metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+    clean_write_metadata(&mut metadata, metadata_info)?;
+
+    Ok(())
+}
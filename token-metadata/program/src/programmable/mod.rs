@@ -0,0 +1,77 @@
+pub mod migrate;
+pub mod transfer;
+
+pub use migrate::*;
+pub use transfer::*;
+
+use crate::state::{EDITION, PREFIX};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
+};
+use spl_token::instruction::{freeze_account, thaw_account};
+
+/// Freezes `token_account_info` using the edition PDA, which must be the freeze authority
+/// of the mint.
+pub fn freeze_with_edition<'a>(
+    token_account_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    edition_info: &AccountInfo<'a>,
+    token_program_info: &AccountInfo<'a>,
+    edition_bump: u8,
+) -> ProgramResult {
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        crate::ID.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        &[edition_bump],
+    ];
+    invoke_signed(
+        &freeze_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        &[edition_seeds],
+    )
+}
+
+/// Thaws `token_account_info` using the edition PDA, which must be the freeze authority
+/// of the mint.
+pub fn thaw_with_edition<'a>(
+    token_account_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    edition_info: &AccountInfo<'a>,
+    token_program_info: &AccountInfo<'a>,
+    edition_bump: u8,
+) -> ProgramResult {
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        crate::ID.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        &[edition_bump],
+    ];
+    invoke_signed(
+        &thaw_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+        ],
+        &[edition_seeds],
+    )
+}
@@ -0,0 +1,340 @@
+use crate::{
+    assertions::rule_set::assert_royalty_free_transfer_allowed,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    programmable::{freeze_with_edition, thaw_with_edition},
+    state::{Metadata, TokenMetadataAccount, TokenStandard, EDITION, PREFIX},
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+};
+use spl_token::state::Account;
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TransferArgs {
+    /// Number of tokens to move.
+    pub amount: u64,
+    /// Price paid for the token in lamports. `seller_fee_basis_points` of it is paid by the
+    /// payer to the creators on the metadata. Transfers without a sale price, or with a zero
+    /// one, are limited to the update authority and authorities allowlisted by the rule set.
+    pub sale_price: Option<u64>,
+}
+
+///# Transfer
+///
+/// Moves a ProgrammableNonFungible token between token accounts, keeping the destination
+/// frozen by the edition PDA and paying creator royalties on the sale price.
+///
+///### Accounts:
+///
+///   0. `[writable]` Source token account
+///   1. `[signer]` Owner or delegate of the source token account
+///   2. `[writable]` Destination token account (created as an ATA if empty)
+///   3. `[]` Owner of the destination token account
+///   4. `[]` Metadata account
+///   5. `[]` Mint account
+///   6. `[]` Master edition account
+///   7. `[writable, signer]` Payer of royalties and of the destination account rent
+///   8. `[]` SPL Token program
+///   9. `[]` SPL Associated Token Account program
+///   10. `[]` System program
+///   11. `[writable]` Creator accounts in the order of `Data.creators`, when `sale_price` is set,
+///       followed by the rule set account allowlisting `authority` when no royalty is due
+#[allow(clippy::too_many_arguments)]
+pub fn transfer(
+    program_id: Pubkey,
+    source: Pubkey,
+    authority: Pubkey,
+    destination: Pubkey,
+    destination_owner: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    payer: Pubkey,
+    creators: Vec<Pubkey>,
+    amount: u64,
+    sale_price: Option<u64>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(source, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(destination, false),
+        AccountMeta::new_readonly(destination_owner, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(edition, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    for creator in creators {
+        accounts.push(AccountMeta::new(creator, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Transfer(TransferArgs { amount, sale_price })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_transfer<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: TransferArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let source_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let destination_owner_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let ata_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(source_info, &spl_token::id())?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_token_program_matches_package(token_program_info)?;
+    assert_signer(authority_info)?;
+    assert_signer(payer_info)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if metadata.token_standard != Some(TokenStandard::ProgrammableNonFungible) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    let edition_bump = assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    let source: Account = assert_initialized(source_info)?;
+
+    if source.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if source.amount < args.amount {
+        return Err(MetadataError::InsufficientTokens.into());
+    }
+
+    // Allocate the destination ATA if it doesn't exist.
+    if destination_info.data_is_empty() {
+        #[allow(deprecated)]
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                payer_info.key,
+                destination_owner_info.key,
+                mint_info.key,
+            );
+
+        invoke(
+            &create_ata_ix,
+            &[
+                payer_info.clone(),
+                destination_info.clone(),
+                destination_owner_info.clone(),
+                mint_info.clone(),
+                system_program_info.clone(),
+                token_program_info.clone(),
+                ata_program_info.clone(),
+            ],
+        )?;
+    }
+
+    assert_owned_by(destination_info, &spl_token::id())?;
+    let destination: Account = assert_initialized(destination_info)?;
+
+    if destination.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if destination.owner != *destination_owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    // A delegate on the destination could thaw it through `ThawDelegatedAccount`
+    // once it holds the token.
+    if destination.delegate != COption::None {
+        return Err(MetadataError::InvalidDelegate.into());
+    }
+
+    if source.is_frozen() {
+        thaw_with_edition(
+            source_info,
+            mint_info,
+            edition_info,
+            token_program_info,
+            edition_bump,
+        )?;
+    }
+
+    if destination.is_frozen() {
+        thaw_with_edition(
+            destination_info,
+            mint_info,
+            edition_info,
+            token_program_info,
+            edition_bump,
+        )?;
+    }
+
+    // The token program checks that the authority is the owner or the delegate.
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_info.key,
+            source_info.key,
+            destination_info.key,
+            authority_info.key,
+            &[],
+            args.amount,
+        )?,
+        &[
+            source_info.clone(),
+            destination_info.clone(),
+            authority_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    freeze_with_edition(
+        destination_info,
+        mint_info,
+        edition_info,
+        token_program_info,
+        edition_bump,
+    )?;
+
+    // Leave an emptied source account thawed so its owner can close it.
+    if source.amount > args.amount {
+        freeze_with_edition(
+            source_info,
+            mint_info,
+            edition_info,
+            token_program_info,
+            edition_bump,
+        )?;
+    }
+
+    let royalties = match args.sale_price {
+        Some(sale_price) => pay_creator_royalties(
+            account_info_iter.as_slice(),
+            &metadata,
+            payer_info,
+            system_program_info,
+            sale_price,
+        )?,
+        None => 0,
+    };
+
+    // A sale price too small to yield any royalty is no different from not naming one.
+    if royalties == 0 {
+        assert_royalty_free_transfer_allowed(
+            program_id,
+            &metadata,
+            authority_info,
+            account_info_iter.as_slice(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Pays `seller_fee_basis_points` of `sale_price` from `payer_info` to the creators of
+/// `metadata` according to their shares. `creator_infos` must list the creators in the same
+/// order as `Data.creators`. Returns the total amount paid.
+pub fn pay_creator_royalties<'a>(
+    creator_infos: &[AccountInfo<'a>],
+    metadata: &Metadata,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    sale_price: u64,
+) -> Result<u64, ProgramError> {
+    let total_fee = (metadata.data.seller_fee_basis_points as u128)
+        .checked_mul(sale_price as u128)
+        .ok_or(MetadataError::NumericalOverflowError)?
+        .checked_div(10000)
+        .ok_or(MetadataError::NumericalOverflowError)? as u64;
+
+    let creators = match &metadata.data.creators {
+        Some(creators) => creators,
+        None => return Ok(0),
+    };
+
+    if creator_infos.len() < creators.len() {
+        return Err(MetadataError::InvalidCreatorAccount.into());
+    }
+
+    let mut paid: u64 = 0;
+    for (creator, creator_info) in creators.iter().zip(creator_infos.iter()) {
+        if creator.address != *creator_info.key {
+            return Err(MetadataError::InvalidCreatorAccount.into());
+        }
+
+        let creator_fee = (creator.share as u128)
+            .checked_mul(total_fee as u128)
+            .ok_or(MetadataError::NumericalOverflowError)?
+            .checked_div(100)
+            .ok_or(MetadataError::NumericalOverflowError)? as u64;
+
+        if creator_fee > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, creator_info.key, creator_fee),
+                &[
+                    payer_info.clone(),
+                    creator_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        paid = paid
+            .checked_add(creator_fee)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+    }
+
+    Ok(paid)
+}
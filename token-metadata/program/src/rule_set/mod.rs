@@ -0,0 +1,5 @@
+pub mod remove_rule_set;
+pub mod set_rule_set;
+
+pub use remove_rule_set::*;
+pub use set_rule_set::*;
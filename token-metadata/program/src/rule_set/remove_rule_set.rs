@@ -0,0 +1,84 @@
+use crate::{
+    deser::clean_write_metadata,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, RuleSet, TokenMetadataAccount},
+    utils::{assert_owned_by, assert_update_authority_is_correct, close_account_raw},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Remove Rule Set
+///
+/// Detaches the rule set from the metadata and closes the rule set account.
+///
+///### Accounts:
+///
+///   0. `[writable]` Rule set account
+///   1. `[writable]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority
+///   4. `[writable]` Account receiving the rule set rent
+pub fn remove_rule_set(
+    program_id: Pubkey,
+    rule_set: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    receiver: Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(rule_set, false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(receiver, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::RemoveRuleSet.try_to_vec().unwrap(),
+    }
+}
+
+pub fn process_remove_rule_set(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let rule_set_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(rule_set_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if metadata.rule_set != Some(*rule_set_info.key) {
+        return Err(MetadataError::InvalidRuleSet.into());
+    }
+
+    let rule_set = RuleSet::from_account_info(rule_set_info)?;
+    if rule_set.mint != *mint_info.key {
+        return Err(MetadataError::InvalidRuleSet.into());
+    }
+
+    close_account_raw(receiver_info, rule_set_info)?;
+
+    metadata.rule_set = None;
+    clean_write_metadata(&mut metadata, metadata_info)?;
+
+    Ok(())
+}
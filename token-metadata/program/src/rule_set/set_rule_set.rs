@@ -0,0 +1,156 @@
+use crate::{
+    deser::clean_write_metadata,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Key, Metadata, RuleSet, TokenMetadataAccount, PREFIX, RULE_SET},
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_update_authority_is_correct,
+        create_or_allocate_account_raw, resize_or_reallocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetRuleSetArgs {
+    pub allow_list: Vec<Pubkey>,
+    pub deny_list: Vec<Pubkey>,
+}
+
+///# Set Rule Set
+///
+/// Creates or replaces the rule set of an asset and attaches it to the metadata.
+///
+///### Accounts:
+///
+///   0. `[writable]` Rule set account (pda of ['metadata', program id, mint id, 'rule_set'])
+///   1. `[writable]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority
+///   4. `[writable, signer]` Payer
+///   5. `[]` System program
+#[allow(clippy::too_many_arguments)]
+pub fn set_rule_set(
+    program_id: Pubkey,
+    rule_set: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    allow_list: Vec<Pubkey>,
+    deny_list: Vec<Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(rule_set, false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetRuleSet(SetRuleSetArgs {
+            allow_list,
+            deny_list,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn process_set_rule_set(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetRuleSetArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let rule_set_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_signer(payer_info)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let rule_set_path = Vec::from([
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        RULE_SET.as_bytes(),
+    ]);
+    let bump = assert_derivation(program_id, rule_set_info, &rule_set_path)?;
+
+    let rule_set_len = RuleSet::serialized_len(args.allow_list.len(), args.deny_list.len());
+
+    if rule_set_info.data_is_empty() {
+        let bump_seed = &[bump];
+        let mut rule_set_seeds = rule_set_path.clone();
+        rule_set_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            rule_set_info,
+            system_program_info,
+            payer_info,
+            rule_set_len,
+            &rule_set_seeds,
+        )?;
+    } else {
+        // Make sure we are replacing a rule set and not some other account.
+        RuleSet::from_account_info(rule_set_info)?;
+
+        if rule_set_info.data_len() != rule_set_len {
+            resize_or_reallocate_account_raw(
+                rule_set_info,
+                payer_info,
+                system_program_info,
+                rule_set_len,
+            )?;
+        }
+    }
+
+    let rule_set = RuleSet {
+        key: Key::RuleSet,
+        mint: *mint_info.key,
+        bump,
+        allow_list: args.allow_list,
+        deny_list: args.deny_list,
+    };
+    BorshSerialize::serialize(&rule_set, &mut *rule_set_info.try_borrow_mut_data()?)?;
+
+    if metadata.rule_set != Some(*rule_set_info.key) {
+        metadata.rule_set = Some(*rule_set_info.key);
+        clean_write_metadata(&mut metadata, metadata_info)?;
+    }
+
+    Ok(())
+}
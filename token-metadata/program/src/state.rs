@@ -53,6 +53,15 @@ pub const MAX_METADATA_LEN: usize = 1 //key
 + 18 // uses
 + 118; // Padding
 
+// Resized metadata accounts always keep room for the optional fields after `Data` so they can
+// be set without reallocating. Only the strings and creators are fitted to their content.
+pub const METADATA_OPTIONAL_FIELDS_LEN: usize = 2 // nonce
++ 2 // token standard
++ 34 // collection
++ 18 // uses
++ 10 // collection details
++ 33; // rule set
+
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
     + 4
@@ -89,8 +98,36 @@ pub const EDITION_MARKER_BIT_SIZE: u64 = 248;
 
 pub const USE_AUTHORITY_RECORD_SIZE: usize = 18; //8 byte padding
 
+pub const SCOPED_USE_AUTHORITY_RECORD_SIZE: usize = 1 // key
+    + 8 // allowed uses
+    + 1 // bump
+    + 9 // expiry
+    + 33; // caller program
+
+pub const USE_AUTHORITY_RECORD_WITH_PAYER_SIZE: usize = SCOPED_USE_AUTHORITY_RECORD_SIZE + 33; // payer
+
 pub const COLLECTION_AUTHORITY_RECORD_SIZE: usize = 35;
 
+pub const SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE: usize = COLLECTION_AUTHORITY_RECORD_SIZE
+    + 1 // permissions
+    + 9; // expiry
+
+pub const COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE: usize =
+    SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE + 33; // payer
+
+/// Permission of a collection authority to verify items.
+pub const COLLECTION_PERMISSION_VERIFY: u8 = 1;
+/// Permission of a collection authority to unverify items.
+pub const COLLECTION_PERMISSION_UNVERIFY: u8 = 1 << 1;
+/// Permission of a collection authority to set the collection size.
+pub const COLLECTION_PERMISSION_SET_SIZE: u8 = 1 << 2;
+/// Permission of a collection authority to set the collection of an item and verify it at once.
+pub const COLLECTION_PERMISSION_SET_AND_VERIFY: u8 = 1 << 3;
+pub const COLLECTION_PERMISSION_ALL: u8 = COLLECTION_PERMISSION_VERIFY
+    | COLLECTION_PERMISSION_UNVERIFY
+    | COLLECTION_PERMISSION_SET_SIZE
+    | COLLECTION_PERMISSION_SET_AND_VERIFY;
+
 pub trait TokenMetadataAccount: BorshDeserialize {
     fn key() -> Key;
 
@@ -155,6 +192,17 @@ pub enum Key {
     UseAuthorityRecord,
     CollectionAuthorityRecord,
     TokenOwnedEscrow,
+    RuleSet,
+    MetadataDelegate,
+    MetadataAttributes,
+    CollectionRoyalties,
+    PrintConfig,
+    PrintRecord,
+    UseSchedule,
+    CollectionCreators,
+    CreatorsProposal,
+    FungibleConfig,
+    CollectionValidationProfile,
 }
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -212,6 +260,10 @@ pub enum UseMethod {
     Burn,
     Multiple,
     Single,
+    /// Uses are restored periodically, see `UseSchedule`.
+    Recharging,
+    /// Uses can't be spent after a deadline, see `UseSchedule`.
+    Expiring,
 }
 
 #[repr(C)]
@@ -235,19 +287,26 @@ pub struct Uses {
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, FromPrimitive)]
 pub enum TokenStandard {
-    NonFungible,        // This is a master edition
-    FungibleAsset,      // A token with metadata that can also have attrributes
-    Fungible,           // A token with simple metadata
-    NonFungibleEdition, // This is a limited edition
+    NonFungible,             // This is a master edition
+    FungibleAsset,           // A token with metadata that can also have attrributes
+    Fungible,                // A token with simple metadata
+    NonFungibleEdition,      // This is a limited edition
+    ProgrammableNonFungible, // A master edition whose token account stays frozen between transfers
 }
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+#[derive(PartialEq, Eq, Debug, Clone, ShankAccount)]
 pub struct UseAuthorityRecord {
     pub key: Key,          //1
     pub allowed_uses: u64, //8
     pub bump: u8,
+    /// Unix timestamp after which the use authority can't use the asset
+    pub expiry: Option<i64>,
+    /// Program the use authority must be invoked through
+    pub caller_program: Option<Pubkey>,
+    /// Account that funded the record, which gets the rent back when it is closed
+    pub payer: Option<Pubkey>,
 }
 
 impl Default for UseAuthorityRecord {
@@ -256,10 +315,57 @@ impl Default for UseAuthorityRecord {
             key: Key::UseAuthorityRecord,
             allowed_uses: 0,
             bump: 255,
+            expiry: None,
+            caller_program: None,
+            payer: None,
         }
     }
 }
 
+// Records approved before the payer was stored end after the scope, or are zero padded.
+impl borsh::de::BorshDeserialize for UseAuthorityRecord {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, BorshError> {
+        let key: Key = BorshDeserialize::deserialize(buf)?;
+        let allowed_uses: u64 = BorshDeserialize::deserialize(buf)?;
+        let bump: u8 = BorshDeserialize::deserialize(buf)?;
+        let expiry: Option<i64> = BorshDeserialize::deserialize(buf)?;
+        let caller_program: Option<Pubkey> = BorshDeserialize::deserialize(buf)?;
+        let payer: Option<Pubkey> = if buf.is_empty() {
+            None
+        } else {
+            BorshDeserialize::deserialize(buf)?
+        };
+
+        Ok(UseAuthorityRecord {
+            key,
+            allowed_uses,
+            bump,
+            expiry,
+            caller_program,
+            payer,
+        })
+    }
+}
+
+impl borsh::ser::BorshSerialize for UseAuthorityRecord {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> ::core::result::Result<(), BorshError> {
+        BorshSerialize::serialize(&self.key, writer)?;
+        BorshSerialize::serialize(&self.allowed_uses, writer)?;
+        BorshSerialize::serialize(&self.bump, writer)?;
+        BorshSerialize::serialize(&self.expiry, writer)?;
+        BorshSerialize::serialize(&self.caller_program, writer)?;
+
+        if self.payer.is_some() {
+            BorshSerialize::serialize(&self.payer, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl TokenMetadataAccount for UseAuthorityRecord {
     fn key() -> Key {
         Key::UseAuthorityRecord
@@ -268,6 +374,24 @@ impl TokenMetadataAccount for UseAuthorityRecord {
     fn size() -> usize {
         USE_AUTHORITY_RECORD_SIZE
     }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        // Records without a scope keep the original size, the padding reads as no scope.
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => {
+                (key == data_type || key == Key::Uninitialized)
+                    && (data.len() == data_size
+                        || data.len() == SCOPED_USE_AUTHORITY_RECORD_SIZE
+                        || data.len() == USE_AUTHORITY_RECORD_WITH_PAYER_SIZE)
+            }
+            None => false,
+        }
+    }
 }
 
 impl UseAuthorityRecord {
@@ -280,15 +404,25 @@ impl UseAuthorityRecord {
     pub fn bump_empty(&self) -> bool {
         self.bump == 0 && self.key == Key::UseAuthorityRecord
     }
+
+    pub fn is_scoped(&self) -> bool {
+        self.expiry.is_some() || self.caller_program.is_some()
+    }
 }
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+#[derive(PartialEq, Eq, Debug, Clone, ShankAccount)]
 pub struct CollectionAuthorityRecord {
     pub key: Key,                         //1
     pub bump: u8,                         //1
     pub update_authority: Option<Pubkey>, //33 (1 + 32)
+    /// Bitmask of the `COLLECTION_PERMISSION_*` operations the authority may perform
+    pub permissions: u8,
+    /// Unix timestamp after which the authority can't act on the collection
+    pub expiry: Option<i64>,
+    /// Account that funded the record, which gets the rent back when it is closed
+    pub payer: Option<Pubkey>,
 }
 
 impl Default for CollectionAuthorityRecord {
@@ -297,10 +431,74 @@ impl Default for CollectionAuthorityRecord {
             key: Key::CollectionAuthorityRecord,
             bump: 255,
             update_authority: None,
+            permissions: COLLECTION_PERMISSION_ALL,
+            expiry: None,
+            payer: None,
         }
     }
 }
 
+// Records approved without a scope end after the update authority and allow everything.
+impl borsh::de::BorshDeserialize for CollectionAuthorityRecord {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, BorshError> {
+        let key: Key = BorshDeserialize::deserialize(buf)?;
+        let bump: u8 = BorshDeserialize::deserialize(buf)?;
+        let update_authority: Option<Pubkey> = BorshDeserialize::deserialize(buf)?;
+        // The update authority is zero padded to a fixed size when not set.
+        if update_authority.is_none() {
+            *buf = buf.get(32..).unwrap_or_default();
+        }
+
+        let (permissions, expiry) = if buf.is_empty() {
+            (COLLECTION_PERMISSION_ALL, None)
+        } else {
+            (
+                BorshDeserialize::deserialize(buf)?,
+                BorshDeserialize::deserialize(buf)?,
+            )
+        };
+        let payer: Option<Pubkey> = if buf.is_empty() {
+            None
+        } else {
+            BorshDeserialize::deserialize(buf)?
+        };
+
+        Ok(CollectionAuthorityRecord {
+            key,
+            bump,
+            update_authority,
+            permissions,
+            expiry,
+            payer,
+        })
+    }
+}
+
+impl borsh::ser::BorshSerialize for CollectionAuthorityRecord {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> ::core::result::Result<(), BorshError> {
+        BorshSerialize::serialize(&self.key, writer)?;
+        BorshSerialize::serialize(&self.bump, writer)?;
+        BorshSerialize::serialize(&self.update_authority, writer)?;
+
+        if self.is_scoped() || self.payer.is_some() {
+            if self.update_authority.is_none() {
+                writer.write_all(&[0; 32])?;
+            }
+            BorshSerialize::serialize(&self.permissions, writer)?;
+            BorshSerialize::serialize(&self.expiry, writer)?;
+        }
+
+        if self.payer.is_some() {
+            BorshSerialize::serialize(&self.payer, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl TokenMetadataAccount for CollectionAuthorityRecord {
     fn key() -> Key {
         Key::CollectionAuthorityRecord
@@ -309,6 +507,23 @@ impl TokenMetadataAccount for CollectionAuthorityRecord {
     fn size() -> usize {
         COLLECTION_AUTHORITY_RECORD_SIZE
     }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => {
+                (key == data_type || key == Key::Uninitialized)
+                    && (data.len() == data_size
+                        || data.len() == SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE
+                        || data.len() == COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE)
+            }
+            None => false,
+        }
+    }
 }
 
 impl CollectionAuthorityRecord {
@@ -320,6 +535,10 @@ impl CollectionAuthorityRecord {
         )?;
         Ok(ca)
     }
+
+    pub fn is_scoped(&self) -> bool {
+        self.permissions != COLLECTION_PERMISSION_ALL || self.expiry.is_some()
+    }
 }
 
 #[repr(C)]
@@ -352,6 +571,8 @@ pub struct Metadata {
     pub uses: Option<Uses>,
     /// Item Details
     pub collection_details: Option<CollectionDetails>,
+    /// Rule set restricting who may act as a delegate, if one is attached
+    pub rule_set: Option<Pubkey>,
 }
 
 impl Default for Metadata {
@@ -368,6 +589,7 @@ impl Default for Metadata {
             collection: None,
             uses: None,
             collection_details: None,
+            rule_set: None,
         }
     }
 }
@@ -380,6 +602,43 @@ impl TokenMetadataAccount for Metadata {
     fn size() -> usize {
         MAX_METADATA_LEN
     }
+
+    // Legacy accounts are padded out to `MAX_METADATA_LEN`; resized accounts are shorter, but
+    // only ever hold initialized metadata.
+    fn is_correct_account_type(data: &[u8], data_type: Key, data_size: usize) -> bool {
+        if data.is_empty() || data.len() > data_size {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(Key::MetadataV1) => data_type == Key::MetadataV1,
+            Some(key) => (key == data_type || key == Key::Uninitialized) && data.len() == data_size,
+            None => false,
+        }
+    }
+}
+
+impl Metadata {
+    /// Account size needed to hold this metadata with unpadded strings.
+    pub fn compact_len(&self) -> usize {
+        let creators_len = match &self.data.creators {
+            Some(creators) => 1 + 4 + creators.len() * MAX_CREATOR_LEN,
+            None => 1,
+        };
+
+        1 // key
+        + 32 // update auth pubkey
+        + 32 // mint pubkey
+        + 4 + self.data.name.trim_end_matches(char::from(0)).len()
+        + 4 + self.data.symbol.trim_end_matches(char::from(0)).len()
+        + 4 + self.data.uri.trim_end_matches(char::from(0)).len()
+        + 2 // seller fee basis points
+        + creators_len
+        + 1 // primary sale
+        + 1 // mutable
+        + METADATA_OPTIONAL_FIELDS_LEN
+    }
 }
 
 // We have a custom implementation of BorshDeserialize for Metadata because of corrupted metadata issues
@@ -421,13 +680,19 @@ pub fn get_master_edition(account: &AccountInfo) -> Result<Box<dyn MasterEdition
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, ShankAccount)]
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, ShankAccount)]
 pub struct MasterEditionV2 {
     pub key: Key,
 
     pub supply: u64,
 
     pub max_supply: Option<u64>,
+
+    /// Name and uri of the prints, when they should differ from the master.
+    pub print_template: Option<PrintTemplate>,
+
+    /// Print config enforced when minting prints, if one is attached.
+    pub print_config: Option<Pubkey>,
 }
 
 impl Default for MasterEditionV2 {
@@ -436,10 +701,65 @@ impl Default for MasterEditionV2 {
             key: Key::MasterEditionV2,
             supply: 0,
             max_supply: Some(0),
+            print_template: None,
+            print_config: None,
         }
     }
 }
 
+impl MasterEditionV2 {
+    /// Print config attached to the master edition of `mint`. Master editions converted from V1
+    /// may read the leftover printing mints as one, so only the print config PDA of `mint` counts.
+    pub fn print_config_of(&self, program_id: &Pubkey, mint: &Pubkey) -> Option<Pubkey> {
+        let print_config = self.print_config?;
+        let (print_config_key, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint.as_ref(),
+                PRINT_CONFIG.as_bytes(),
+            ],
+            program_id,
+        );
+
+        if print_config == print_config_key {
+            Some(print_config)
+        } else {
+            None
+        }
+    }
+}
+
+// Master editions converted from V1 still hold the V1 printing mints after `max_supply`, so an
+// unreadable print template is treated as not set.
+impl borsh::de::BorshDeserialize for MasterEditionV2 {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, BorshError> {
+        let key: Key = BorshDeserialize::deserialize(buf)?;
+        let supply: u64 = BorshDeserialize::deserialize(buf)?;
+        let max_supply: Option<u64> = BorshDeserialize::deserialize(buf)?;
+        let print_template_res: Result<Option<PrintTemplate>, BorshError> =
+            BorshDeserialize::deserialize(buf);
+        let print_config_res: Result<Option<Pubkey>, BorshError> =
+            BorshDeserialize::deserialize(buf);
+
+        let (print_template, print_config) = match (print_template_res, print_config_res) {
+            (Ok(Some(print_template)), Ok(print_config)) if print_template.is_valid() => {
+                (Some(print_template), print_config)
+            }
+            (Ok(None), Ok(print_config)) => (None, print_config),
+            _ => (None, None),
+        };
+
+        Ok(MasterEditionV2 {
+            key,
+            supply,
+            max_supply,
+            print_template,
+            print_config,
+        })
+    }
+}
+
 impl TokenMetadataAccount for MasterEditionV2 {
     fn key() -> Key {
         Key::MasterEditionV2
@@ -547,6 +867,9 @@ pub struct Edition {
 
     /// Starting at 0 for master record, this is incremented for each edition minted.
     pub edition: u64,
+
+    /// Uri derived from the print template of the master edition, if it had one.
+    pub uri_override: Option<String>,
 }
 
 impl Default for Edition {
@@ -555,6 +878,7 @@ impl Default for Edition {
             key: Key::EditionV1,
             parent: Pubkey::default(),
             edition: 0,
+            uri_override: None,
         }
     }
 }
@@ -569,6 +893,60 @@ impl TokenMetadataAccount for Edition {
     }
 }
 
+/// Placeholder of a print template uri replaced with the edition number.
+pub const EDITION_NUMBER_PLACEHOLDER: &str = "{edition}";
+
+/// The uri override has to fit the 200 bytes left after the edition number, including the
+/// option and string length prefixes.
+pub const MAX_EDITION_URI_LENGTH: usize = 195;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PrintTemplate {
+    /// Appends ` #<edition>` to the name of each print.
+    pub append_edition_number: bool,
+    /// Uri of each print, with `{edition}` replaced by the edition number.
+    pub uri_template: Option<String>,
+}
+
+impl PrintTemplate {
+    pub fn is_valid(&self) -> bool {
+        match &self.uri_template {
+            Some(uri_template) => {
+                uri_template.len() <= MAX_URI_LENGTH
+                    && uri_template.contains(EDITION_NUMBER_PLACEHOLDER)
+            }
+            None => self.append_edition_number,
+        }
+    }
+
+    /// Name of a print. The base name is truncated when needed so that the name with the edition
+    /// number still fits `MAX_NAME_LENGTH`.
+    pub fn name(&self, name: &str, edition: u64) -> String {
+        if !self.append_edition_number {
+            return name.to_owned();
+        }
+
+        let suffix = format!(" #{}", edition);
+        let mut base = name.trim_end_matches(char::from(0));
+        let mut max_base_len = MAX_NAME_LENGTH.saturating_sub(suffix.len());
+        if base.len() > max_base_len {
+            while !base.is_char_boundary(max_base_len) {
+                max_base_len -= 1;
+            }
+            base = base[..max_base_len].trim_end();
+        }
+        format!("{}{}", base, suffix)
+    }
+
+    pub fn uri(&self, edition: u64) -> Option<String> {
+        self.uri_template.as_ref().map(|uri_template| {
+            uri_template.replace(EDITION_NUMBER_PLACEHOLDER, &edition.to_string())
+        })
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Eq, Hash)]
@@ -962,14 +1340,63 @@ impl EscrowAuthority {
     }
 }
 
+/// Maximum number of escrow levels below and including a root escrow.
+pub const MAX_ESCROW_DEPTH: u8 = 4;
+
+pub const ESCROW_HOLDING_LEN: usize = 32 // mint
+    + 8 // amount
+    + 1; // nested height
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct EscrowHolding {
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Height of the escrow owned by the held token, zero when it has none.
+    pub nested_height: u8,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
 pub struct TokenOwnedEscrow {
     pub key: Key,
     pub base_token: Pubkey,
     pub authority: EscrowAuthority,
     pub bump: u8,
+    /// Tokens transferred in through `TransferIntoEscrow`.
+    pub holdings: Vec<EscrowHolding>,
+    /// Escrow currently holding the base token, when this escrow is nested.
+    pub parent: Option<Pubkey>,
+}
+
+// Escrows created before holdings were tracked end after the bump.
+impl borsh::de::BorshDeserialize for TokenOwnedEscrow {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, BorshError> {
+        let key: Key = BorshDeserialize::deserialize(buf)?;
+        let base_token: Pubkey = BorshDeserialize::deserialize(buf)?;
+        let authority: EscrowAuthority = BorshDeserialize::deserialize(buf)?;
+        let bump: u8 = BorshDeserialize::deserialize(buf)?;
+
+        let (holdings, parent) = if buf.is_empty() {
+            (vec![], None)
+        } else {
+            (
+                BorshDeserialize::deserialize(buf)?,
+                BorshDeserialize::deserialize(buf)?,
+            )
+        };
+
+        Ok(TokenOwnedEscrow {
+            key,
+            base_token,
+            authority,
+            bump,
+            holdings,
+            parent,
+        })
+    }
 }
 
 impl TokenMetadataAccount for TokenOwnedEscrow {
@@ -989,3 +1416,650 @@ impl TokenMetadataAccount for TokenOwnedEscrow {
         }
     }
 }
+
+impl TokenOwnedEscrow {
+    pub fn serialized_len(&self) -> usize {
+        1 // key
+        + 32 // base token
+        + match self.authority {
+            EscrowAuthority::TokenOwner => 1,
+            EscrowAuthority::Creator(_) => 33,
+        }
+        + 1 // bump
+        + 4 + self.holdings.len() * ESCROW_HOLDING_LEN
+        + 1 + if self.parent.is_some() { 32 } else { 0 }
+    }
+
+    /// Number of escrow levels from this escrow down to its deepest nested escrow.
+    pub fn height(&self) -> u8 {
+        1 + self
+            .holdings
+            .iter()
+            .map(|holding| holding.nested_height)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Tracked amount of `mint`, zero when it isn't held.
+    pub fn holding_amount(&self, mint: &Pubkey) -> u64 {
+        self.holdings
+            .iter()
+            .find(|holding| holding.mint == *mint)
+            .map_or(0, |holding| holding.amount)
+    }
+
+    pub fn add_holding(
+        &mut self,
+        mint: Pubkey,
+        amount: u64,
+        nested_height: u8,
+    ) -> Result<(), ProgramError> {
+        match self
+            .holdings
+            .iter_mut()
+            .find(|holding| holding.mint == mint)
+        {
+            Some(holding) => {
+                holding.amount = holding
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(MetadataError::NumericalOverflowError)?;
+                holding.nested_height = holding.nested_height.max(nested_height);
+            }
+            None => self.holdings.push(EscrowHolding {
+                mint,
+                amount,
+                nested_height,
+            }),
+        }
+
+        Ok(())
+    }
+
+    /// Decreases the tracked amount of `mint`, dropping the holding once it is empty. Returns the
+    /// removed holding's nested height, or `None` when `mint` isn't tracked.
+    pub fn remove_holding(&mut self, mint: &Pubkey, amount: u64) -> Option<u8> {
+        let index = self
+            .holdings
+            .iter()
+            .position(|holding| holding.mint == *mint)?;
+        let holding = &mut self.holdings[index];
+        let nested_height = holding.nested_height;
+
+        // Untracked deposits can leave more tokens in the escrow than recorded.
+        holding.amount = holding.amount.saturating_sub(amount);
+        if holding.amount == 0 {
+            self.holdings.remove(index);
+        }
+
+        Some(nested_height)
+    }
+
+    pub fn set_nested_height(&mut self, mint: &Pubkey, nested_height: u8) -> bool {
+        match self
+            .holdings
+            .iter_mut()
+            .find(|holding| holding.mint == *mint)
+        {
+            Some(holding) => {
+                holding.nested_height = nested_height;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub const RULE_SET: &str = "rule_set";
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct RuleSet {
+    pub key: Key,
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Programs or wallets that may act as delegates. When empty, anyone not on the deny list may.
+    pub allow_list: Vec<Pubkey>,
+    /// Programs or wallets that may never act as delegates.
+    pub deny_list: Vec<Pubkey>,
+}
+
+impl TokenMetadataAccount for RuleSet {
+    fn key() -> Key {
+        Key::RuleSet
+    }
+
+    fn size() -> usize {
+        0
+    }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, _data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => key == data_type || key == Key::Uninitialized,
+            None => false,
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn serialized_len(allow_list_len: usize, deny_list_len: usize) -> usize {
+        1 // key
+        + 32 // mint
+        + 1 // bump
+        + 4 + allow_list_len * 32
+        + 4 + deny_list_len * 32
+    }
+
+    /// Checks a delegate against the lists. Both the delegate address and the program owning
+    /// the delegate account are matched, so listing a program ID covers all of its PDAs.
+    pub fn allows(&self, delegate: &Pubkey, delegate_owner: &Pubkey) -> bool {
+        let listed = |list: &[Pubkey]| {
+            list.iter()
+                .any(|key| key == delegate || key == delegate_owner)
+        };
+
+        if listed(&self.deny_list) {
+            return false;
+        }
+
+        self.allow_list.is_empty() || listed(&self.allow_list)
+    }
+
+    /// Like `allows`, but an empty allow list allows nobody. Used for privileges that must be
+    /// granted explicitly, such as transferring a programmable asset without paying royalties.
+    pub fn explicitly_allows(&self, delegate: &Pubkey, delegate_owner: &Pubkey) -> bool {
+        let listed = |list: &[Pubkey]| {
+            list.iter()
+                .any(|key| key == delegate || key == delegate_owner)
+        };
+
+        !listed(&self.deny_list) && listed(&self.allow_list)
+    }
+}
+
+pub const METADATA_DELEGATE: &str = "metadata_delegate";
+
+pub const METADATA_DELEGATE_RECORD_SIZE: usize = 1 // key
++ 1 // bump
++ 1 // role
++ 32 // update authority
++ 32; // payer
+
+/// The fields of a metadata a delegate may change through `Update`.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MetadataDelegateRole {
+    Uri,
+    Uses,
+    Collection,
+    /// May set and remove on-chain attributes; cannot change any `Update` field.
+    Attributes,
+}
+
+impl MetadataDelegateRole {
+    pub fn seed(&self) -> &'static str {
+        match self {
+            MetadataDelegateRole::Uri => "uri",
+            MetadataDelegateRole::Uses => "uses",
+            MetadataDelegateRole::Collection => "collection",
+            MetadataDelegateRole::Attributes => "attributes",
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MetadataDelegateRecord {
+    pub key: Key,
+    pub bump: u8,
+    pub role: MetadataDelegateRole,
+    /// Update authority that approved the delegate. The record stops working if the
+    /// update authority of the metadata changes.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub update_authority: Pubkey,
+    /// Account that funded the record, which gets the rent back when it is revoked.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub payer: Pubkey,
+}
+
+impl TokenMetadataAccount for MetadataDelegateRecord {
+    fn key() -> Key {
+        Key::MetadataDelegate
+    }
+
+    fn size() -> usize {
+        METADATA_DELEGATE_RECORD_SIZE
+    }
+}
+
+pub const ATTRIBUTES: &str = "attributes";
+
+pub const MAX_ATTRIBUTES: usize = 32;
+
+pub const MAX_ATTRIBUTE_KEY_LENGTH: usize = 32;
+
+pub const MAX_ATTRIBUTE_VALUE_LENGTH: usize = 64;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Attribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// On-chain key/value traits of an asset, readable by other programs without fetching the uri.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MetadataAttributes {
+    pub key: Key,
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub attributes: Vec<Attribute>,
+}
+
+impl TokenMetadataAccount for MetadataAttributes {
+    fn key() -> Key {
+        Key::MetadataAttributes
+    }
+
+    fn size() -> usize {
+        0
+    }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, _data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => key == data_type || key == Key::Uninitialized,
+            None => false,
+        }
+    }
+}
+
+impl MetadataAttributes {
+    pub fn serialized_len(&self) -> usize {
+        1 // key
+        + 32 // mint
+        + 1 // bump
+        + 4 + self
+            .attributes
+            .iter()
+            .map(|attribute| 4 + attribute.key.len() + 4 + attribute.value.len())
+            .sum::<usize>()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    /// Inserts the attribute or replaces the value of an existing one with the same key.
+    pub fn set(&mut self, key: String, value: String) {
+        match self
+            .attributes
+            .iter_mut()
+            .find(|attribute| attribute.key == key)
+        {
+            Some(attribute) => attribute.value = value,
+            None => self.attributes.push(Attribute { key, value }),
+        }
+    }
+
+    /// Removes the attribute, returning whether it was present.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let len = self.attributes.len();
+        self.attributes.retain(|attribute| attribute.key != key);
+        self.attributes.len() != len
+    }
+}
+
+pub const COLLECTION_ROYALTIES: &str = "collection_royalties";
+
+/// Default royalties of a sized collection, inherited by verified items without creators.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionRoyalties {
+    pub key: Key,
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+impl TokenMetadataAccount for CollectionRoyalties {
+    fn key() -> Key {
+        Key::CollectionRoyalties
+    }
+
+    fn size() -> usize {
+        0
+    }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, _data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => key == data_type || key == Key::Uninitialized,
+            None => false,
+        }
+    }
+}
+
+impl CollectionRoyalties {
+    pub fn serialized_len(&self) -> usize {
+        1 // key
+        + 32 // mint
+        + 1 // bump
+        + 2 // seller fee basis points
+        + 4 + self.creators.len() * MAX_CREATOR_LEN
+    }
+}
+
+pub const COLLECTION_CREATORS: &str = "collection_creators";
+
+/// Verified creators of a collection allowed to verify items they are verified creators of,
+/// without the update authority of the collection.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionCreators {
+    pub key: Key,
+    /// Mint of the collection
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub creators: Vec<Pubkey>,
+}
+
+impl TokenMetadataAccount for CollectionCreators {
+    fn key() -> Key {
+        Key::CollectionCreators
+    }
+
+    fn size() -> usize {
+        0
+    }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, _data_size: usize) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let key: Option<Key> = Key::from_u8(data[0]);
+        match key {
+            Some(key) => key == data_type || key == Key::Uninitialized,
+            None => false,
+        }
+    }
+}
+
+impl CollectionCreators {
+    pub fn serialized_len(&self) -> usize {
+        1 // key
+        + 32 // mint
+        + 1 // bump
+        + 4 + self.creators.len() * 32
+    }
+}
+
+pub const CREATORS_PROPOSAL: &str = "creators_proposal";
+
+pub const CREATORS_PROPOSAL_SIZE: usize = 1 // key
++ 32 // mint
++ 1 // bump
++ 4 + MAX_CREATOR_LIMIT * MAX_CREATOR_LEN // creators
++ 4 + 2 * MAX_CREATOR_LIMIT * 32; // approvals
+
+/// Creators proposed by the update authority, applied once every verified creator of the current
+/// and proposed creators has approved them.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CreatorsProposal {
+    pub key: Key,
+    /// Mint of the metadata the creators are proposed for
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub creators: Vec<Creator>,
+    /// Creators who approved the proposed creators
+    pub approvals: Vec<Pubkey>,
+}
+
+impl TokenMetadataAccount for CreatorsProposal {
+    fn key() -> Key {
+        Key::CreatorsProposal
+    }
+
+    fn size() -> usize {
+        CREATORS_PROPOSAL_SIZE
+    }
+}
+
+pub const FUNGIBLE_CONFIG: &str = "fungible_config";
+
+pub const FUNGIBLE_CONFIG_SIZE: usize = 1 // key
++ 32 // mint
++ 1 // bump
++ 32 // mint authority
++ 8 // max supply
++ 1; // decimals
+
+/// Supply cap of a fungible asset. The config is the mint authority of the mint, so tokens can
+/// only be minted through the program and never beyond `max_supply`.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct FungibleConfig {
+    pub key: Key,
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Authority allowed to mint through the program, the mint authority before the handoff
+    pub mint_authority: Pubkey,
+    /// Maximum supply in base units of the mint
+    pub max_supply: u64,
+    /// Decimals of the mint, recorded so the cap can be shown without fetching the mint
+    pub decimals: u8,
+}
+
+impl TokenMetadataAccount for FungibleConfig {
+    fn key() -> Key {
+        Key::FungibleConfig
+    }
+
+    fn size() -> usize {
+        FUNGIBLE_CONFIG_SIZE
+    }
+}
+
+impl FungibleConfig {
+    /// The max supply in whole tokens.
+    pub fn ui_max_supply(&self) -> f64 {
+        spl_token::amount_to_ui_amount(self.max_supply, self.decimals)
+    }
+}
+
+pub const COLLECTION_VALIDATION_PROFILE: &str = "validation_profile";
+
+pub const COLLECTION_VALIDATION_PROFILE_SIZE: usize = 1 // key
++ 32 // mint
++ 1 // bump
++ 1; // rules
+
+/// The uri must use the https, ar or ipfs scheme.
+pub const VALIDATION_RULE_URI_SCHEME: u8 = 1;
+/// The name must only contain printable ASCII characters, which rules out homoglyphs.
+pub const VALIDATION_RULE_NAME_CHARSET: u8 = 1 << 1;
+/// The symbol must only contain uppercase ASCII letters and digits.
+pub const VALIDATION_RULE_UPPERCASE_SYMBOL: u8 = 1 << 2;
+/// The name, symbol and uri must not contain zero-width or other invisible characters.
+pub const VALIDATION_RULE_NO_ZERO_WIDTH: u8 = 1 << 3;
+pub const VALIDATION_RULES_ALL: u8 = VALIDATION_RULE_URI_SCHEME
+    | VALIDATION_RULE_NAME_CHARSET
+    | VALIDATION_RULE_UPPERCASE_SYMBOL
+    | VALIDATION_RULE_NO_ZERO_WIDTH;
+
+/// Rules a sized collection requires of the name, symbol and uri of the items it verifies.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionValidationProfile {
+    pub key: Key,
+    /// Mint of the collection
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Bitmask of the `VALIDATION_RULE_*` rules items must follow
+    pub rules: u8,
+}
+
+impl TokenMetadataAccount for CollectionValidationProfile {
+    fn key() -> Key {
+        Key::CollectionValidationProfile
+    }
+
+    fn size() -> usize {
+        COLLECTION_VALIDATION_PROFILE_SIZE
+    }
+}
+
+pub const PRINT_CONFIG: &str = "print_config";
+
+pub const PRINT_CONFIG_SIZE: usize = 1 // key
+    + 32 // mint
+    + 1 // bump
+    + 9 // start time
+    + 9 // end time
+    + 9 // max per wallet
+    + 1 + 8 + 33 + 32; // price
+
+pub const PRINT_RECORD_SIZE: usize = 1 // key
+    + 1 // bump
+    + 8; // count
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PrintPrice {
+    pub amount: u64,
+    /// Mint of the payment token, or `None` for SOL.
+    pub mint: Option<Pubkey>,
+    /// Wallet receiving SOL, or token account receiving the payment token.
+    pub destination: Pubkey,
+}
+
+/// Sale rules of the prints of a master edition.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct PrintConfig {
+    pub key: Key,
+    /// Mint of the master edition
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Unix timestamp before which prints can't be minted
+    pub start_time: Option<i64>,
+    /// Unix timestamp after which prints can't be minted
+    pub end_time: Option<i64>,
+    /// Number of prints a single payer can mint
+    pub max_per_wallet: Option<u64>,
+    pub price: Option<PrintPrice>,
+}
+
+impl TokenMetadataAccount for PrintConfig {
+    fn key() -> Key {
+        Key::PrintConfig
+    }
+
+    fn size() -> usize {
+        PRINT_CONFIG_SIZE
+    }
+}
+
+/// Number of prints a payer minted under a print config.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct PrintRecord {
+    pub key: Key,
+    pub bump: u8,
+    pub count: u64,
+}
+
+impl TokenMetadataAccount for PrintRecord {
+    fn key() -> Key {
+        Key::PrintRecord
+    }
+
+    fn size() -> usize {
+        PRINT_RECORD_SIZE
+    }
+}
+
+pub const USE_SCHEDULE: &str = "use_schedule";
+
+pub const USE_SCHEDULE_SIZE: usize = 1 // key
+    + 32 // mint
+    + 1 // bump
+    + 1 + 8 + 8 + 8; // kind
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UseScheduleKind {
+    /// Restores `amount` uses every `period` seconds, up to `Uses.total`.
+    Recharging {
+        period: i64,
+        amount: u64,
+        /// Unix timestamp the last period was credited at
+        last_recharge: i64,
+    },
+    /// Forbids spending uses after the `deadline` unix timestamp.
+    Expiring { deadline: i64 },
+}
+
+/// Time rules of the uses of an asset with a `Recharging` or `Expiring` use method.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct UseSchedule {
+    pub key: Key,
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub kind: UseScheduleKind,
+}
+
+impl TokenMetadataAccount for UseSchedule {
+    fn key() -> Key {
+        Key::UseSchedule
+    }
+
+    fn size() -> usize {
+        USE_SCHEDULE_SIZE
+    }
+}
+
+impl UseSchedule {
+    pub fn use_method(&self) -> UseMethod {
+        match self.kind {
+            UseScheduleKind::Recharging { .. } => UseMethod::Recharging,
+            UseScheduleKind::Expiring { .. } => UseMethod::Expiring,
+        }
+    }
+}
@@ -8,11 +8,15 @@ use crate::{
     error::MetadataError,
     state::{
         CollectionAuthorityRecord, Edition, EditionMarker, Key, MasterEditionV2, Metadata,
-        UseAuthorityRecord, MAX_METADATA_LEN,
+        MetadataAttributes, PrintTemplate, RuleSet, UseAuthorityRecord, COLLECTION_PERMISSION_ALL,
+        MAX_METADATA_LEN, MAX_NAME_LENGTH,
     },
     ID,
 };
-pub use crate::{state::Creator, utils::puff_out_data_fields};
+pub use crate::{
+    state::Creator,
+    utils::{puff_out_data_fields, unpuff_data_fields},
+};
 
 #[cfg(test)]
 mod metadata {
@@ -97,13 +101,45 @@ mod metadata {
         assert_eq!(error, MetadataError::IncorrectOwner.into());
     }
 
+    #[test]
+    fn successfully_deserialize_compact_metadata() {
+        let mut expected_metadata = expected_pesky_metadata();
+        unpuff_data_fields(&mut expected_metadata);
+
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+        buf.resize(expected_metadata.compact_len(), 0);
+        assert!(buf.len() < MAX_METADATA_LEN);
+
+        let pubkey = Keypair::new().pubkey();
+        let owner = &ID;
+        let mut lamports = 1_000_000_000;
+        let mut data = buf.clone();
+
+        let md_account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            owner,
+            false,
+            1_000_000_000,
+        );
+
+        let md = Metadata::from_account_info(&md_account_info).unwrap();
+        assert_eq!(md, expected_metadata);
+    }
+
     #[test]
     fn fail_to_deserialize_metadata_with_wrong_size() {
         let expected_metadata = expected_pesky_metadata();
 
         let mut buf = Vec::new();
         expected_metadata.serialize(&mut buf).unwrap();
-        // No padding is added to the metadata so it's too short.
+        // Resized accounts may be shorter, but never longer than MAX_METADATA_LEN.
+        pad_metadata_length(&mut buf);
+        buf.push(0);
 
         let pubkey = Keypair::new().pubkey();
         let owner = ID;
@@ -127,12 +163,39 @@ mod metadata {
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
 
+    #[test]
+    fn fail_to_deserialize_short_uninitialized_metadata() {
+        let mut expected_metadata = expected_pesky_metadata();
+        unpuff_data_fields(&mut expected_metadata);
+        expected_metadata.key = Key::Uninitialized;
+
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+        buf.resize(expected_metadata.compact_len(), 0);
+
+        assert!(!Metadata::is_correct_account_type(
+            &buf,
+            Key::MetadataV1,
+            MAX_METADATA_LEN
+        ));
+
+        // Uninitialized accounts are still accepted at the original size.
+        buf.resize(MAX_METADATA_LEN, 0);
+        assert!(Metadata::is_correct_account_type(
+            &buf,
+            Key::MetadataV1,
+            MAX_METADATA_LEN
+        ));
+    }
+
     #[test]
     fn fail_to_deserialize_master_edition_into_metadata() {
         let master_edition = MasterEditionV2 {
             key: Key::MasterEditionV2,
             supply: 0,
             max_supply: Some(0),
+            print_template: None,
+            print_config: None,
         };
         let mut buf = Vec::new();
         master_edition.serialize(&mut buf).unwrap();
@@ -166,6 +229,7 @@ mod metadata {
             key: Key::EditionV1,
             parent,
             edition,
+            uri_override: None,
         };
 
         let mut buf = Vec::new();
@@ -197,6 +261,9 @@ mod metadata {
             key: Key::UseAuthorityRecord,
             allowed_uses: 14,
             bump: 255,
+            expiry: None,
+            caller_program: None,
+            payer: None,
         };
 
         let mut buf = Vec::new();
@@ -228,6 +295,9 @@ mod metadata {
             key: Key::CollectionAuthorityRecord,
             bump: 255,
             update_authority: None,
+            permissions: COLLECTION_PERMISSION_ALL,
+            expiry: None,
+            payer: None,
         };
 
         let mut buf = Vec::new();
@@ -285,7 +355,12 @@ mod metadata {
 }
 
 mod master_edition {
-    use crate::state::TokenMetadataAccount;
+    use solana_program::pubkey::Pubkey;
+
+    use crate::{
+        pda::find_print_config_account,
+        state::{MasterEditionV1, TokenMetadataAccount},
+    };
 
     use super::*;
 
@@ -345,6 +420,148 @@ mod master_edition {
         let error = MasterEditionV2::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn ignore_stale_v1_printing_mints() {
+        let expected_data = MasterEditionV2 {
+            max_supply: None,
+            ..MasterEditionV2::default()
+        };
+
+        // A V1 master edition converted in place keeps its printing mints after `max_supply`.
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        buf.extend(vec![7; 64]);
+        MasterEditionV2::pad_length(&mut buf).unwrap();
+
+        let data = MasterEditionV2::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn print_template_numbers_name_and_uri() {
+        let print_template = PrintTemplate {
+            append_edition_number: true,
+            uri_template: Some("https://example.com/{edition}.json".to_string()),
+        };
+        assert!(print_template.is_valid());
+        assert_eq!(print_template.name("Art\0\0\0", 12), "Art #12");
+        let long_name = "A very long name for the artwork";
+        assert_eq!(long_name.len(), MAX_NAME_LENGTH);
+        assert_eq!(
+            print_template.name(long_name, 12345),
+            "A very long name for the #12345"
+        );
+        assert_eq!(
+            print_template.name("Ünïcödé ártwörk nâmé", 123_456_789),
+            "Ünïcödé ártwörk #123456789"
+        );
+        assert_eq!(
+            print_template.uri(12),
+            Some("https://example.com/12.json".to_string())
+        );
+
+        let print_template = PrintTemplate {
+            append_edition_number: false,
+            uri_template: None,
+        };
+        assert!(!print_template.is_valid());
+        assert_eq!(print_template.name("Art", 12), "Art");
+        assert_eq!(print_template.uri(12), None);
+
+        let print_template = PrintTemplate {
+            append_edition_number: true,
+            uri_template: Some("https://example.com/art.json".to_string()),
+        };
+        assert!(!print_template.is_valid());
+    }
+
+    #[test]
+    fn ignore_printing_mints_left_by_conversion() {
+        // Printing mints whose bytes read as no print template followed by a print config.
+        let mut printing_mint = Keypair::new().pubkey().to_bytes();
+        printing_mint[0] = 0;
+        printing_mint[1] = 1;
+
+        let master_edition_v1 = MasterEditionV1 {
+            key: Key::MasterEditionV1,
+            supply: 3,
+            max_supply: Some(10),
+            printing_mint: Pubkey::new_from_array(printing_mint),
+            one_time_printing_authorization_mint: Keypair::new().pubkey(),
+        };
+
+        // Conversions before the printing mints were cleared only rewrote the leading fields.
+        let mut buf = master_edition_v1.try_to_vec().unwrap();
+        buf[0] = Key::MasterEditionV2 as u8;
+        MasterEditionV2::pad_length(&mut buf).unwrap();
+
+        let master_edition = MasterEditionV2::safe_deserialize(&buf).unwrap();
+        assert_eq!(master_edition.supply, 3);
+        assert_eq!(master_edition.max_supply, Some(10));
+        assert!(master_edition.print_config.is_some());
+
+        let mint = Keypair::new().pubkey();
+        assert_eq!(master_edition.print_config_of(&ID, &mint), None);
+
+        let (print_config, _) = find_print_config_account(&mint);
+        let master_edition = MasterEditionV2 {
+            print_config: Some(print_config),
+            ..master_edition
+        };
+        assert_eq!(
+            master_edition.print_config_of(&ID, &mint),
+            Some(print_config)
+        );
+    }
+}
+
+mod print_config {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{
+        PrintConfig, PrintPrice, TokenMetadataAccount, MAX_MASTER_EDITION_LEN, MAX_URI_LENGTH,
+        PRINT_CONFIG_SIZE,
+    };
+
+    #[test]
+    fn largest_print_settings_fit_their_accounts() {
+        let print_config = PrintConfig {
+            key: Key::PrintConfig,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            start_time: Some(0),
+            end_time: Some(i64::MAX),
+            max_per_wallet: Some(1),
+            price: Some(PrintPrice {
+                amount: u64::MAX,
+                mint: Some(Pubkey::new_unique()),
+                destination: Pubkey::new_unique(),
+            }),
+        };
+        let mut buf = print_config.try_to_vec().unwrap();
+        assert_eq!(buf.len(), PRINT_CONFIG_SIZE);
+        PrintConfig::pad_length(&mut buf).unwrap();
+        assert_eq!(PrintConfig::safe_deserialize(&buf).unwrap(), print_config);
+
+        let master_edition = MasterEditionV2 {
+            max_supply: Some(u64::MAX),
+            print_template: Some(PrintTemplate {
+                append_edition_number: true,
+                uri_template: Some(format!("{{edition}}{}", "a".repeat(MAX_URI_LENGTH - 9))),
+            }),
+            print_config: Some(Pubkey::new_unique()),
+            ..MasterEditionV2::default()
+        };
+        let mut buf = master_edition.try_to_vec().unwrap();
+        assert!(buf.len() <= MAX_MASTER_EDITION_LEN);
+        MasterEditionV2::pad_length(&mut buf).unwrap();
+        assert_eq!(
+            MasterEditionV2::safe_deserialize(&buf).unwrap(),
+            master_edition
+        );
+    }
 }
 
 mod edition {
@@ -474,7 +691,10 @@ mod edition_marker {
 }
 
 mod use_authority_record {
-    use crate::state::TokenMetadataAccount;
+    use crate::state::{
+        TokenMetadataAccount, SCOPED_USE_AUTHORITY_RECORD_SIZE, USE_AUTHORITY_RECORD_SIZE,
+        USE_AUTHORITY_RECORD_WITH_PAYER_SIZE,
+    };
 
     use super::*;
 
@@ -533,10 +753,53 @@ mod use_authority_record {
         let error = UseAuthorityRecord::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn deserialize_scoped_and_legacy_records() {
+        let scoped = UseAuthorityRecord {
+            allowed_uses: 3,
+            expiry: Some(1_700_000_000),
+            caller_program: Some(Keypair::new().pubkey()),
+            ..UseAuthorityRecord::default()
+        };
+        let buf = scoped.try_to_vec().unwrap();
+        assert_eq!(buf.len(), SCOPED_USE_AUTHORITY_RECORD_SIZE);
+        assert_eq!(UseAuthorityRecord::safe_deserialize(&buf).unwrap(), scoped);
+
+        // Records approved before scopes existed are zero padded to the original size.
+        let mut legacy = vec![Key::UseAuthorityRecord as u8];
+        legacy.extend_from_slice(&7u64.to_le_bytes());
+        legacy.push(254);
+        legacy.resize(USE_AUTHORITY_RECORD_SIZE, 0);
+
+        let record = UseAuthorityRecord::safe_deserialize(&legacy).unwrap();
+        assert_eq!(record.allowed_uses, 7);
+        assert_eq!(record.bump, 254);
+        assert!(!record.is_scoped());
+        assert_eq!(record.payer, None);
+    }
+
+    #[test]
+    fn record_keeps_its_payer() {
+        let record = UseAuthorityRecord {
+            allowed_uses: 3,
+            expiry: Some(1_700_000_000),
+            caller_program: Some(Keypair::new().pubkey()),
+            payer: Some(Keypair::new().pubkey()),
+            ..UseAuthorityRecord::default()
+        };
+        let buf = record.try_to_vec().unwrap();
+        assert_eq!(buf.len(), USE_AUTHORITY_RECORD_WITH_PAYER_SIZE);
+        assert_eq!(UseAuthorityRecord::safe_deserialize(&buf).unwrap(), record);
+    }
 }
 
 mod collection_authority_record {
-    use crate::state::TokenMetadataAccount;
+    use crate::state::{
+        TokenMetadataAccount, COLLECTION_AUTHORITY_RECORD_SIZE,
+        COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE, COLLECTION_PERMISSION_SET_AND_VERIFY,
+        COLLECTION_PERMISSION_VERIFY, SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE,
+    };
 
     use super::*;
 
@@ -595,4 +858,405 @@ mod collection_authority_record {
         let error = CollectionAuthorityRecord::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn scoped_record_keeps_permissions_and_expiry() {
+        let legacy = CollectionAuthorityRecord {
+            update_authority: Some(Keypair::new().pubkey()),
+            ..CollectionAuthorityRecord::default()
+        };
+        let buf = legacy.try_to_vec().unwrap();
+        assert_eq!(buf.len(), COLLECTION_AUTHORITY_RECORD_SIZE);
+        assert_eq!(
+            CollectionAuthorityRecord::safe_deserialize(&buf).unwrap(),
+            legacy
+        );
+
+        let scoped = CollectionAuthorityRecord {
+            permissions: COLLECTION_PERMISSION_VERIFY | COLLECTION_PERMISSION_SET_AND_VERIFY,
+            expiry: Some(1_700_000_000),
+            ..legacy
+        };
+        let buf = scoped.try_to_vec().unwrap();
+        assert_eq!(buf.len(), SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE);
+        assert_eq!(
+            CollectionAuthorityRecord::safe_deserialize(&buf).unwrap(),
+            scoped
+        );
+    }
+
+    #[test]
+    fn record_keeps_its_payer() {
+        let record = CollectionAuthorityRecord {
+            update_authority: Some(Keypair::new().pubkey()),
+            expiry: Some(1_700_000_000),
+            payer: Some(Keypair::new().pubkey()),
+            ..CollectionAuthorityRecord::default()
+        };
+        let buf = record.try_to_vec().unwrap();
+        assert_eq!(buf.len(), COLLECTION_AUTHORITY_RECORD_WITH_PAYER_SIZE);
+        assert_eq!(
+            CollectionAuthorityRecord::safe_deserialize(&buf).unwrap(),
+            record
+        );
+    }
+}
+
+mod rule_set {
+    use solana_program::{pubkey::Pubkey, system_program};
+
+    use crate::state::TokenMetadataAccount;
+
+    use super::*;
+
+    fn rule_set(allow_list: Vec<Pubkey>, deny_list: Vec<Pubkey>) -> RuleSet {
+        RuleSet {
+            key: Key::RuleSet,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            allow_list,
+            deny_list,
+        }
+    }
+
+    #[test]
+    fn successfully_deserialize() {
+        let expected_data = rule_set(vec![Pubkey::new_unique()], vec![Pubkey::new_unique()]);
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), RuleSet::serialized_len(1, 1));
+
+        let pubkey = Keypair::new().pubkey();
+        let owner = &ID;
+        let mut lamports = 1_000_000_000;
+        let mut data = buf.clone();
+
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            owner,
+            false,
+            1_000_000_000,
+        );
+
+        let data = RuleSet::from_account_info(&account_info).unwrap();
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn empty_allow_list_allows_anyone_not_denied() {
+        let denied = Pubkey::new_unique();
+        let rule_set = rule_set(vec![], vec![denied]);
+
+        assert!(rule_set.allows(&Pubkey::new_unique(), &system_program::id()));
+        assert!(!rule_set.allows(&denied, &system_program::id()));
+    }
+
+    #[test]
+    fn allow_list_matches_delegate_or_owning_program() {
+        let allowed_wallet = Pubkey::new_unique();
+        let allowed_program = Pubkey::new_unique();
+        let rule_set = rule_set(vec![allowed_wallet, allowed_program], vec![]);
+
+        assert!(rule_set.allows(&allowed_wallet, &system_program::id()));
+        assert!(rule_set.allows(&Pubkey::new_unique(), &allowed_program));
+        assert!(!rule_set.allows(&Pubkey::new_unique(), &system_program::id()));
+    }
+
+    #[test]
+    fn deny_list_wins_over_allow_list() {
+        let program = Pubkey::new_unique();
+        let rule_set = rule_set(vec![program], vec![program]);
+
+        assert!(!rule_set.allows(&Pubkey::new_unique(), &program));
+    }
+
+    #[test]
+    fn explicitly_allows_requires_listing() {
+        let allowed = Pubkey::new_unique();
+
+        assert!(!rule_set(vec![], vec![]).explicitly_allows(&allowed, &system_program::id()));
+        assert!(rule_set(vec![allowed], vec![]).explicitly_allows(&allowed, &system_program::id()));
+        assert!(!rule_set(vec![allowed], vec![allowed])
+            .explicitly_allows(&allowed, &system_program::id()));
+    }
+}
+
+mod attributes {
+    use solana_program::pubkey::Pubkey;
+
+    use crate::state::TokenMetadataAccount;
+
+    use super::*;
+
+    fn attributes() -> MetadataAttributes {
+        let mut attributes = MetadataAttributes {
+            key: Key::MetadataAttributes,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            attributes: vec![],
+        };
+        attributes.set("level".to_string(), "7".to_string());
+        attributes.set("rarity".to_string(), "legendary".to_string());
+        attributes
+    }
+
+    #[test]
+    fn successfully_deserialize() {
+        let expected_data = attributes();
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), expected_data.serialized_len());
+
+        let pubkey = Keypair::new().pubkey();
+        let owner = &ID;
+        let mut lamports = 1_000_000_000;
+        let mut data = buf.clone();
+
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            owner,
+            false,
+            1_000_000_000,
+        );
+
+        let data = MetadataAttributes::from_account_info(&account_info).unwrap();
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn set_replaces_existing_value() {
+        let mut attributes = attributes();
+        attributes.set("level".to_string(), "8".to_string());
+
+        assert_eq!(attributes.attributes.len(), 2);
+        assert_eq!(attributes.get("level"), Some("8"));
+        assert_eq!(attributes.get("power"), None);
+    }
+
+    #[test]
+    fn remove_reports_missing_key() {
+        let mut attributes = attributes();
+
+        assert!(attributes.remove("level"));
+        assert!(!attributes.remove("level"));
+        assert_eq!(attributes.get("rarity"), Some("legendary"));
+    }
+}
+
+mod token_owned_escrow {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow};
+
+    fn escrow() -> TokenOwnedEscrow {
+        TokenOwnedEscrow {
+            key: Key::TokenOwnedEscrow,
+            base_token: Pubkey::new_unique(),
+            authority: EscrowAuthority::Creator(Pubkey::new_unique()),
+            bump: 254,
+            holdings: vec![],
+            parent: None,
+        }
+    }
+
+    #[test]
+    fn deserialize_escrow_without_holdings() {
+        let expected_data = escrow();
+
+        // Escrows created before holdings were tracked end after the bump.
+        let mut buf = expected_data.try_to_vec().unwrap();
+        buf.truncate(buf.len() - 5);
+
+        let data = TokenOwnedEscrow::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn holdings_track_amounts_and_height() {
+        let mut escrow = escrow();
+        let gem = Pubkey::new_unique();
+        let bag = Pubkey::new_unique();
+        assert_eq!(escrow.height(), 1);
+
+        escrow.add_holding(gem, 5, 0).unwrap();
+        escrow.add_holding(bag, 1, 2).unwrap();
+        escrow.add_holding(gem, 3, 0).unwrap();
+        escrow.parent = Some(Pubkey::new_unique());
+
+        assert_eq!(escrow.holdings.len(), 2);
+        assert_eq!(escrow.holdings[0].amount, 8);
+        assert_eq!(escrow.height(), 3);
+        assert_eq!(escrow.serialized_len(), escrow.try_to_vec().unwrap().len());
+
+        assert_eq!(escrow.remove_holding(&bag, 1), Some(2));
+        assert_eq!(escrow.height(), 1);
+        assert_eq!(escrow.remove_holding(&gem, 10), Some(0));
+        assert!(escrow.holdings.is_empty());
+        assert_eq!(escrow.remove_holding(&gem, 1), None);
+    }
+}
+
+mod use_schedule {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{
+        TokenMetadataAccount, UseMethod, UseSchedule, UseScheduleKind, USE_SCHEDULE_SIZE,
+    };
+
+    #[test]
+    fn successfully_deserialize_both_kinds() {
+        let recharging = UseSchedule {
+            key: Key::UseSchedule,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            kind: UseScheduleKind::Recharging {
+                period: 86_400,
+                amount: 3,
+                last_recharge: 1_700_000_000,
+            },
+        };
+        let mut buf = recharging.try_to_vec().unwrap();
+        assert_eq!(buf.len(), USE_SCHEDULE_SIZE);
+        assert_eq!(UseSchedule::safe_deserialize(&buf).unwrap(), recharging);
+        assert_eq!(recharging.use_method(), UseMethod::Recharging);
+
+        let expiring = UseSchedule {
+            kind: UseScheduleKind::Expiring {
+                deadline: 1_700_000_000,
+            },
+            ..recharging
+        };
+        buf.fill(0);
+        expiring.serialize(&mut buf.as_mut_slice()).unwrap();
+        assert_eq!(UseSchedule::safe_deserialize(&buf).unwrap(), expiring);
+        assert_eq!(expiring.use_method(), UseMethod::Expiring);
+    }
+}
+
+mod collection_creators {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{CollectionCreators, TokenMetadataAccount};
+
+    #[test]
+    fn successfully_deserialize() {
+        let expected_data = CollectionCreators {
+            key: Key::CollectionCreators,
+            mint: Pubkey::new_unique(),
+            bump: 254,
+            creators: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        };
+
+        let buf = expected_data.try_to_vec().unwrap();
+        assert_eq!(buf.len(), expected_data.serialized_len());
+
+        let data = CollectionCreators::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn fail_to_deserialize_with_wrong_key() {
+        let data = CollectionCreators {
+            key: Key::CollectionRoyalties,
+            mint: Pubkey::new_unique(),
+            bump: 254,
+            creators: vec![Pubkey::new_unique()],
+        };
+
+        let buf = data.try_to_vec().unwrap();
+        assert!(CollectionCreators::safe_deserialize(&buf).is_err());
+    }
+}
+
+mod creators_proposal {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{Creator, CreatorsProposal, TokenMetadataAccount, CREATORS_PROPOSAL_SIZE};
+
+    #[test]
+    fn successfully_deserialize_from_padded_account() {
+        let expected_data = CreatorsProposal {
+            key: Key::CreatorsProposal,
+            mint: Pubkey::new_unique(),
+            bump: 253,
+            creators: vec![Creator {
+                address: Pubkey::new_unique(),
+                verified: true,
+                share: 100,
+            }],
+            approvals: vec![Pubkey::new_unique()],
+        };
+
+        let mut buf = vec![0; CREATORS_PROPOSAL_SIZE];
+        expected_data.serialize(&mut buf.as_mut_slice()).unwrap();
+
+        let data = CreatorsProposal::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+    }
+}
+
+mod fungible_config {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{FungibleConfig, TokenMetadataAccount, FUNGIBLE_CONFIG_SIZE};
+
+    #[test]
+    fn successfully_deserialize() {
+        let expected_data = FungibleConfig {
+            key: Key::FungibleConfig,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            mint_authority: Pubkey::new_unique(),
+            max_supply: 21_000_000_000_000,
+            decimals: 6,
+        };
+
+        let buf = expected_data.try_to_vec().unwrap();
+        assert_eq!(buf.len(), FUNGIBLE_CONFIG_SIZE);
+
+        let data = FungibleConfig::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+        assert_eq!(data.ui_max_supply(), 21_000_000.0);
+    }
+}
+
+mod collection_validation_profile {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{
+        CollectionValidationProfile, TokenMetadataAccount, COLLECTION_VALIDATION_PROFILE_SIZE,
+        VALIDATION_RULES_ALL,
+    };
+
+    #[test]
+    fn successfully_deserialize() {
+        let expected_data = CollectionValidationProfile {
+            key: Key::CollectionValidationProfile,
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            rules: VALIDATION_RULES_ALL,
+        };
+
+        let buf = expected_data.try_to_vec().unwrap();
+        assert_eq!(buf.len(), COLLECTION_VALIDATION_PROFILE_SIZE);
+
+        let data = CollectionValidationProfile::safe_deserialize(&buf).unwrap();
+        assert_eq!(data, expected_data);
+    }
 }
@@ -0,0 +1,144 @@
+use crate::{
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{
+        Key, Metadata, MetadataDelegateRecord, MetadataDelegateRole, TokenMetadataAccount,
+        METADATA_DELEGATE_RECORD_SIZE,
+    },
+    update::metadata_delegate_seeds,
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_update_authority_is_correct,
+        create_or_allocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MetadataDelegateArgs {
+    pub role: MetadataDelegateRole,
+}
+
+///# Approve Metadata Delegate
+///
+/// Allows another account to update the fields covered by `role` through [update].
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata delegate record (pda of ['metadata', program id, mint id, 'metadata_delegate', role, delegate])
+///   1. `[]` Delegate
+///   2. `[]` Metadata account
+///   3. `[]` Mint account
+///   4. `[signer]` Update authority
+///   5. `[writable, signer]` Payer
+///   6. `[]` System program
+#[allow(clippy::too_many_arguments)]
+pub fn approve_metadata_delegate(
+    program_id: Pubkey,
+    delegate_record: Pubkey,
+    delegate: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    role: MetadataDelegateRole,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(delegate_record, false),
+        AccountMeta::new_readonly(delegate, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ApproveMetadataDelegate(MetadataDelegateArgs { role })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_approve_metadata_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MetadataDelegateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let delegate_record_info = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let mut delegate_seeds =
+        metadata_delegate_seeds(program_id, mint_info.key, &args.role, delegate_info.key);
+    let bump = assert_derivation(program_id, delegate_record_info, &delegate_seeds)?;
+
+    // A record left behind by a previous update authority can be taken over, the rent
+    // still belongs to whoever funded it.
+    let payer = if delegate_record_info.data_is_empty() {
+        let bump_seed = &[bump];
+        delegate_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            delegate_record_info,
+            system_program_info,
+            payer_info,
+            METADATA_DELEGATE_RECORD_SIZE,
+            &delegate_seeds,
+        )?;
+
+        *payer_info.key
+    } else {
+        let record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+        if record.update_authority == metadata.update_authority {
+            return Err(MetadataError::InvalidMetadataDelegate.into());
+        }
+
+        record.payer
+    };
+
+    let record = MetadataDelegateRecord {
+        key: Key::MetadataDelegate,
+        bump,
+        role: args.role,
+        update_authority: metadata.update_authority,
+        payer,
+    };
+    BorshSerialize::serialize(&record, &mut *delegate_record_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+pub mod approve_metadata_delegate;
+pub mod resize_metadata;
+pub mod revoke_metadata_delegate;
+pub mod update_metadata;
+
+pub use approve_metadata_delegate::*;
+pub use resize_metadata::*;
+pub use revoke_metadata_delegate::*;
+pub use update_metadata::*;
+
+use crate::{
+    error::MetadataError,
+    state::{
+        Metadata, MetadataDelegateRecord, MetadataDelegateRole, TokenMetadataAccount,
+        METADATA_DELEGATE, PREFIX,
+    },
+    utils::{assert_derivation, assert_owned_by},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+pub fn metadata_delegate_seeds<'a>(
+    program_id: &'a Pubkey,
+    mint: &'a Pubkey,
+    role: &MetadataDelegateRole,
+    delegate: &'a Pubkey,
+) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        METADATA_DELEGATE.as_bytes(),
+        role.seed().as_bytes(),
+        delegate.as_ref(),
+    ]
+}
+
+/// Checks that `record_info` is a live metadata delegate record for `delegate_info` on
+/// `metadata` and returns its role.
+pub fn assert_metadata_delegate(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    delegate_info: &AccountInfo,
+    record_info: &AccountInfo,
+) -> Result<MetadataDelegateRole, ProgramError> {
+    assert_owned_by(record_info, program_id)?;
+
+    let record = MetadataDelegateRecord::from_account_info(record_info)
+        .map_err(|_| MetadataError::InvalidMetadataDelegate)?;
+
+    assert_derivation(
+        program_id,
+        record_info,
+        &metadata_delegate_seeds(program_id, &metadata.mint, &record.role, delegate_info.key),
+    )
+    .map_err(|_| MetadataError::InvalidMetadataDelegate)?;
+
+    if record.update_authority != metadata.update_authority {
+        return Err(MetadataError::InvalidMetadataDelegate.into());
+    }
+
+    Ok(record.role)
+}
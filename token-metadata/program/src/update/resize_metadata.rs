@@ -0,0 +1,112 @@
+use crate::{
+    deser::clean_write_metadata,
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, TokenMetadataAccount, MAX_METADATA_LEN},
+    utils::{
+        assert_owned_by, assert_signer, assert_update_authority_is_correct, puff_out_data_fields,
+        resize_or_reallocate_account_raw, shrink_account_raw, unpuff_data_fields,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ResizeArgs {
+    /// New account size. `None` fits the account to its current content; a larger size leaves
+    /// room for a following update and `MAX_METADATA_LEN` restores the legacy padded layout.
+    pub size: Option<u32>,
+}
+
+///# Resize
+///
+/// Reallocates a metadata account. Growing is funded by the payer and shrinking refunds the
+/// excess rent to it.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[writable, signer]` Payer, funds growth and receives refunded rent
+///   3. `[]` System program
+pub fn resize(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    size: Option<u32>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: MetadataInstruction::Resize(ResizeArgs { size })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_resize<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ResizeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    let compact_len = metadata.compact_len();
+    let new_len = match args.size {
+        None => compact_len,
+        Some(size) => {
+            let size = size as usize;
+            if size < compact_len || size > MAX_METADATA_LEN {
+                return Err(MetadataError::InvalidMetadataSize.into());
+            }
+            size
+        }
+    };
+
+    if new_len == MAX_METADATA_LEN {
+        puff_out_data_fields(&mut metadata);
+    } else {
+        unpuff_data_fields(&mut metadata);
+    }
+
+    let current_len = metadata_info.data_len();
+    if new_len > current_len {
+        resize_or_reallocate_account_raw(metadata_info, payer_info, system_program_info, new_len)?;
+    } else if new_len < current_len {
+        shrink_account_raw(metadata_info, payer_info, new_len)?;
+    }
+
+    clean_write_metadata(&mut metadata, metadata_info)
+}
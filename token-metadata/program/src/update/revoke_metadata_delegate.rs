@@ -0,0 +1,102 @@
+use crate::{
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, MetadataDelegateRecord, MetadataDelegateRole, TokenMetadataAccount},
+    update::{metadata_delegate_seeds, MetadataDelegateArgs},
+    utils::{assert_derivation, assert_owned_by, assert_signer, close_account_raw},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+///# Revoke Metadata Delegate
+///
+/// Closes a metadata delegate record. Either the update authority or the delegate itself
+/// can revoke, and the record rent goes back to the payer that funded it.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata delegate record
+///   1. `[]` Delegate
+///   2. `[]` Metadata account
+///   3. `[]` Mint account
+///   4. `[signer]` Update authority or delegate
+///   5. `[writable]` Payer recorded at approval, receives the record rent
+#[allow(clippy::too_many_arguments)]
+pub fn revoke_metadata_delegate(
+    program_id: Pubkey,
+    delegate_record: Pubkey,
+    delegate: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    role: MetadataDelegateRole,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(delegate_record, false),
+        AccountMeta::new_readonly(delegate, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(payer, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::RevokeMetadataDelegate(MetadataDelegateArgs { role })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_revoke_metadata_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MetadataDelegateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let delegate_record_info = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(delegate_record_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if *authority_info.key != metadata.update_authority && authority_info.key != delegate_info.key {
+        return Err(MetadataError::UpdateAuthorityIncorrect.into());
+    }
+
+    assert_derivation(
+        program_id,
+        delegate_record_info,
+        &metadata_delegate_seeds(program_id, mint_info.key, &args.role, delegate_info.key),
+    )?;
+
+    // Make sure we are closing a delegate record and not some other account.
+    let record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+    if record.payer != *payer_info.key {
+        return Err(MetadataError::RentReceiverMismatch.into());
+    }
+
+    close_account_raw(payer_info, delegate_record_info)?;
+
+    Ok(())
+}
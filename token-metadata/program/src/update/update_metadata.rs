@@ -0,0 +1,306 @@
+use crate::{
+    assertions::{
+        collection::assert_collection_update_is_valid, uses::assert_valid_use,
+        validation::assert_update_meets_validation_profile,
+    },
+    deser::realloc_and_write_metadata,
+    error::MetadataError,
+    events::{emit_update, MetadataValues},
+    instruction::MetadataInstruction,
+    state::{
+        Collection, Creator, Metadata, MetadataDelegateRole, TokenMetadataAccount, TokenStandard,
+        Uses, EDITION, PREFIX,
+    },
+    update::assert_metadata_delegate,
+    utils::{
+        assert_data_valid, assert_derivation, assert_owned_by, assert_signer, check_token_standard,
+        puff_out_data_fields,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+/// Fields left as `None` are not changed. For the nested options, `Some(None)` clears the field.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UpdateArgs {
+    V1 {
+        name: Option<String>,
+        uri: Option<String>,
+        creators: Option<Option<Vec<Creator>>>,
+        collection: Option<Option<Collection>>,
+        uses: Option<Option<Uses>>,
+        primary_sale_happened: Option<bool>,
+        token_standard: Option<TokenStandard>,
+    },
+}
+
+impl Default for UpdateArgs {
+    fn default() -> Self {
+        UpdateArgs::V1 {
+            name: None,
+            uri: None,
+            creators: None,
+            collection: None,
+            uses: None,
+            primary_sale_happened: None,
+            token_standard: None,
+        }
+    }
+}
+
+///# Update
+///
+/// Updates individual metadata fields. The update authority may change any field; a metadata
+/// delegate may only change the fields covered by its role.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority or metadata delegate
+///   2. `[]` Mint account
+///   3. `[]` Edition account (pda of ['metadata', program id, mint id, 'edition']), may be uninitialized
+///   4. Optional `[]` Metadata delegate record, when the authority is a delegate
+///   5. Optional `[writable, signer]` Payer, funds growing a resized metadata account
+///   6. Optional `[]` System program, required with the payer
+///   7. Optional `[]` Collection validation profile PDA, found by its address; required to change
+///      the name or uri of a verified collection item
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    authority: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    delegate_record: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    validation_profile: Option<Pubkey>,
+    args: UpdateArgs,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(edition, false),
+    ];
+
+    if let Some(delegate_record) = delegate_record {
+        accounts.push(AccountMeta::new_readonly(delegate_record, false));
+    }
+
+    if let Some(payer) = payer {
+        accounts.push(AccountMeta::new(payer, true));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+
+    if let Some(validation_profile) = validation_profile {
+        accounts.push(AccountMeta::new_readonly(validation_profile, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Update(args).try_to_vec().unwrap(),
+    }
+}
+
+fn assert_field_allowed(
+    delegate_role: Option<MetadataDelegateRole>,
+    is_set: bool,
+    field_role: Option<MetadataDelegateRole>,
+) -> ProgramResult {
+    match delegate_role {
+        Some(role) if is_set && field_role != Some(role) => {
+            Err(MetadataError::DelegateRoleCannotUpdateField.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn process_update<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let old_values = MetadataValues::from(&metadata);
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let delegate_role = if *authority_info.key == metadata.update_authority {
+        None
+    } else {
+        let delegate_record_info = account_info_iter
+            .next()
+            .ok_or(MetadataError::UpdateAuthorityIncorrect)?;
+        Some(assert_metadata_delegate(
+            program_id,
+            &metadata,
+            authority_info,
+            delegate_record_info,
+        )?)
+    };
+
+    let UpdateArgs::V1 {
+        name,
+        uri,
+        creators,
+        collection,
+        uses,
+        primary_sale_happened,
+        token_standard,
+    } = args;
+
+    assert_field_allowed(delegate_role, name.is_some(), None)?;
+    assert_field_allowed(
+        delegate_role,
+        uri.is_some(),
+        Some(MetadataDelegateRole::Uri),
+    )?;
+    assert_field_allowed(delegate_role, creators.is_some(), None)?;
+    assert_field_allowed(
+        delegate_role,
+        collection.is_some(),
+        Some(MetadataDelegateRole::Collection),
+    )?;
+    assert_field_allowed(
+        delegate_role,
+        uses.is_some(),
+        Some(MetadataDelegateRole::Uses),
+    )?;
+    assert_field_allowed(delegate_role, primary_sale_happened.is_some(), None)?;
+    assert_field_allowed(delegate_role, token_standard.is_some(), None)?;
+
+    let updates_data = name.is_some()
+        || uri.is_some()
+        || creators.is_some()
+        || collection.is_some()
+        || uses.is_some();
+
+    if updates_data && !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    if name.is_some() || uri.is_some() || creators.is_some() {
+        let mut data = metadata.data.clone();
+        if let Some(name) = name {
+            data.name = name;
+        }
+        if let Some(uri) = uri {
+            data.uri = uri;
+        }
+        if let Some(creators) = creators {
+            data.creators = creators;
+        }
+
+        assert_data_valid(
+            &data,
+            authority_info.key,
+            &metadata,
+            false,
+            authority_info.is_signer,
+        )?;
+        metadata.data = data;
+    }
+
+    if let Some(collection) = collection {
+        // A verified collection can only be changed through the collection instructions,
+        // which keep the collection size in sync.
+        if let Some(existing) = metadata.collection.as_ref() {
+            if existing.verified && collection.as_ref() != Some(existing) {
+                return Err(MetadataError::CannotUpdateVerifiedCollection.into());
+            }
+        }
+        assert_collection_update_is_valid(false, &metadata.collection, &collection)?;
+        metadata.collection = collection;
+    }
+
+    if let Some(uses) = uses {
+        assert_valid_use(&uses, &metadata.uses)?;
+        metadata.uses = uses;
+    }
+
+    if let Some(val) = primary_sale_happened {
+        // If received val is true, flip to true.
+        if val || !metadata.primary_sale_happened {
+            metadata.primary_sale_happened = val
+        } else {
+            return Err(MetadataError::PrimarySaleCanOnlyBeFlippedToTrue.into());
+        }
+    }
+
+    if let Some(token_standard) = token_standard {
+        // Programmable NFTs have their own migration path since their token account must be
+        // frozen at the same time.
+        if token_standard == TokenStandard::ProgrammableNonFungible
+            || metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible)
+        {
+            return Err(MetadataError::InvalidTokenStandard.into());
+        }
+
+        assert_derivation(
+            program_id,
+            edition_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                EDITION.as_bytes(),
+            ],
+        )?;
+
+        let edition = if edition_info.data_is_empty() {
+            None
+        } else {
+            assert_owned_by(edition_info, program_id)?;
+            Some(edition_info)
+        };
+
+        if check_token_standard(mint_info, edition)? != token_standard {
+            return Err(MetadataError::InvalidTokenStandard.into());
+        }
+        metadata.token_standard = Some(token_standard);
+    }
+
+    assert_update_meets_validation_profile(
+        program_id,
+        &old_values.data,
+        &metadata,
+        account_info_iter.as_slice(),
+    )?;
+
+    let payer_info = account_info_iter.next();
+    let system_program_info = account_info_iter.next();
+
+    puff_out_data_fields(&mut metadata);
+    realloc_and_write_metadata(
+        &mut metadata,
+        metadata_info,
+        payer_info,
+        system_program_info,
+    )?;
+
+    emit_update(metadata_info.key, old_values, &metadata)
+}
@@ -0,0 +1,42 @@
+pub mod recharge;
+pub mod set_use_schedule;
+
+pub use recharge::*;
+pub use set_use_schedule::*;
+
+use crate::{
+    error::MetadataError,
+    state::{TokenMetadataAccount, UseSchedule, PREFIX, USE_SCHEDULE},
+    utils::{assert_derivation, assert_owned_by},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+pub fn use_schedule_seeds<'a>(program_id: &'a Pubkey, mint: &'a Pubkey) -> Vec<&'a [u8]> {
+    vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        USE_SCHEDULE.as_bytes(),
+    ]
+}
+
+/// Loads the use schedule of `mint`, checking its derivation.
+pub fn load_use_schedule(
+    program_id: &Pubkey,
+    use_schedule_info: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<UseSchedule, ProgramError> {
+    assert_owned_by(use_schedule_info, program_id)?;
+    assert_derivation(
+        program_id,
+        use_schedule_info,
+        &use_schedule_seeds(program_id, mint),
+    )?;
+
+    let use_schedule = UseSchedule::from_account_info(use_schedule_info)?;
+    if use_schedule.mint != *mint {
+        return Err(MetadataError::InvalidUseSchedule.into());
+    }
+
+    Ok(use_schedule)
+}
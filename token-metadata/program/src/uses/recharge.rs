@@ -0,0 +1,134 @@
+use crate::{
+    assertions::uses::{
+        assert_use_authority_derivation, assert_use_authority_scope, assert_valid_bump,
+        process_use_authority_validation,
+    },
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Metadata, TokenMetadataAccount, UseAuthorityRecord, UseScheduleKind},
+    uses::load_use_schedule,
+    utils::{assert_owned_by, assert_signer},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar::{self, Sysvar},
+};
+
+///# Recharge
+///
+/// Credits the uses of every elapsed period of a recharging asset, up to its total uses. Callable
+/// by the update authority or a use authority.
+///
+///### Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[writable]` Use schedule account (pda of ['metadata', program id, mint id, 'use_schedule'])
+///   2. `[]` Mint of the metadata
+///   3. `[signer]` Update authority or use authority
+///   4. Optional `[]` Use authority record PDA, required when a use authority recharges
+///   5. Optional `[]` Instructions sysvar, required when the use authority has a caller program
+pub fn recharge(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    use_schedule: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    use_authority_record: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(use_schedule, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(authority, true),
+    ];
+
+    if let Some(use_authority_record) = use_authority_record {
+        accounts.push(AccountMeta::new_readonly(use_authority_record, false));
+        accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Recharge.try_to_vec().unwrap(),
+    }
+}
+
+pub fn process_recharge(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let use_schedule_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_signer(authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if metadata.update_authority != *authority_info.key {
+        let use_authority_record_info = next_account_info(account_info_iter)?;
+        process_use_authority_validation(use_authority_record_info.data_len(), false)?;
+        assert_owned_by(use_authority_record_info, program_id)?;
+        let canonical_bump = assert_use_authority_derivation(
+            program_id,
+            use_authority_record_info,
+            authority_info,
+            mint_info,
+        )?;
+        let record = UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+        if !record.bump_empty() {
+            assert_valid_bump(canonical_bump, &record)?;
+        }
+        assert_use_authority_scope(&record, account_info_iter.next())?;
+    }
+
+    let mut use_schedule = load_use_schedule(program_id, use_schedule_info, &metadata.mint)?;
+    let mut uses = match metadata.uses {
+        Some(uses) if uses.use_method == use_schedule.use_method() => uses,
+        Some(_) => return Err(MetadataError::InvalidUseSchedule.into()),
+        None => return Err(MetadataError::Unusable.into()),
+    };
+
+    let (period, amount, last_recharge) = match &mut use_schedule.kind {
+        UseScheduleKind::Recharging {
+            period,
+            amount,
+            last_recharge,
+        } => (*period, *amount, last_recharge),
+        UseScheduleKind::Expiring { .. } => return Err(MetadataError::InvalidUseSchedule.into()),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let periods = now.saturating_sub(*last_recharge) / period;
+    if periods <= 0 {
+        return Err(MetadataError::RechargeNotDue.into());
+    }
+
+    // Periods spent at the cap are not carried over.
+    uses.remaining = amount
+        .saturating_mul(periods as u64)
+        .saturating_add(uses.remaining)
+        .min(uses.total);
+    *last_recharge = last_recharge
+        .checked_add(periods * period)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+    metadata.uses = Some(uses);
+    metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    BorshSerialize::serialize(
+        &use_schedule,
+        &mut *use_schedule_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
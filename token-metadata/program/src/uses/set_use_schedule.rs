@@ -0,0 +1,145 @@
+use crate::{
+    error::MetadataError,
+    instruction::MetadataInstruction,
+    state::{Key, Metadata, TokenMetadataAccount, UseSchedule, UseScheduleKind, USE_SCHEDULE_SIZE},
+    uses::use_schedule_seeds,
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_update_authority_is_correct,
+        create_or_allocate_account_raw,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetUseScheduleArgs {
+    pub kind: UseScheduleKind,
+}
+
+///# Set Use Schedule
+///
+/// Creates or replaces the use schedule of an asset. The schedule must match the `Recharging` or
+/// `Expiring` use method of the asset. The `last_recharge` of a recharging schedule is ignored,
+/// the first period starts when the schedule is set.
+///
+///### Accounts:
+///
+///   0. `[writable]` Use schedule account (pda of ['metadata', program id, mint id, 'use_schedule'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[writable, signer]` Payer
+///   4. `[]` System program
+pub fn set_use_schedule(
+    program_id: Pubkey,
+    use_schedule: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    kind: UseScheduleKind,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(use_schedule, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetUseSchedule(SetUseScheduleArgs { kind })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn process_set_use_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetUseScheduleArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let use_schedule_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(MetadataError::InvalidSystemProgram.into());
+    }
+
+    assert_signer(payer_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    let mut use_schedule = UseSchedule {
+        key: Key::UseSchedule,
+        mint: metadata.mint,
+        bump: 0,
+        kind: args.kind,
+    };
+
+    match metadata.uses {
+        Some(uses) if uses.use_method == use_schedule.use_method() => (),
+        _ => return Err(MetadataError::InvalidUseSchedule.into()),
+    }
+
+    if let UseScheduleKind::Recharging {
+        period,
+        amount,
+        last_recharge,
+    } = &mut use_schedule.kind
+    {
+        if *period <= 0 || *amount == 0 {
+            return Err(MetadataError::InvalidUseSchedule.into());
+        }
+        *last_recharge = Clock::get()?.unix_timestamp;
+    }
+
+    let use_schedule_path = use_schedule_seeds(program_id, &metadata.mint);
+    use_schedule.bump = assert_derivation(program_id, use_schedule_info, &use_schedule_path)?;
+
+    if use_schedule_info.data_is_empty() {
+        let bump_seed = &[use_schedule.bump];
+        let mut use_schedule_seeds = use_schedule_path.clone();
+        use_schedule_seeds.push(bump_seed);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            use_schedule_info,
+            system_program_info,
+            payer_info,
+            USE_SCHEDULE_SIZE,
+            &use_schedule_seeds,
+        )?;
+    } else {
+        assert_owned_by(use_schedule_info, program_id)?;
+        // Make sure the account really is a use schedule before overwriting it.
+        UseSchedule::from_account_info(use_schedule_info)?;
+    }
+
+    BorshSerialize::serialize(
+        &use_schedule,
+        &mut *use_schedule_info.try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
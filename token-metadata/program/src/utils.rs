@@ -2,13 +2,15 @@ use crate::{
     assertions::{collection::assert_collection_update_is_valid, uses::assert_valid_use},
     deser::clean_write_metadata,
     error::MetadataError,
+    events::{emit_event, MetadataEventV1},
     pda::find_master_edition_account,
     state::{
-        get_reservation_list, CollectionDetails, Creator, Data, DataV2, Edition, EditionMarker,
-        Key, MasterEditionV1, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard, Uses,
-        EDITION, EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN,
-        MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH,
-        MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+        get_reservation_list, CollectionDetails, CollectionRoyalties, Creator, Data, DataV2,
+        Edition, EditionMarker, Key, MasterEditionV1, MasterEditionV2, Metadata,
+        MetadataAttributes, PrintTemplate, TokenMetadataAccount, TokenStandard, Uses, ATTRIBUTES,
+        COLLECTION_ROYALTIES, EDITION, EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN,
+        MAX_EDITION_MARKER_SIZE, MAX_EDITION_URI_LENGTH, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
+        MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
     },
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
@@ -116,6 +118,12 @@ pub fn assert_data_valid(
                         return Err(MetadataError::CannotVerifyAnotherCreator.into());
                     } else if !creator.verified && existing_creators_map[address].verified {
                         return Err(MetadataError::CannotUnverifyAnotherCreator.into());
+                    } else if creator.verified
+                        && creator.share != existing_creators_map[address].share
+                    {
+                        // Verified royalty splits need the consent of their creators, which
+                        // is given through a creators proposal.
+                        return Err(MetadataError::CannotChangeVerifiedCreatorShare.into());
                     }
                 } else if creator.verified {
                     // If this specific creator is not in the existing creator's array, then we
@@ -149,6 +157,22 @@ pub fn assert_data_valid(
                 }
             }
         }
+    } else if !allow_direct_creator_writes {
+        // Clearing the creators would drop verified creators without their consent, the same as
+        // removing them from the array.
+        let wipes_verified_creator =
+            existing_metadata
+                .data
+                .creators
+                .iter()
+                .flatten()
+                .any(|creator| {
+                    creator.verified
+                        && !(update_authority_is_signer && creator.address == *update_authority)
+                });
+        if wipes_verified_creator {
+            return Err(MetadataError::CannotWipeVerifiedCreators.into());
+        }
     }
 
     Ok(())
@@ -240,6 +264,28 @@ pub fn resize_or_reallocate_account_raw<'a>(
     Ok(())
 }
 
+/// Shrink a program-owned account and move the rent it no longer needs to `receiver_account`.
+pub fn shrink_account_raw<'a>(
+    target_account: &AccountInfo<'a>,
+    receiver_account: &AccountInfo<'a>,
+    new_size: usize,
+) -> ProgramResult {
+    target_account.realloc(new_size, false)?;
+
+    let rent = Rent::get()?;
+    let new_minimum_balance = rent.minimum_balance(new_size);
+
+    let target_lamports = target_account.lamports();
+    let refund = target_lamports.saturating_sub(new_minimum_balance);
+    **target_account.try_borrow_mut_lamports()? = target_lamports - refund;
+    **receiver_account.try_borrow_mut_lamports()? = receiver_account
+        .lamports()
+        .checked_add(refund)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+    Ok(())
+}
+
 /// Close src_account and transfer lamports to dst_account, lifted from Solana Cookbook
 #[inline(always)]
 pub fn close_account_raw<'a>(
@@ -544,6 +590,18 @@ pub fn calculate_edition_number(
     Ok(edition)
 }
 
+/// Returns the print template of a `MasterEditionV2`, or `None` for any other master edition.
+pub fn get_print_template_off_master_edition(
+    master_edition_account_info: &AccountInfo,
+) -> Result<Option<PrintTemplate>, ProgramError> {
+    if master_edition_account_info.try_borrow_data()?[0] != Key::MasterEditionV2 as u8 {
+        return Ok(None);
+    }
+
+    let master_edition = MasterEditionV2::from_account_info(master_edition_account_info)?;
+    Ok(master_edition.print_template)
+}
+
 fn get_max_supply_off_master_edition(
     master_edition_account_info: &AccountInfo,
 ) -> Result<Option<u64>, ProgramError> {
@@ -689,12 +747,39 @@ pub fn mint_limited_edition<'a>(
     if mint_supply != 1 {
         return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
     }
+
+    let edition_number = calculate_edition_number(
+        mint_authority_info,
+        reservation_list_info,
+        edition_override,
+        me_supply,
+    )?;
+
+    let master_mint = master_metadata.mint;
     let master_data = master_metadata.data;
+    let (name, uri, uri_override) =
+        match get_print_template_off_master_edition(master_edition_account_info)? {
+            Some(print_template) => {
+                let uri_override = print_template.uri(edition_number);
+                if let Some(uri) = &uri_override {
+                    if uri.len() > MAX_EDITION_URI_LENGTH {
+                        return Err(MetadataError::UriTooLong.into());
+                    }
+                }
+                (
+                    print_template.name(&master_data.name, edition_number),
+                    uri_override.clone().unwrap_or(master_data.uri),
+                    uri_override,
+                )
+            }
+            None => (master_data.name, master_data.uri, None),
+        };
+
     // bundle data into v2
     let data_v2 = DataV2 {
-        name: master_data.name,
+        name,
         symbol: master_data.symbol,
-        uri: master_data.uri,
+        uri,
         seller_fee_basis_points: master_data.seller_fee_basis_points,
         creators: master_data.creators,
         collection: master_metadata.collection,
@@ -745,18 +830,14 @@ pub fn mint_limited_edition<'a>(
     let edition_data = &mut new_edition_account_info.data.borrow_mut();
     let output = array_mut_ref![edition_data, 0, MAX_EDITION_LEN];
 
-    let (key, parent, edition, _padding) = mut_array_refs![output, 1, 32, 8, 200];
+    let (key, parent, edition, uri_override_data) = mut_array_refs![output, 1, 32, 8, 200];
 
     *key = [Key::EditionV1 as u8];
     parent.copy_from_slice(master_edition_account_info.key.as_ref());
 
-    *edition = calculate_edition_number(
-        mint_authority_info,
-        reservation_list_info,
-        edition_override,
-        me_supply,
-    )?
-    .to_le_bytes();
+    *edition = edition_number.to_le_bytes();
+
+    uri_override.serialize(&mut uri_override_data.as_mut())?;
 
     // Now make sure this mint can never be used by anybody else.
     transfer_mint_authority(
@@ -767,7 +848,13 @@ pub fn mint_limited_edition<'a>(
         token_program_account_info,
     )?;
 
-    Ok(())
+    emit_event(MetadataEventV1::MintEdition {
+        master_mint,
+        edition_mint: *mint_info.key,
+        edition: edition_number,
+        old_supply: me_supply,
+        new_supply: get_supply_off_master_edition(master_edition_account_info)?,
+    })
 }
 
 pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
@@ -1116,7 +1203,11 @@ pub fn process_create_metadata_accounts_logic(
     metadata.edition_nonce = Some(edition_bump_seed);
     metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
 
-    Ok(())
+    emit_event(MetadataEventV1::Create {
+        metadata: *metadata_account_info.key,
+        mint: metadata.mint,
+        values: (&metadata).into(),
+    })
 }
 
 /// Strings need to be appended with `\0`s in order to have a deterministic length.
@@ -1128,6 +1219,18 @@ pub fn puff_out_data_fields(metadata: &mut Metadata) {
     metadata.data.uri = puffed_out_string(&metadata.data.uri, MAX_URI_LENGTH);
 }
 
+/// Strips the `0u8` padding added by `puff_out_data_fields`.
+pub fn unpuff_data_fields(metadata: &mut Metadata) {
+    metadata.data.name = unpuffed_string(&metadata.data.name);
+    metadata.data.symbol = unpuffed_string(&metadata.data.symbol);
+    metadata.data.uri = unpuffed_string(&metadata.data.uri);
+}
+
+/// Removes trailing `0u8`s from the string.
+pub fn unpuffed_string(s: &str) -> String {
+    s.trim_end_matches(char::from(0)).to_owned()
+}
+
 /// Pads the string to the desired size with `0u8`s.
 /// NOTE: it is assumed that the string's size is never larger than the given size.
 pub fn puffed_out_string(s: &str, size: usize) -> String {
@@ -1482,3 +1585,76 @@ pub fn assert_edition_is_not_mint_authority(mint_account_info: &AccountInfo) ->
 
     Ok(())
 }
+
+/// Reads the on-chain attribute `key` of `mint` from its attributes account. Returns `None`
+/// when the asset has no attributes account or no entry for `key`.
+pub fn get_attribute(
+    attributes_info: &AccountInfo,
+    mint: &Pubkey,
+    key: &str,
+) -> Result<Option<String>, ProgramError> {
+    assert_derivation(
+        &crate::id(),
+        attributes_info,
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            ATTRIBUTES.as_bytes(),
+        ],
+    )?;
+
+    if attributes_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    assert_owned_by(attributes_info, &crate::id())?;
+    let attributes = MetadataAttributes::from_account_info(attributes_info)?;
+
+    Ok(attributes.get(key).map(|value| value.to_owned()))
+}
+
+/// Resolves the seller fee basis points and creators a marketplace should pay for an item.
+///
+/// Verified items of a collection that don't declare creators of their own inherit the default
+/// royalties of the collection, when `collection_royalties_info` is the collection royalties
+/// account. Otherwise the royalties of the item itself are returned.
+pub fn resolve_royalties(
+    metadata: &Metadata,
+    collection_royalties_info: Option<&AccountInfo>,
+) -> Result<(u16, Option<Vec<Creator>>), ProgramError> {
+    let own_royalties = (
+        metadata.data.seller_fee_basis_points,
+        metadata.data.creators.clone(),
+    );
+
+    if metadata.data.creators.is_some() {
+        return Ok(own_royalties);
+    }
+
+    let (collection, collection_royalties_info) =
+        match (&metadata.collection, collection_royalties_info) {
+            (Some(collection), Some(info)) if collection.verified => (collection, info),
+            _ => return Ok(own_royalties),
+        };
+
+    assert_derivation(
+        &crate::id(),
+        collection_royalties_info,
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            collection.key.as_ref(),
+            COLLECTION_ROYALTIES.as_bytes(),
+        ],
+    )?;
+
+    if collection_royalties_info.data_is_empty() {
+        return Ok(own_royalties);
+    }
+
+    assert_owned_by(collection_royalties_info, &crate::id())?;
+    let royalties = CollectionRoyalties::from_account_info(collection_royalties_info)?;
+
+    Ok((royalties.seller_fee_basis_points, Some(royalties.creators)))
+}
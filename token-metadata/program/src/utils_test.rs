@@ -46,6 +46,7 @@ mod puff_out_test {
             uses: None,
             token_standard: None,
             collection_details: None,
+            rule_set: None,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -86,3 +87,183 @@ mod try_from_slice_checked {
         assert_eq!(metadata, expected_metadata);
     }
 }
+
+mod get_attribute {
+    use borsh::BorshSerialize;
+    use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+    use crate::{
+        pda::find_attributes_account,
+        state::{Key, MetadataAttributes},
+        utils::get_attribute,
+        ID,
+    };
+
+    #[test]
+    fn reads_attribute_from_derived_account() {
+        let mint = Pubkey::new_unique();
+        let (pubkey, bump) = find_attributes_account(&mint);
+
+        let mut attributes = MetadataAttributes {
+            key: Key::MetadataAttributes,
+            mint,
+            bump,
+            attributes: vec![],
+        };
+        attributes.set("level".to_string(), "7".to_string());
+
+        let mut lamports = 1_000_000_000;
+        let mut data = attributes.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &ID,
+            false,
+            1_000_000_000,
+        );
+
+        assert_eq!(
+            get_attribute(&account_info, &mint, "level").unwrap(),
+            Some("7".to_string())
+        );
+        assert_eq!(get_attribute(&account_info, &mint, "rarity").unwrap(), None);
+        assert!(get_attribute(&account_info, &Pubkey::new_unique(), "level").is_err());
+    }
+}
+
+mod resolve_royalties {
+    use borsh::BorshSerialize;
+    use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+    use crate::{
+        pda::find_collection_royalties_account,
+        state::{Collection, CollectionRoyalties, Creator, Data, Key, Metadata},
+        utils::resolve_royalties,
+        ID,
+    };
+
+    fn collection_item(collection_mint: Pubkey, creators: Option<Vec<Creator>>) -> Metadata {
+        Metadata {
+            data: Data {
+                seller_fee_basis_points: 100,
+                creators,
+                ..Data::default()
+            },
+            collection: Some(Collection {
+                key: collection_mint,
+                verified: true,
+            }),
+            ..Metadata::default()
+        }
+    }
+
+    #[test]
+    fn inherits_collection_royalties() {
+        let collection_mint = Pubkey::new_unique();
+        let (pubkey, bump) = find_collection_royalties_account(&collection_mint);
+        let collection_creators = vec![Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 100,
+        }];
+
+        let royalties = CollectionRoyalties {
+            key: Key::CollectionRoyalties,
+            mint: collection_mint,
+            bump,
+            seller_fee_basis_points: 500,
+            creators: collection_creators.clone(),
+        };
+
+        let mut lamports = 1_000_000_000;
+        let mut data = royalties.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &ID,
+            false,
+            1_000_000_000,
+        );
+
+        let item = collection_item(collection_mint, None);
+        assert_eq!(
+            resolve_royalties(&item, Some(&account_info)).unwrap(),
+            (500, Some(collection_creators))
+        );
+        assert_eq!(resolve_royalties(&item, None).unwrap(), (100, None));
+
+        // Items declaring their own creators keep their own royalties.
+        let item_creators = vec![Creator {
+            address: Pubkey::new_unique(),
+            verified: false,
+            share: 100,
+        }];
+        let item = collection_item(collection_mint, Some(item_creators.clone()));
+        assert_eq!(
+            resolve_royalties(&item, Some(&account_info)).unwrap(),
+            (100, Some(item_creators))
+        );
+
+        // Unverified items don't inherit anything.
+        let mut item = collection_item(collection_mint, None);
+        item.collection.as_mut().unwrap().verified = false;
+        assert_eq!(
+            resolve_royalties(&item, Some(&account_info)).unwrap(),
+            (100, None)
+        );
+
+        // The account must be derived from the collection mint of the item.
+        let item = collection_item(Pubkey::new_unique(), None);
+        assert!(resolve_royalties(&item, Some(&account_info)).is_err());
+    }
+}
+
+mod assert_data_valid {
+    use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+    use crate::{
+        error::MetadataError,
+        state::{Creator, Data, Metadata},
+        utils::assert_data_valid,
+    };
+
+    fn metadata_with_creator(address: Pubkey, verified: bool) -> Metadata {
+        Metadata {
+            data: Data {
+                creators: Some(vec![Creator {
+                    address,
+                    verified,
+                    share: 100,
+                }]),
+                ..Data::default()
+            },
+            ..Metadata::default()
+        }
+    }
+
+    #[test]
+    fn wiping_verified_creators_is_rejected() {
+        let update_authority = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let wiped = Data::default();
+
+        let existing = metadata_with_creator(creator, true);
+        assert_eq!(
+            assert_data_valid(&wiped, &update_authority, &existing, false, true).unwrap_err(),
+            ProgramError::from(MetadataError::CannotWipeVerifiedCreators)
+        );
+        assert!(assert_data_valid(&wiped, &update_authority, &existing, true, true).is_ok());
+
+        // Unverified creators and the update authority itself can be dropped.
+        let existing = metadata_with_creator(creator, false);
+        assert!(assert_data_valid(&wiped, &update_authority, &existing, false, true).is_ok());
+        let existing = metadata_with_creator(update_authority, true);
+        assert!(assert_data_valid(&wiped, &update_authority, &existing, false, true).is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    attributes::{remove_attribute, set_attribute},
+    error::MetadataError,
+    pda::find_attributes_account,
+    state::{MetadataAttributes, MetadataDelegateRole, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+mod attributes {
+    use super::*;
+
+    async fn send_set(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        authority: &Keypair,
+        delegate_record: Option<Pubkey>,
+        key: &str,
+        value: &str,
+    ) -> Result<(), BanksClientError> {
+        let (attributes, _) = find_attributes_account(&test_metadata.mint.pubkey());
+        let ix = set_attribute(
+            mpl_token_metadata::id(),
+            attributes,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            authority.pubkey(),
+            context.payer.pubkey(),
+            delegate_record,
+            key.to_string(),
+            value.to_string(),
+        );
+
+        process_instruction(context, ix, &[authority]).await
+    }
+
+    async fn send_remove(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        receiver: &Pubkey,
+        key: &str,
+    ) -> Result<(), BanksClientError> {
+        let (attributes, _) = find_attributes_account(&test_metadata.mint.pubkey());
+        let ix = remove_attribute(
+            mpl_token_metadata::id(),
+            attributes,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            *receiver,
+            None,
+            key.to_string(),
+        );
+
+        process_instruction(context, ix, &[]).await
+    }
+
+    async fn get_attributes(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+    ) -> MetadataAttributes {
+        let (attributes, _) = find_attributes_account(&test_metadata.mint.pubkey());
+        let account = get_account(context, &attributes).await;
+        MetadataAttributes::safe_deserialize(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_authority_sets_attributes() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        send_set(&mut context, &test_metadata, &payer, None, "level", "7")
+            .await
+            .unwrap();
+        send_set(&mut context, &test_metadata, &payer, None, "rarity", "rare")
+            .await
+            .unwrap();
+
+        let attributes = get_attributes(&mut context, &test_metadata).await;
+        assert_eq!(attributes.mint, test_metadata.mint.pubkey());
+        assert_eq!(attributes.get("level"), Some("7"));
+        assert_eq!(attributes.get("rarity"), Some("rare"));
+    }
+
+    #[tokio::test]
+    async fn attributes_delegate_sets_attributes() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+
+        let record = test_metadata
+            .approve_metadata_delegate(
+                &mut context,
+                &delegate.pubkey(),
+                MetadataDelegateRole::Attributes,
+            )
+            .await
+            .unwrap();
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &delegate,
+            Some(record),
+            "power",
+            "9000",
+        )
+        .await
+        .unwrap();
+
+        let attributes = get_attributes(&mut context, &test_metadata).await;
+        assert_eq!(attributes.get("power"), Some("9000"));
+    }
+
+    #[tokio::test]
+    async fn fail_to_set_attribute_without_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let fake_authority = Keypair::new();
+
+        let err = send_set(
+            &mut context,
+            &test_metadata,
+            &fake_authority,
+            None,
+            "level",
+            "99",
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+
+    #[tokio::test]
+    async fn removing_last_attribute_closes_account() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        send_set(&mut context, &test_metadata, &payer, None, "level", "7")
+            .await
+            .unwrap();
+        let receiver = context.payer.pubkey();
+        send_remove(&mut context, &test_metadata, &receiver, "level")
+            .await
+            .unwrap();
+
+        let (attributes, _) = find_attributes_account(&test_metadata.mint.pubkey());
+        let account = context.banks_client.get_account(attributes).await.unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_to_remove_missing_attribute() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        send_set(&mut context, &test_metadata, &payer, None, "level", "7")
+            .await
+            .unwrap();
+        let receiver = context.payer.pubkey();
+        let err = send_remove(&mut context, &test_metadata, &receiver, "rarity")
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AttributeNotFound);
+    }
+
+    #[tokio::test]
+    async fn fail_to_refund_attributes_rent_to_other_receiver() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        send_set(&mut context, &test_metadata, &payer, None, "level", "7")
+            .await
+            .unwrap();
+        let err = send_remove(
+            &mut context,
+            &test_metadata,
+            &Keypair::new().pubkey(),
+            "level",
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RentReceiverMismatch);
+    }
+}
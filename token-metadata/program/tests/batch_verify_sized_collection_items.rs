@@ -0,0 +1,234 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    collection::{batch_unverify_sized_collection_items, batch_verify_sized_collection_items},
+    error::MetadataError,
+    state::{Collection, CollectionDetails},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod batch_verify_sized_collection_items {
+    use super::*;
+
+    async fn create_collection(
+        context: &mut ProgramTestContext,
+        collection_details: Option<CollectionDetails>,
+    ) -> (Metadata, MasterEditionV2) {
+        let test_collection = Metadata::new();
+        test_collection
+            .create_v3(
+                context,
+                "Collection".to_string(),
+                "COL".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                collection_details,
+            )
+            .await
+            .unwrap();
+
+        let collection_master_edition = MasterEditionV2::new(&test_collection);
+        collection_master_edition
+            .create_v3(context, Some(0))
+            .await
+            .unwrap();
+
+        (test_collection, collection_master_edition)
+    }
+
+    async fn create_items(
+        context: &mut ProgramTestContext,
+        collection_mint: Pubkey,
+        count: usize,
+    ) -> Vec<Metadata> {
+        let mut items = vec![];
+        for _ in 0..count {
+            let item = Metadata::new();
+            item.create_v3(
+                context,
+                "Item".to_string(),
+                "ITM".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                Some(Collection {
+                    key: collection_mint,
+                    verified: false,
+                }),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            items.push(item);
+        }
+
+        items
+    }
+
+    async fn send_batch(
+        context: &mut ProgramTestContext,
+        test_collection: &Metadata,
+        collection_master_edition: &MasterEditionV2,
+        items: &[&Metadata],
+        verify: bool,
+    ) -> Result<(), BanksClientError> {
+        let builder = if verify {
+            batch_verify_sized_collection_items
+        } else {
+            batch_unverify_sized_collection_items
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[builder(
+                mpl_token_metadata::id(),
+                context.payer.pubkey(),
+                test_collection.mint.pubkey(),
+                test_collection.pubkey,
+                collection_master_edition.pubkey,
+                items.iter().map(|item| item.pubkey).collect(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn collection_size(context: &mut ProgramTestContext, test_collection: &Metadata) -> u64 {
+        match test_collection.get_data(context).await.collection_details {
+            Some(CollectionDetails::V1 { size }) => size,
+            None => panic!("Expected CollectionDetails::V1"),
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_and_unverify_many_items() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) =
+            create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let items = create_items(&mut context, test_collection.mint.pubkey(), 3).await;
+        let item_refs: Vec<&Metadata> = items.iter().collect();
+
+        send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &item_refs,
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(collection_size(&mut context, &test_collection).await, 3);
+        for item in &items {
+            let metadata = item.get_data(&mut context).await;
+            assert!(metadata.collection.unwrap().verified);
+        }
+
+        send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &item_refs[..2],
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(collection_size(&mut context, &test_collection).await, 1);
+        let first = items[0].get_data(&mut context).await;
+        assert!(!first.collection.unwrap().verified);
+        let last = items[2].get_data(&mut context).await;
+        assert!(last.collection.unwrap().verified);
+    }
+
+    #[tokio::test]
+    async fn fail_when_one_item_is_already_verified() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) =
+            create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let items = create_items(&mut context, test_collection.mint.pubkey(), 2).await;
+
+        send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &[&items[0]],
+            true,
+        )
+        .await
+        .unwrap();
+
+        let err = send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &[&items[1], &items[0]],
+            true,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AlreadyVerified);
+        // The whole batch is rolled back.
+        assert_eq!(collection_size(&mut context, &test_collection).await, 1);
+        let second = items[1].get_data(&mut context).await;
+        assert!(!second.collection.unwrap().verified);
+    }
+
+    #[tokio::test]
+    async fn fail_when_item_belongs_to_another_collection() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) =
+            create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let items = create_items(&mut context, test_collection.mint.pubkey(), 1).await;
+        let strays = create_items(&mut context, Pubkey::new_unique(), 1).await;
+
+        let err = send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &[&items[0], &strays[0]],
+            true,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionNotFound);
+    }
+
+    #[tokio::test]
+    async fn fail_for_unsized_collection() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, collection_master_edition) =
+            create_collection(&mut context, None).await;
+        let items = create_items(&mut context, test_collection.mint.pubkey(), 1).await;
+
+        let err = send_batch(
+            &mut context,
+            &test_collection,
+            &collection_master_edition,
+            &[&items[0]],
+            true,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UnsizedCollection);
+    }
+}
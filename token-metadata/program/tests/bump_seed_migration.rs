@@ -10,7 +10,7 @@ use mpl_token_metadata::{
 use borsh::BorshSerialize;
 use mpl_token_metadata::pda::{find_program_as_burner_account, find_use_authority_account};
 use mpl_token_metadata::state::Key as MetadataKey;
-use mpl_token_metadata::state::UseAuthorityRecord;
+use mpl_token_metadata::state::{UseAuthorityRecord, USE_AUTHORITY_RECORD_SIZE};
 use solana_program::borsh::try_from_slice_unchecked;
 use solana_program_test::*;
 use solana_sdk::account::WritableAccount;
@@ -60,6 +60,9 @@ mod bump_seed_migration {
             key: MetadataKey::UseAuthorityRecord,
             allowed_uses: 10,
             bump: 0,
+            expiry: None,
+            caller_program: None,
+            payer: None,
         };
         let mut account = Account {
             lamports: 1113600,
@@ -70,7 +73,7 @@ mod bump_seed_migration {
         };
         let data_mut = account.data_mut();
         use_record_struct.serialize(data_mut).unwrap();
-        data_mut.append(&mut vec![0, 0, 0, 0, 0, 0, 0, 0]);
+        data_mut.resize(USE_AUTHORITY_RECORD_SIZE, 0);
         let shared_data = &AccountSharedData::from(account);
         context.set_account(&record, shared_data);
         airdrop(&mut context, &use_authority_account.pubkey(), 1113600)
@@ -0,0 +1,524 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    burn::{burn, BurnDelegateRecord},
+    error::MetadataError,
+    instruction::{approve_collection_authority, approve_use_authority},
+    pda::{
+        find_collection_authority_account, find_program_as_burner_account,
+        find_use_authority_account,
+    },
+    state::{Collection, CollectionDetails, UseMethod, Uses},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_token::instruction::AuthorityType;
+use utils::*;
+
+mod burn_asset {
+    use super::*;
+
+    async fn send_burn(
+        context: &mut ProgramTestContext,
+        asset: &Metadata,
+        collection_metadata: Option<Pubkey>,
+        update_authority: Option<Pubkey>,
+        delegate_records: Vec<BurnDelegateRecord>,
+        amount: u64,
+        force: bool,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[burn(
+                mpl_token_metadata::id(),
+                asset.pubkey,
+                context.payer.pubkey(),
+                asset.mint.pubkey(),
+                asset.token.pubkey(),
+                None,
+                collection_metadata,
+                update_authority,
+                delegate_records,
+                amount,
+                force,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn account_exists(context: &mut ProgramTestContext, pubkey: Pubkey) -> bool {
+        context
+            .banks_client
+            .get_account(pubkey)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    async fn create_verified_item(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        parent_edition: &MasterEditionV2,
+    ) -> Metadata {
+        let item = Metadata::new();
+        item.create_v3(
+            context,
+            "Item".to_string(),
+            "ITM".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let item_edition = MasterEditionV2::new(&item);
+        item_edition.create_v3(context, Some(0)).await.unwrap();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        item.verify_sized_collection_item(
+            context,
+            parent.pubkey,
+            &payer,
+            parent.mint.pubkey(),
+            parent_edition.pubkey,
+            None,
+        )
+        .await
+        .unwrap();
+
+        item
+    }
+
+    async fn create_fungible(context: &mut ProgramTestContext) -> Metadata {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                0,
+                false,
+                9,
+            )
+            .await
+            .unwrap();
+        let payer_pubkey = context.payer.pubkey();
+        mint_tokens(
+            context,
+            &test_metadata.mint.pubkey(),
+            &test_metadata.token.pubkey(),
+            9,
+            &payer_pubkey,
+            None,
+        )
+        .await
+        .unwrap();
+
+        test_metadata
+    }
+
+    async fn approve_delegate(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        delegate: &Keypair,
+        record_payer: &Keypair,
+    ) -> Pubkey {
+        let (record, _) =
+            find_collection_authority_account(&parent.mint.pubkey(), &delegate.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_collection_authority(
+                mpl_token_metadata::id(),
+                record,
+                delegate.pubkey(),
+                context.payer.pubkey(),
+                record_payer.pubkey(),
+                parent.pubkey,
+                parent.mint.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, record_payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        record
+    }
+
+    async fn remove_mint_authority(context: &mut ProgramTestContext, mint: &Pubkey) {
+        let tx = Transaction::new_signed_with_payer(
+            &[spl_token::instruction::set_authority(
+                &spl_token::id(),
+                mint,
+                None,
+                AuthorityType::MintTokens,
+                &context.payer.pubkey(),
+                &[],
+            )
+            .unwrap()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn burning_last_fungible_tokens_closes_metadata() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_fungible(&mut context).await;
+        let update_authority = Some(context.payer.pubkey());
+
+        send_burn(
+            &mut context,
+            &test_metadata,
+            None,
+            update_authority,
+            vec![],
+            4,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+        assert_eq!(mint.supply, 6);
+        assert!(account_exists(&mut context, test_metadata.pubkey).await);
+
+        remove_mint_authority(&mut context, &test_metadata.mint.pubkey()).await;
+        send_burn(
+            &mut context,
+            &test_metadata,
+            None,
+            update_authority,
+            vec![],
+            6,
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!account_exists(&mut context, test_metadata.pubkey).await);
+    }
+
+    #[tokio::test]
+    async fn burning_last_fungible_tokens_keeps_metadata_of_mintable_asset() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_fungible(&mut context).await;
+        let update_authority = Some(context.payer.pubkey());
+
+        send_burn(
+            &mut context,
+            &test_metadata,
+            None,
+            update_authority,
+            vec![],
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+        assert_eq!(mint.supply, 0);
+        assert!(account_exists(&mut context, test_metadata.pubkey).await);
+    }
+
+    #[tokio::test]
+    async fn fail_to_burn_fungible_with_other_rent_receiver() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_fungible(&mut context).await;
+
+        let err = send_burn(
+            &mut context,
+            &test_metadata,
+            None,
+            Some(Keypair::new().pubkey()),
+            vec![],
+            10,
+            false,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::RentReceiverMismatch);
+    }
+
+    #[tokio::test]
+    async fn fail_to_burn_collection_parent_with_items_unless_forced() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        create_verified_item(&mut context, &parent, &parent_edition).await;
+
+        let err = send_burn(&mut context, &parent, None, None, vec![], 1, false)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CollectionNotEmpty);
+
+        send_burn(&mut context, &parent, None, None, vec![], 1, true)
+            .await
+            .unwrap();
+
+        assert!(!account_exists(&mut context, parent.pubkey).await);
+        assert!(!account_exists(&mut context, parent_edition.pubkey).await);
+    }
+
+    #[tokio::test]
+    async fn burning_items_empties_collection_parent() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let item = create_verified_item(&mut context, &parent, &parent_edition).await;
+
+        send_burn(
+            &mut context,
+            &item,
+            Some(parent.pubkey),
+            None,
+            vec![],
+            1,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let parent_data = parent.get_data(&mut context).await;
+        assert_eq!(
+            parent_data.collection_details,
+            Some(CollectionDetails::V1 { size: 0 })
+        );
+
+        send_burn(&mut context, &parent, None, None, vec![], 1, false)
+            .await
+            .unwrap();
+        assert!(!account_exists(&mut context, parent.pubkey).await);
+    }
+
+    #[tokio::test]
+    async fn burning_closes_collection_authority_records() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, _) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record_payer = Keypair::new();
+        airdrop(&mut context, &record_payer.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let record = approve_delegate(&mut context, &parent, &delegate, &record_payer).await;
+        let record_rent = get_account(&mut context, &record).await.lamports;
+        let payer_lamports = get_account(&mut context, &record_payer.pubkey())
+            .await
+            .lamports;
+
+        send_burn(
+            &mut context,
+            &parent,
+            None,
+            None,
+            vec![BurnDelegateRecord {
+                record,
+                delegate: delegate.pubkey(),
+                payer: record_payer.pubkey(),
+            }],
+            1,
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!account_exists(&mut context, record).await);
+        assert_eq!(
+            get_account(&mut context, &record_payer.pubkey())
+                .await
+                .lamports,
+            payer_lamports + record_rent
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_to_refund_record_to_another_account() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, _) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record_payer = Keypair::new();
+        airdrop(&mut context, &record_payer.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let record = approve_delegate(&mut context, &parent, &delegate, &record_payer).await;
+
+        let payer_pubkey = context.payer.pubkey();
+        let err = send_burn(
+            &mut context,
+            &parent,
+            None,
+            None,
+            vec![BurnDelegateRecord {
+                record,
+                delegate: delegate.pubkey(),
+                payer: payer_pubkey,
+            }],
+            1,
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RentReceiverMismatch);
+        assert!(account_exists(&mut context, record).await);
+    }
+
+    #[tokio::test]
+    async fn burning_as_second_holder_keeps_use_authority_record() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                0,
+                false,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    total: 10,
+                    remaining: 10,
+                }),
+            )
+            .await
+            .unwrap();
+
+        // The first holder approves a use authority for their token.
+        let use_authority = Keypair::new();
+        let (record, _) =
+            find_use_authority_account(&test_metadata.mint.pubkey(), &use_authority.pubkey());
+        let (burner, _) = find_program_as_burner_account();
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_use_authority(
+                mpl_token_metadata::id(),
+                record,
+                use_authority.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.token.pubkey(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                burner,
+                1,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // A second holder burns their own token and passes the first holder's record.
+        let holder = Keypair::new();
+        let holder_token = Keypair::new();
+        create_token_account(
+            &mut context,
+            &holder_token,
+            &test_metadata.mint.pubkey(),
+            &holder.pubkey(),
+        )
+        .await
+        .unwrap();
+        let payer_pubkey = context.payer.pubkey();
+        mint_tokens(
+            &mut context,
+            &test_metadata.mint.pubkey(),
+            &holder_token.pubkey(),
+            1,
+            &payer_pubkey,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn(
+                mpl_token_metadata::id(),
+                test_metadata.pubkey,
+                holder.pubkey(),
+                test_metadata.mint.pubkey(),
+                holder_token.pubkey(),
+                None,
+                None,
+                None,
+                vec![BurnDelegateRecord {
+                    record,
+                    delegate: use_authority.pubkey(),
+                    payer: context.payer.pubkey(),
+                }],
+                1,
+                false,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &holder],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(account_exists(&mut context, test_metadata.pubkey).await);
+        assert!(account_exists(&mut context, record).await);
+    }
+
+    #[tokio::test]
+    async fn fail_to_close_record_of_another_delegate() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, _) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let record = approve_delegate(&mut context, &parent, &delegate, &payer).await;
+
+        let payer_pubkey = context.payer.pubkey();
+        let err = send_burn(
+            &mut context,
+            &parent,
+            None,
+            None,
+            vec![BurnDelegateRecord {
+                record,
+                delegate: Keypair::new().pubkey(),
+                payer: payer_pubkey,
+            }],
+            1,
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidCollectionAuthorityRecord);
+    }
+}
@@ -0,0 +1,239 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    collection::{set_collection_creators, verify_collection_item_as_creator},
+    error::MetadataError,
+    instruction::sign_metadata,
+    pda::find_collection_creators_account,
+    state::{Collection, CollectionCreators, CollectionDetails, Creator, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collection_creators {
+    use super::*;
+
+    fn shared_creators(context: &ProgramTestContext, creator: &Keypair) -> Vec<Creator> {
+        vec![
+            Creator {
+                address: context.payer.pubkey(),
+                verified: true,
+                share: 50,
+            },
+            Creator {
+                address: creator.pubkey(),
+                verified: false,
+                share: 50,
+            },
+        ]
+    }
+
+    async fn sign(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        creator: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[sign_metadata(
+                mpl_token_metadata::id(),
+                metadata.pubkey,
+                creator.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, creator],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn create_parent(
+        context: &mut ProgramTestContext,
+        creator: &Keypair,
+    ) -> (Metadata, MasterEditionV2) {
+        let parent = Metadata::new();
+        parent
+            .create_v3(
+                context,
+                "Collection".to_string(),
+                "COL".to_string(),
+                "uri".to_string(),
+                Some(shared_creators(context, creator)),
+                10,
+                false,
+                None,
+                None,
+                Some(CollectionDetails::V1 { size: 0 }),
+            )
+            .await
+            .unwrap();
+        let parent_edition = MasterEditionV2::new(&parent);
+        parent_edition.create_v3(context, Some(0)).await.unwrap();
+
+        (parent, parent_edition)
+    }
+
+    async fn create_item(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        creator: &Keypair,
+    ) -> Metadata {
+        let item = Metadata::new();
+        item.create_v3(
+            context,
+            "Item".to_string(),
+            "ITM".to_string(),
+            "uri".to_string(),
+            Some(shared_creators(context, creator)),
+            10,
+            false,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        sign(context, &item, creator).await.unwrap();
+
+        item
+    }
+
+    async fn set_creators(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        creators: Vec<Pubkey>,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (collection_creators, _) = find_collection_creators_account(&parent.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[set_collection_creators(
+                mpl_token_metadata::id(),
+                collection_creators,
+                parent.pubkey,
+                parent.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                creators,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(collection_creators)
+    }
+
+    async fn verify_as_creator(
+        context: &mut ProgramTestContext,
+        item: &Metadata,
+        parent: &Metadata,
+        parent_edition: &MasterEditionV2,
+        collection_creators: Pubkey,
+        creator: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_collection_item_as_creator(
+                mpl_token_metadata::id(),
+                item.pubkey,
+                creator.pubkey(),
+                parent.mint.pubkey(),
+                parent.pubkey,
+                parent_edition.pubkey,
+                collection_creators,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, creator],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn listed_creator_verifies_sized_item() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        let (parent, parent_edition) = create_parent(&mut context, &creator).await;
+        sign(&mut context, &parent, &creator).await.unwrap();
+
+        let collection_creators = set_creators(&mut context, &parent, vec![creator.pubkey()])
+            .await
+            .unwrap();
+        let account = get_account(&mut context, &collection_creators).await;
+        let creators_data = CollectionCreators::safe_deserialize(&account.data).unwrap();
+        assert_eq!(creators_data.mint, parent.mint.pubkey());
+        assert_eq!(creators_data.creators, vec![creator.pubkey()]);
+
+        let item = create_item(&mut context, &parent, &creator).await;
+        verify_as_creator(
+            &mut context,
+            &item,
+            &parent,
+            &parent_edition,
+            collection_creators,
+            &creator,
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            item.get_data(&mut context)
+                .await
+                .collection
+                .unwrap()
+                .verified
+        );
+        let parent_data = parent.get_data(&mut context).await;
+        assert_eq!(
+            parent_data.collection_details,
+            Some(CollectionDetails::V1 { size: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn unlisted_creator_cannot_verify() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        let (parent, parent_edition) = create_parent(&mut context, &creator).await;
+        sign(&mut context, &parent, &creator).await.unwrap();
+
+        let payer = context.payer.pubkey();
+        let collection_creators = set_creators(&mut context, &parent, vec![payer])
+            .await
+            .unwrap();
+
+        let item = create_item(&mut context, &parent, &creator).await;
+        let err = verify_as_creator(
+            &mut context,
+            &item,
+            &parent,
+            &parent_edition,
+            collection_creators,
+            &creator,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::NotACollectionCreator);
+    }
+
+    #[tokio::test]
+    async fn unverified_creator_cannot_be_listed() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        let (parent, _) = create_parent(&mut context, &creator).await;
+
+        let err = set_creators(&mut context, &parent, vec![creator.pubkey()])
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidCollectionCreators);
+    }
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    collection::{remove_collection_royalties, set_collection_royalties},
+    error::MetadataError,
+    pda::find_collection_royalties_account,
+    state::{CollectionDetails, CollectionRoyalties, Creator, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collection_royalties {
+    use super::*;
+
+    async fn create_collection(
+        context: &mut ProgramTestContext,
+        collection_details: Option<CollectionDetails>,
+    ) -> Metadata {
+        let test_collection = Metadata::new();
+        test_collection
+            .create_v3(
+                context,
+                "Collection".to_string(),
+                "COL".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+                collection_details,
+            )
+            .await
+            .unwrap();
+
+        test_collection
+    }
+
+    async fn send_set(
+        context: &mut ProgramTestContext,
+        test_collection: &Metadata,
+        authority: &Keypair,
+        seller_fee_basis_points: u16,
+        creators: Vec<Creator>,
+    ) -> Result<(), BanksClientError> {
+        let (collection_royalties, _) =
+            find_collection_royalties_account(&test_collection.mint.pubkey());
+        let signers: Vec<&Keypair> = if authority.pubkey() == context.payer.pubkey() {
+            vec![&context.payer]
+        } else {
+            vec![&context.payer, authority]
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[set_collection_royalties(
+                mpl_token_metadata::id(),
+                collection_royalties,
+                test_collection.pubkey,
+                test_collection.mint.pubkey(),
+                authority.pubkey(),
+                context.payer.pubkey(),
+                seller_fee_basis_points,
+                creators,
+            )],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_royalties(
+        context: &mut ProgramTestContext,
+        test_collection: &Metadata,
+    ) -> CollectionRoyalties {
+        let (collection_royalties, _) =
+            find_collection_royalties_account(&test_collection.mint.pubkey());
+        let account = get_account(context, &collection_royalties).await;
+        CollectionRoyalties::safe_deserialize(&account.data).unwrap()
+    }
+
+    fn creator(address: Pubkey, verified: bool, share: u8) -> Creator {
+        Creator {
+            address,
+            verified,
+            share,
+        }
+    }
+
+    #[tokio::test]
+    async fn set_update_and_remove_collection_royalties() {
+        let mut context = program_test().start_with_context().await;
+        let test_collection = create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let creators = vec![creator(payer.pubkey(), true, 100)];
+        send_set(
+            &mut context,
+            &test_collection,
+            &payer,
+            500,
+            creators.clone(),
+        )
+        .await
+        .unwrap();
+
+        let royalties = get_royalties(&mut context, &test_collection).await;
+        assert_eq!(royalties.mint, test_collection.mint.pubkey());
+        assert_eq!(royalties.seller_fee_basis_points, 500);
+        assert_eq!(royalties.creators, creators);
+
+        // Growing the creators list reallocates the account.
+        let creators = vec![
+            creator(payer.pubkey(), true, 60),
+            creator(Pubkey::new_unique(), false, 40),
+        ];
+        send_set(
+            &mut context,
+            &test_collection,
+            &payer,
+            250,
+            creators.clone(),
+        )
+        .await
+        .unwrap();
+
+        let royalties = get_royalties(&mut context, &test_collection).await;
+        assert_eq!(royalties.seller_fee_basis_points, 250);
+        assert_eq!(royalties.creators, creators);
+
+        let (collection_royalties, _) =
+            find_collection_royalties_account(&test_collection.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[remove_collection_royalties(
+                mpl_token_metadata::id(),
+                collection_royalties,
+                test_collection.pubkey,
+                test_collection.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = context
+            .banks_client
+            .get_account(collection_royalties)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_for_unsized_collection() {
+        let mut context = program_test().start_with_context().await;
+        let test_collection = create_collection(&mut context, None).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let creators = vec![creator(payer.pubkey(), true, 100)];
+        let err = send_set(&mut context, &test_collection, &payer, 500, creators)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::NotACollectionParent);
+    }
+
+    #[tokio::test]
+    async fn fail_without_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let test_collection = create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let fake_authority = Keypair::new();
+
+        let creators = vec![creator(fake_authority.pubkey(), true, 100)];
+        let err = send_set(
+            &mut context,
+            &test_collection,
+            &fake_authority,
+            500,
+            creators,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+
+    #[tokio::test]
+    async fn fail_to_verify_another_creator() {
+        let mut context = program_test().start_with_context().await;
+        let test_collection = create_collection(&mut context, DEFAULT_COLLECTION_DETAILS).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let creators = vec![creator(Pubkey::new_unique(), true, 100)];
+        let err = send_set(&mut context, &test_collection, &payer, 500, creators)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CannotVerifyAnotherCreator);
+    }
+}
@@ -0,0 +1,369 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    collection::{
+        batch_verify_sized_collection_items, remove_collection_validation_profile,
+        set_collection_validation_profile,
+    },
+    error::MetadataError,
+    pda::{find_collection_validation_profile_account, find_master_edition_account},
+    state::{
+        Collection, CollectionValidationProfile, TokenMetadataAccount, VALIDATION_RULES_ALL,
+        VALIDATION_RULE_URI_SCHEME,
+    },
+    update::{update, UpdateArgs},
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collection_validation_profile {
+    use super::*;
+
+    async fn set_profile(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        rules: u8,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[set_collection_validation_profile(
+                mpl_token_metadata::id(),
+                find_collection_validation_profile_account(&parent.mint.pubkey()).0,
+                parent.pubkey,
+                parent.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                rules,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn create_item(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        name: &str,
+        uri: &str,
+    ) -> Result<Metadata, BanksClientError> {
+        let item = Metadata::new();
+        item.create_v3(
+            context,
+            name.to_string(),
+            "ITM".to_string(),
+            uri.to_string(),
+            None,
+            10,
+            true,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+            None,
+        )
+        .await?;
+
+        Ok(item)
+    }
+
+    async fn verify(
+        context: &mut ProgramTestContext,
+        item: &Metadata,
+        parent: &Metadata,
+        parent_edition: &MasterEditionV2,
+    ) -> Result<(), BanksClientError> {
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        item.verify_sized_collection_item(
+            context,
+            parent.pubkey,
+            &payer,
+            parent.mint.pubkey(),
+            parent_edition.pubkey,
+            None,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn fail_to_verify_items_breaking_the_profile() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        // "Item" spelled with a cyrillic "е".
+        let item = create_item(&mut context, &parent, "It\u{0435}m", "https://example.com")
+            .await
+            .unwrap();
+
+        set_profile(&mut context, &parent, VALIDATION_RULES_ALL)
+            .await
+            .unwrap();
+
+        let profile_account = get_account(
+            &mut context,
+            &find_collection_validation_profile_account(&parent.mint.pubkey()).0,
+        )
+        .await;
+        let profile = CollectionValidationProfile::safe_deserialize(&profile_account.data).unwrap();
+        assert_eq!(profile.rules, VALIDATION_RULES_ALL);
+
+        let err = verify(&mut context, &item, &parent, &parent_edition)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::NameCharsetNotAllowed);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[remove_collection_validation_profile(
+                mpl_token_metadata::id(),
+                find_collection_validation_profile_account(&parent.mint.pubkey()).0,
+                parent.pubkey,
+                parent.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        verify(&mut context, &item, &parent, &parent_edition)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_to_create_items_breaking_the_profile() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        set_profile(&mut context, &parent, VALIDATION_RULE_URI_SCHEME)
+            .await
+            .unwrap();
+
+        let err = create_item(&mut context, &parent, "Item", "http://example.com")
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UriSchemeNotAllowed);
+
+        let item = create_item(&mut context, &parent, "Item", "ipfs://item")
+            .await
+            .unwrap();
+        verify(&mut context, &item, &parent, &parent_edition)
+            .await
+            .unwrap();
+    }
+
+    async fn rename(
+        context: &mut ProgramTestContext,
+        item: &Metadata,
+        parent: &Metadata,
+        name: &str,
+        pass_profile: bool,
+    ) -> Result<(), BanksClientError> {
+        let validation_profile = if pass_profile {
+            Some(find_collection_validation_profile_account(&parent.mint.pubkey()).0)
+        } else {
+            None
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[update(
+                mpl_token_metadata::id(),
+                item.pubkey,
+                context.payer.pubkey(),
+                item.mint.pubkey(),
+                find_master_edition_account(&item.mint.pubkey()).0,
+                None,
+                None,
+                validation_profile,
+                UpdateArgs::V1 {
+                    name: Some(name.to_string()),
+                    uri: None,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                    primary_sale_happened: None,
+                    token_standard: None,
+                },
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn fail_to_batch_verify_items_breaking_the_profile() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        let item = create_item(&mut context, &parent, "It\u{0435}m", "https://example.com")
+            .await
+            .unwrap();
+
+        set_profile(&mut context, &parent, VALIDATION_RULES_ALL)
+            .await
+            .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[batch_verify_sized_collection_items(
+                mpl_token_metadata::id(),
+                context.payer.pubkey(),
+                parent.mint.pubkey(),
+                parent.pubkey,
+                parent_edition.pubkey,
+                vec![item.pubkey],
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::NameCharsetNotAllowed);
+    }
+
+    #[tokio::test]
+    async fn items_created_without_the_profile_are_checked_on_verification() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        set_profile(&mut context, &parent, VALIDATION_RULES_ALL)
+            .await
+            .unwrap();
+
+        let item = Metadata::new();
+        let mut ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
+            mpl_token_metadata::id(),
+            item.pubkey,
+            item.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            "It\u{0435}m".to_string(),
+            "ITM".to_string(),
+            "https://example.com".to_string(),
+            None,
+            10,
+            false,
+            true,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+            None,
+        );
+        // Callers from before validation profiles don't pass it.
+        ix.accounts.pop();
+
+        let payer = context.payer.pubkey();
+        create_mint(&mut context, &item.mint, &payer, Some(&payer), 0)
+            .await
+            .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Verifying can't skip the profile either.
+        let mut ix = mpl_token_metadata::instruction::verify_sized_collection_item(
+            mpl_token_metadata::id(),
+            item.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            parent.mint.pubkey(),
+            parent.pubkey,
+            parent_edition.pubkey,
+            None,
+        );
+        ix.accounts.pop();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::MissingValidationProfile);
+
+        let err = verify(&mut context, &item, &parent, &parent_edition)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::NameCharsetNotAllowed);
+    }
+
+    #[tokio::test]
+    async fn fail_to_rename_verified_items_breaking_the_profile() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        let item = create_item(&mut context, &parent, "Item", "https://example.com")
+            .await
+            .unwrap();
+        verify(&mut context, &item, &parent, &parent_edition)
+            .await
+            .unwrap();
+
+        set_profile(&mut context, &parent, VALIDATION_RULES_ALL)
+            .await
+            .unwrap();
+
+        let err = rename(&mut context, &item, &parent, "Item 2", false)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::MissingValidationProfile);
+
+        let err = rename(&mut context, &item, &parent, "It\u{0435}m", true)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::NameCharsetNotAllowed);
+
+        rename(&mut context, &item, &parent, "Item 2", true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_with_unknown_rules() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, _) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        let err = set_profile(&mut context, &parent, 1 << 7)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidValidationRules);
+    }
+}
@@ -0,0 +1,248 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    creators::{apply_creators_proposal, approve_creators_proposal, propose_creators},
+    error::MetadataError,
+    instruction::sign_metadata,
+    pda::find_creators_proposal_account,
+    state::{Creator, CreatorsProposal, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod creators_proposal {
+    use super::*;
+
+    fn split(context: &ProgramTestContext, creator: &Keypair, shares: (u8, u8)) -> Vec<Creator> {
+        vec![
+            Creator {
+                address: context.payer.pubkey(),
+                verified: true,
+                share: shares.0,
+            },
+            Creator {
+                address: creator.pubkey(),
+                verified: true,
+                share: shares.1,
+            },
+        ]
+    }
+
+    async fn create_with_verified_creators(
+        context: &mut ProgramTestContext,
+        creator: &Keypair,
+    ) -> Metadata {
+        let mut creators = split(context, creator, (50, 50));
+        creators[1].verified = false;
+
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                Some(creators),
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[sign_metadata(
+                mpl_token_metadata::id(),
+                test_metadata.pubkey,
+                creator.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, creator],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        test_metadata
+    }
+
+    async fn propose(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        creators: Vec<Creator>,
+    ) -> Pubkey {
+        let (creators_proposal, _) = find_creators_proposal_account(&test_metadata.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[propose_creators(
+                mpl_token_metadata::id(),
+                creators_proposal,
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                creators,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        creators_proposal
+    }
+
+    async fn approve(
+        context: &mut ProgramTestContext,
+        creators_proposal: Pubkey,
+        test_metadata: &Metadata,
+        approver: &Keypair,
+        creators: Vec<Creator>,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_creators_proposal(
+                mpl_token_metadata::id(),
+                creators_proposal,
+                test_metadata.pubkey,
+                approver.pubkey(),
+                creators,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, approver],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn apply(
+        context: &mut ProgramTestContext,
+        creators_proposal: Pubkey,
+        test_metadata: &Metadata,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[apply_creators_proposal(
+                mpl_token_metadata::id(),
+                creators_proposal,
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn verified_shares_change_once_every_creator_approved() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        let test_metadata = create_with_verified_creators(&mut context, &creator).await;
+        let new_creators = split(&context, &creator, (70, 30));
+
+        let err = test_metadata
+            .update_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                Some(new_creators.clone()),
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CannotChangeVerifiedCreatorShare);
+
+        let creators_proposal = propose(&mut context, &test_metadata, new_creators.clone()).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        approve(
+            &mut context,
+            creators_proposal,
+            &test_metadata,
+            &payer,
+            new_creators.clone(),
+        )
+        .await
+        .unwrap();
+
+        let err = apply(&mut context, creators_proposal, &test_metadata)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CreatorsProposalNotApproved);
+
+        approve(
+            &mut context,
+            creators_proposal,
+            &test_metadata,
+            &creator,
+            new_creators.clone(),
+        )
+        .await
+        .unwrap();
+        let account = get_account(&mut context, &creators_proposal).await;
+        let proposal_data = CreatorsProposal::safe_deserialize(&account.data).unwrap();
+        assert_eq!(
+            proposal_data.approvals,
+            vec![context.payer.pubkey(), creator.pubkey()]
+        );
+
+        // New blockhash so the retried apply is not deduplicated.
+        context.warp_to_slot(100).unwrap();
+        apply(&mut context, creators_proposal, &test_metadata)
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.data.creators, Some(new_creators));
+        assert!(context
+            .banks_client
+            .get_account(creators_proposal)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_to_approve_other_creators() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        let test_metadata = create_with_verified_creators(&mut context, &creator).await;
+        let new_creators = split(&context, &creator, (70, 30));
+        let creators_proposal = propose(&mut context, &test_metadata, new_creators.clone()).await;
+
+        // The approved creators must be the proposed ones.
+        let other_creators = split(&context, &creator, (10, 90));
+        let err = approve(
+            &mut context,
+            creators_proposal,
+            &test_metadata,
+            &creator,
+            other_creators,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::CreatorsProposalMismatch);
+
+        let outsider = Keypair::new();
+        let err = approve(
+            &mut context,
+            creators_proposal,
+            &test_metadata,
+            &outsider,
+            new_creators,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidProposalApprover);
+    }
+}
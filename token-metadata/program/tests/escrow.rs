@@ -174,6 +174,8 @@ mod escrow {
             parent_test_metadata.mint.pubkey(),
             parent_test_metadata.token.pubkey(),
             None,
+            None,
+            vec![],
             1,
         );
         println!("{:?} {:?}", &context.payer, &attribute_test_metadata.token);
@@ -0,0 +1,161 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use borsh::BorshDeserialize;
+use mpl_token_metadata::{
+    events::{MetadataEvent, MetadataEventV1},
+    id, instruction,
+    state::{Collection, DataV2},
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use utils::*;
+
+mod events {
+    use super::*;
+
+    async fn simulate_events(
+        context: &mut ProgramTestContext,
+        tx: Transaction,
+    ) -> Vec<MetadataEventV1> {
+        let details = context
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .unwrap()
+            .simulation_details
+            .unwrap();
+
+        details
+            .logs
+            .iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .map(|data| {
+                let MetadataEvent::V1(event) =
+                    MetadataEvent::try_from_slice(&base64::decode(data).unwrap()).unwrap();
+                event
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn update_logs_old_and_new_values() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::update_metadata_accounts_v2(
+                id(),
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                None,
+                Some(DataV2 {
+                    name: "Renamed".to_string(),
+                    symbol: "TST".to_string(),
+                    uri: "uri".to_string(),
+                    creators: None,
+                    seller_fee_basis_points: 10,
+                    collection: None,
+                    uses: None,
+                }),
+                None,
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let events = simulate_events(&mut context, tx).await;
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            MetadataEventV1::Update {
+                metadata,
+                mint,
+                old,
+                new,
+            } => {
+                assert_eq!(*metadata, test_metadata.pubkey);
+                assert_eq!(*mint, test_metadata.mint.pubkey());
+                assert_eq!(old.data.name, "Test");
+                assert_eq!(new.data.name, "Renamed");
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_sized_collection_item_logs_collection_size() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        let item = Metadata::new();
+        item.create_v2(
+            &mut context,
+            "Item".to_string(),
+            "ITM".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::verify_sized_collection_item(
+                id(),
+                item.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                parent.mint.pubkey(),
+                parent.pubkey,
+                parent_edition.pubkey,
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let events = simulate_events(&mut context, tx).await;
+        assert_eq!(
+            events,
+            vec![MetadataEventV1::CollectionVerification {
+                metadata: item.pubkey,
+                mint: item.mint.pubkey(),
+                old_collection: Some(Collection {
+                    key: parent.mint.pubkey(),
+                    verified: false,
+                }),
+                new_collection: Some(Collection {
+                    key: parent.mint.pubkey(),
+                    verified: true,
+                }),
+                old_collection_size: Some(0),
+                new_collection_size: Some(1),
+            }]
+        );
+    }
+}
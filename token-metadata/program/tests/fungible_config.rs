@@ -0,0 +1,263 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    fungible::{create_fungible_config, mint_fungible, update_fungible_config},
+    pda::find_fungible_config_account,
+    state::{FungibleConfig, Key, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod fungible_config {
+    use super::*;
+
+    async fn create_config(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        max_supply: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (fungible_config, _) = find_fungible_config_account(&test_metadata.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[create_fungible_config(
+                mpl_token_metadata::id(),
+                fungible_config,
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                max_supply,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(fungible_config)
+    }
+
+    async fn mint(
+        context: &mut ProgramTestContext,
+        fungible_config: Pubkey,
+        test_metadata: &Metadata,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mint_fungible(
+                mpl_token_metadata::id(),
+                fungible_config,
+                test_metadata.mint.pubkey(),
+                test_metadata.token.pubkey(),
+                context.payer.pubkey(),
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn update_config(
+        context: &mut ProgramTestContext,
+        fungible_config: Pubkey,
+        test_metadata: &Metadata,
+        update_authority: &Keypair,
+        new_mint_authority: Option<Pubkey>,
+        max_supply: Option<u64>,
+    ) -> Result<(), BanksClientError> {
+        let ix = update_fungible_config(
+            mpl_token_metadata::id(),
+            fungible_config,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            update_authority.pubkey(),
+            new_mint_authority,
+            max_supply,
+        );
+        process_instruction(context, ix, &[update_authority]).await
+    }
+
+    #[tokio::test]
+    async fn mints_up_to_the_max_supply() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata.create_v3_default(&mut context).await.unwrap();
+
+        let fungible_config = create_config(&mut context, &test_metadata, 10)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &fungible_config).await;
+        let config_data = FungibleConfig::safe_deserialize(&account.data).unwrap();
+        assert_eq!(config_data.key, Key::FungibleConfig);
+        assert_eq!(config_data.mint_authority, context.payer.pubkey());
+        assert_eq!(config_data.max_supply, 10);
+        assert_eq!(config_data.decimals, 0);
+
+        let mint_account = get_account(&mut context, &test_metadata.mint.pubkey()).await;
+        let mint_data = spl_token::state::Mint::unpack(&mint_account.data).unwrap();
+        assert_eq!(mint_data.mint_authority, Some(fungible_config).into());
+
+        mint(&mut context, fungible_config, &test_metadata, 9)
+            .await
+            .unwrap();
+        let err = mint(&mut context, fungible_config, &test_metadata, 1)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::SupplyCapExceeded);
+    }
+
+    #[tokio::test]
+    async fn fail_with_max_supply_below_supply() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata.create_v3_default(&mut context).await.unwrap();
+
+        let err = create_config(&mut context, &test_metadata, 0)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::MaxSupplyBelowCurrentSupply);
+    }
+
+    #[tokio::test]
+    async fn fail_for_non_fungible() {
+        let mut context = program_test().start_with_context().await;
+        let (nft, _) = Metadata::create_default_nft(&mut context).await.unwrap();
+
+        let err = create_config(&mut context, &nft, 10).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+    }
+
+    #[tokio::test]
+    async fn update_authority_rotates_mint_authority() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata.create_v3_default(&mut context).await.unwrap();
+        let fungible_config = create_config(&mut context, &test_metadata, 10)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let new_mint_authority = Keypair::new();
+
+        update_config(
+            &mut context,
+            fungible_config,
+            &test_metadata,
+            &payer,
+            Some(new_mint_authority.pubkey()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(&mut context, &fungible_config).await;
+        let config_data = FungibleConfig::safe_deserialize(&account.data).unwrap();
+        assert_eq!(config_data.mint_authority, new_mint_authority.pubkey());
+        assert_eq!(config_data.max_supply, 10);
+
+        let err = mint(&mut context, fungible_config, &test_metadata, 1)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidMintAuthority);
+
+        let ix = mint_fungible(
+            mpl_token_metadata::id(),
+            fungible_config,
+            test_metadata.mint.pubkey(),
+            test_metadata.token.pubkey(),
+            new_mint_authority.pubkey(),
+            1,
+        );
+        process_instruction(&mut context, ix, &[&new_mint_authority])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_authority_lowers_max_supply() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata.create_v3_default(&mut context).await.unwrap();
+        let fungible_config = create_config(&mut context, &test_metadata, 10)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let err = update_config(
+            &mut context,
+            fungible_config,
+            &test_metadata,
+            &payer,
+            None,
+            Some(11),
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::MaxSupplyCannotBeRaised);
+
+        let err = update_config(
+            &mut context,
+            fungible_config,
+            &test_metadata,
+            &payer,
+            None,
+            Some(0),
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::MaxSupplyBelowCurrentSupply);
+
+        update_config(
+            &mut context,
+            fungible_config,
+            &test_metadata,
+            &payer,
+            None,
+            Some(2),
+        )
+        .await
+        .unwrap();
+
+        mint(&mut context, fungible_config, &test_metadata, 1)
+            .await
+            .unwrap();
+        let err = mint(&mut context, fungible_config, &test_metadata, 1)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::SupplyCapExceeded);
+    }
+
+    #[tokio::test]
+    async fn fail_to_update_without_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata.create_v3_default(&mut context).await.unwrap();
+        let fungible_config = create_config(&mut context, &test_metadata, 10)
+            .await
+            .unwrap();
+        let fake_authority = Keypair::new();
+
+        let err = update_config(
+            &mut context,
+            fungible_config,
+            &test_metadata,
+            &fake_authority,
+            Some(fake_authority.pubkey()),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
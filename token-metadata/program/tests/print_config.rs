@@ -0,0 +1,246 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    edition::{print_config_accounts, remove_print_config, set_print_config},
+    error::MetadataError,
+    pda::{find_print_config_account, find_print_record_account},
+    state::{PrintConfig, PrintPrice, PrintRecord, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod print_config {
+    use super::*;
+
+    async fn create_master(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        test_metadata.create_v2_default(context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&test_metadata);
+        master_edition.create_v3(context, None).await.unwrap();
+
+        (test_metadata, master_edition)
+    }
+
+    async fn send_set(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        master_edition: &MasterEditionV2,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        max_per_wallet: Option<u64>,
+        price: Option<PrintPrice>,
+    ) -> Result<(), BanksClientError> {
+        let (print_config, _) = find_print_config_account(&test_metadata.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[set_print_config(
+                mpl_token_metadata::id(),
+                print_config,
+                master_edition.pubkey,
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                start_time,
+                end_time,
+                max_per_wallet,
+                price,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn print(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        master_edition: &MasterEditionV2,
+        edition: u64,
+        payment_destination: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let print_edition = EditionMarker::new(test_metadata, master_edition, edition);
+        let accounts = print_config_accounts(
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            payment_destination,
+            None,
+        );
+        print_edition
+            .create_with_extra_accounts(context, accounts)
+            .await
+    }
+
+    #[tokio::test]
+    async fn print_within_wallet_limit() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            None,
+            None,
+            Some(1),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let (print_config, _) = find_print_config_account(&test_metadata.mint.pubkey());
+        let master = master_edition.get_data(&mut context).await;
+        assert_eq!(master.print_config, Some(print_config));
+
+        print(&mut context, &test_metadata, &master_edition, 1, None)
+            .await
+            .unwrap();
+
+        let (print_record, _) =
+            find_print_record_account(&test_metadata.mint.pubkey(), &context.payer.pubkey());
+        let account = get_account(&mut context, &print_record).await;
+        let record = PrintRecord::safe_deserialize(&account.data).unwrap();
+        assert_eq!(record.count, 1);
+
+        let err = print(&mut context, &test_metadata, &master_edition, 2, None)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::PrintLimitReached);
+    }
+
+    #[tokio::test]
+    async fn fail_outside_print_window() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            Some(i64::MAX),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let err = print(&mut context, &test_metadata, &master_edition, 1, None)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::PrintingNotStarted);
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            None,
+            Some(1),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let err = print(&mut context, &test_metadata, &master_edition, 2, None)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::PrintingEnded);
+    }
+
+    #[tokio::test]
+    async fn print_pays_sol_price() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+        let destination = Pubkey::new_unique();
+        let price = 1_000_000_000;
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            None,
+            None,
+            None,
+            Some(PrintPrice {
+                amount: price,
+                mint: None,
+                destination,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let err = print(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            1,
+            Some(Pubkey::new_unique()),
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::PaymentDestinationMismatch);
+
+        print(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            2,
+            Some(destination),
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(&mut context, &destination).await;
+        assert_eq!(account.lamports, price);
+    }
+
+    #[tokio::test]
+    async fn removed_print_config_is_not_enforced() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            Some(i64::MAX),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let (print_config, _) = find_print_config_account(&test_metadata.mint.pubkey());
+        let account = get_account(&mut context, &print_config).await;
+        let config = PrintConfig::safe_deserialize(&account.data).unwrap();
+        assert_eq!(config.start_time, Some(i64::MAX));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[remove_print_config(
+                mpl_token_metadata::id(),
+                print_config,
+                master_edition.pubkey,
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let print_edition = EditionMarker::new(&test_metadata, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+    }
+}
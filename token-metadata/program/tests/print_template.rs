@@ -0,0 +1,204 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    edition::set_print_template,
+    error::MetadataError,
+    state::{Edition as PrintEdition, PrintTemplate, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod print_template {
+    use super::*;
+
+    async fn create_master(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Art".to_string(),
+                "ART".to_string(),
+                "https://example.com/art.json".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let master_edition = MasterEditionV2::new(&test_metadata);
+        master_edition.create_v3(context, Some(10)).await.unwrap();
+
+        (test_metadata, master_edition)
+    }
+
+    async fn send_set(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        master_edition: &MasterEditionV2,
+        update_authority: &Keypair,
+        print_template: Option<PrintTemplate>,
+    ) -> Result<(), BanksClientError> {
+        let signers: Vec<&Keypair> = if update_authority.pubkey() == context.payer.pubkey() {
+            vec![&context.payer]
+        } else {
+            vec![&context.payer, update_authority]
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[set_print_template(
+                mpl_token_metadata::id(),
+                master_edition.pubkey,
+                test_metadata.pubkey,
+                update_authority.pubkey(),
+                print_template,
+            )],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_print(
+        context: &mut ProgramTestContext,
+        print_edition: &EditionMarker,
+    ) -> (mpl_token_metadata::state::Metadata, PrintEdition) {
+        let account = get_account(context, &print_edition.new_metadata_pubkey).await;
+        let metadata =
+            mpl_token_metadata::state::Metadata::safe_deserialize(&account.data).unwrap();
+        let account = get_account(context, &print_edition.new_edition_pubkey).await;
+        let edition = PrintEdition::safe_deserialize(&account.data).unwrap();
+
+        (metadata, edition)
+    }
+
+    #[tokio::test]
+    async fn prints_are_numbered_from_template() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let print_template = PrintTemplate {
+            append_edition_number: true,
+            uri_template: Some("https://example.com/art/{edition}.json".to_string()),
+        };
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            &payer,
+            Some(print_template.clone()),
+        )
+        .await
+        .unwrap();
+
+        let master = master_edition.get_data(&mut context).await;
+        assert_eq!(master.print_template, Some(print_template));
+
+        for edition in 1..=2 {
+            let print_edition = EditionMarker::new(&test_metadata, &master_edition, edition);
+            print_edition.create(&mut context).await.unwrap();
+
+            let (metadata, print) = get_print(&mut context, &print_edition).await;
+            let uri = format!("https://example.com/art/{}.json", edition);
+            assert_eq!(
+                metadata.data.name.trim_end_matches(char::from(0)),
+                format!("Art #{}", edition)
+            );
+            assert_eq!(metadata.data.uri.trim_end_matches(char::from(0)), uri);
+            assert_eq!(print.edition, edition);
+            assert_eq!(print.uri_override, Some(uri));
+        }
+    }
+
+    #[tokio::test]
+    async fn prints_copy_master_without_template() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let print_template = PrintTemplate {
+            append_edition_number: true,
+            uri_template: None,
+        };
+        send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            &payer,
+            Some(print_template),
+        )
+        .await
+        .unwrap();
+        send_set(&mut context, &test_metadata, &master_edition, &payer, None)
+            .await
+            .unwrap();
+
+        let print_edition = EditionMarker::new(&test_metadata, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        let (metadata, print) = get_print(&mut context, &print_edition).await;
+        assert_eq!(metadata.data.name.trim_end_matches(char::from(0)), "Art");
+        assert_eq!(
+            metadata.data.uri.trim_end_matches(char::from(0)),
+            "https://example.com/art.json"
+        );
+        assert_eq!(print.uri_override, None);
+    }
+
+    #[tokio::test]
+    async fn fail_with_template_missing_placeholder() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let print_template = PrintTemplate {
+            append_edition_number: false,
+            uri_template: Some("https://example.com/art.json".to_string()),
+        };
+        let err = send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            &payer,
+            Some(print_template),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidPrintTemplate);
+    }
+
+    #[tokio::test]
+    async fn fail_without_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, master_edition) = create_master(&mut context).await;
+        let fake_authority = Keypair::new();
+
+        let print_template = PrintTemplate {
+            append_edition_number: true,
+            uri_template: None,
+        };
+        let err = send_set(
+            &mut context,
+            &test_metadata,
+            &master_edition,
+            &fake_authority,
+            Some(print_template),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -0,0 +1,369 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    programmable::{migrate_to_programmable, transfer},
+    state::{Creator, Metadata as ProgramMetadata, TokenMetadataAccount, TokenStandard},
+    ID as PROGRAM_ID,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account as TokenAccount;
+use utils::*;
+
+mod programmable {
+    use super::*;
+
+    async fn migrate(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+    ) -> Result<(), BanksClientError> {
+        let ix = migrate_to_programmable(
+            PROGRAM_ID,
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+            test_metadata.token.pubkey(),
+            context.payer.pubkey(),
+        );
+
+        process_instruction(context, ix, &[]).await
+    }
+
+    async fn transfer_to(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        destination_owner: &Pubkey,
+        creators: Vec<Pubkey>,
+        sale_price: Option<u64>,
+    ) -> Result<(), BanksClientError> {
+        let destination =
+            get_associated_token_address(destination_owner, &test_metadata.mint.pubkey());
+
+        let ix = transfer(
+            PROGRAM_ID,
+            test_metadata.token.pubkey(),
+            context.payer.pubkey(),
+            destination,
+            *destination_owner,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+            context.payer.pubkey(),
+            creators,
+            1,
+            sale_price,
+        );
+
+        process_instruction(context, ix, &[]).await
+    }
+
+    async fn transfer_from(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        owner: &Keypair,
+        destination_owner: &Pubkey,
+        rule_set: Option<Pubkey>,
+        sale_price: Option<u64>,
+    ) -> Result<(), BanksClientError> {
+        let mint = test_metadata.mint.pubkey();
+        let mut instruction = transfer(
+            PROGRAM_ID,
+            get_associated_token_address(&owner.pubkey(), &mint),
+            owner.pubkey(),
+            get_associated_token_address(destination_owner, &mint),
+            *destination_owner,
+            test_metadata.pubkey,
+            mint,
+            test_master_edition.pubkey,
+            context.payer.pubkey(),
+            vec![],
+            1,
+            sale_price,
+        );
+        if let Some(rule_set) = rule_set {
+            instruction
+                .accounts
+                .push(AccountMeta::new_readonly(rule_set, false));
+        }
+
+        process_instruction(context, instruction, &[owner]).await
+    }
+
+    async fn get_token_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> TokenAccount {
+        let account = get_account(context, pubkey).await;
+        TokenAccount::unpack(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn migrate_to_programmable_success() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let metadata_account = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        );
+
+        let token = get_token_account(&mut context, &test_metadata.token.pubkey()).await;
+        assert!(token.is_frozen());
+    }
+
+    #[tokio::test]
+    async fn transfer_keeps_token_frozen() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let new_owner = Keypair::new();
+        transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &new_owner.pubkey(),
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+        let destination =
+            get_associated_token_address(&new_owner.pubkey(), &test_metadata.mint.pubkey());
+        let destination_token = get_token_account(&mut context, &destination).await;
+        assert_eq!(destination_token.amount, 1);
+        assert!(destination_token.is_frozen());
+
+        // The emptied source account is left thawed so it can be closed.
+        let source_token = get_token_account(&mut context, &test_metadata.token.pubkey()).await;
+        assert_eq!(source_token.amount, 0);
+        assert!(!source_token.is_frozen());
+    }
+
+    #[tokio::test]
+    async fn transfer_pays_creator_royalties() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+        airdrop(&mut context, &creator.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let creators = vec![Creator {
+            address: creator.pubkey(),
+            verified: false,
+            share: 100,
+        }];
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, Some(creators), 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let new_owner = Keypair::new();
+        transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &new_owner.pubkey(),
+            vec![creator.pubkey()],
+            Some(1_000_000),
+        )
+        .await
+        .unwrap();
+
+        // 500 basis points of the sale price.
+        let creator_account = get_account(&mut context, &creator.pubkey()).await;
+        assert_eq!(creator_account.lamports, 1_000_050_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_fails_with_wrong_creator_account() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+
+        let creators = vec![Creator {
+            address: creator.pubkey(),
+            verified: false,
+            share: 100,
+        }];
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, Some(creators), 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let new_owner = Keypair::new();
+        let err = transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &new_owner.pubkey(),
+            vec![Keypair::new().pubkey()],
+            Some(1_000_000),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidCreatorAccount);
+    }
+
+    #[tokio::test]
+    async fn transfer_fails_for_non_programmable() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 500, false, None, true)
+                .await
+                .unwrap();
+
+        let new_owner = Keypair::new();
+        let err = transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &new_owner.pubkey(),
+            vec![],
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+    }
+
+    #[tokio::test]
+    async fn transfer_without_sale_price_requires_allowlisted_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        // The update authority may move the token without a sale price.
+        let holder = Keypair::new();
+        transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &holder.pubkey(),
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+        let new_owner = Keypair::new();
+        let err = transfer_from(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &holder,
+            &new_owner.pubkey(),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RoyaltiesRequired);
+
+        let rule_set = test_metadata
+            .set_rule_set(&mut context, vec![holder.pubkey()], vec![])
+            .await
+            .unwrap();
+
+        transfer_from(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &holder,
+            &new_owner.pubkey(),
+            Some(rule_set),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let destination =
+            get_associated_token_address(&new_owner.pubkey(), &test_metadata.mint.pubkey());
+        let destination_token = get_token_account(&mut context, &destination).await;
+        assert_eq!(destination_token.amount, 1);
+    }
+
+    #[tokio::test]
+    async fn transfer_with_royalty_free_sale_price_requires_allowlisted_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 500, false, None, true)
+                .await
+                .unwrap();
+
+        migrate(&mut context, &test_metadata, &test_master_edition)
+            .await
+            .unwrap();
+
+        let holder = Keypair::new();
+        transfer_to(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &holder.pubkey(),
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+        // 500 basis points of a single lamport round down to no royalty at all.
+        let new_owner = Keypair::new();
+        let err = transfer_from(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &holder,
+            &new_owner.pubkey(),
+            None,
+            Some(1),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RoyaltiesRequired);
+    }
+}
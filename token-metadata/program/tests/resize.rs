@@ -0,0 +1,216 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    pda::find_master_edition_account,
+    state::{Metadata as ProgramMetadata, TokenMetadataAccount, MAX_METADATA_LEN},
+    update::{resize, update, UpdateArgs},
+    utils::puffed_out_string,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+mod resize {
+    use super::*;
+
+    async fn send_resize(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        size: Option<u32>,
+    ) -> Result<(), BanksClientError> {
+        let ix = resize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            size,
+        );
+
+        process_instruction(context, ix, &[]).await
+    }
+
+    async fn send_uri_update(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        uri: &str,
+        fund_growth: bool,
+    ) -> Result<(), BanksClientError> {
+        let payer = if fund_growth {
+            Some(context.payer.pubkey())
+        } else {
+            None
+        };
+        let (edition, _) = find_master_edition_account(&test_metadata.mint.pubkey());
+        let ix = update(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            edition,
+            None,
+            payer,
+            None,
+            UpdateArgs::V1 {
+                name: None,
+                uri: Some(uri.to_string()),
+                creators: None,
+                collection: None,
+                uses: None,
+                primary_sale_happened: None,
+                token_standard: None,
+            },
+        );
+
+        process_instruction(context, ix, &[]).await
+    }
+
+    #[tokio::test]
+    async fn resize_fits_account_to_content_and_refunds_rent() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+
+        let before = get_account(&mut context, &test_metadata.pubkey).await;
+        assert_eq!(before.data.len(), MAX_METADATA_LEN);
+
+        send_resize(&mut context, &test_metadata, None)
+            .await
+            .unwrap();
+
+        let after = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&after.data).unwrap();
+        assert_eq!(after.data.len(), metadata.compact_len());
+        assert!(after.lamports < before.lamports);
+        assert_eq!(metadata.data.name, "Test");
+        assert_eq!(metadata.data.uri, "uri");
+    }
+
+    #[tokio::test]
+    async fn update_fails_until_compact_account_is_grown() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let new_uri = "https://a.much.longer.uri/for/this/metadata.json";
+
+        send_resize(&mut context, &test_metadata, None)
+            .await
+            .unwrap();
+
+        let err = send_uri_update(&mut context, &test_metadata, new_uri, false)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::MetadataAccountTooSmall);
+
+        let account = get_account(&mut context, &test_metadata.pubkey).await;
+        let grown = account.data.len() as u32 + new_uri.len() as u32;
+        send_resize(&mut context, &test_metadata, Some(grown))
+            .await
+            .unwrap();
+        send_uri_update(&mut context, &test_metadata, new_uri, false)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&account.data).unwrap();
+        assert_eq!(metadata.data.uri, new_uri);
+
+        // Trim the spare room left by growing.
+        let compact_len = metadata.compact_len();
+        send_resize(&mut context, &test_metadata, Some(compact_len as u32))
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &test_metadata.pubkey).await;
+        assert_eq!(account.data.len(), compact_len);
+    }
+
+    #[tokio::test]
+    async fn update_grows_compact_account_from_payer() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let new_uri = "https://a.much.longer.uri/for/this/metadata.json";
+
+        send_resize(&mut context, &test_metadata, None)
+            .await
+            .unwrap();
+        let before = get_account(&mut context, &test_metadata.pubkey).await;
+
+        send_uri_update(&mut context, &test_metadata, new_uri, true)
+            .await
+            .unwrap();
+
+        let after = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&after.data).unwrap();
+        assert_eq!(metadata.data.uri, new_uri);
+        assert_eq!(after.data.len(), metadata.compact_len());
+        assert!(after.lamports > before.lamports);
+    }
+
+    #[tokio::test]
+    async fn resize_to_max_restores_padded_layout() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+
+        send_resize(&mut context, &test_metadata, None)
+            .await
+            .unwrap();
+        send_resize(&mut context, &test_metadata, Some(MAX_METADATA_LEN as u32))
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&account.data).unwrap();
+        assert_eq!(account.data.len(), MAX_METADATA_LEN);
+        assert_eq!(metadata.data.name, puffed_out_string("Test", 32));
+        assert_eq!(metadata.data.uri, puffed_out_string("uri", 200));
+    }
+
+    #[tokio::test]
+    async fn fail_to_resize_below_content() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+
+        let err = send_resize(&mut context, &test_metadata, Some(100))
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidMetadataSize);
+    }
+
+    #[tokio::test]
+    async fn fail_to_resize_with_wrong_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, None, false)
+            .await
+            .unwrap();
+        let fake_authority = Keypair::new();
+
+        let ix = resize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            fake_authority.pubkey(),
+            context.payer.pubkey(),
+            None,
+        );
+        let err = process_instruction(&mut context, ix, &[&fake_authority])
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -0,0 +1,251 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{approve_collection_authority, freeze_delegated_account},
+    pda::{find_collection_authority_account, find_rule_set_account},
+    rule_set::remove_rule_set,
+    state::{Metadata as ProgramMetadata, RuleSet, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod rule_set {
+    use super::*;
+
+    async fn approve_delegate(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        delegate: &Keypair,
+    ) {
+        let approve_ix = spl_token::instruction::approve(
+            &spl_token::id(),
+            &test_metadata.token.pubkey(),
+            &delegate.pubkey(),
+            &context.payer.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap();
+        process_instruction(context, approve_ix, &[]).await.unwrap();
+    }
+
+    async fn freeze(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        delegate: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let freeze_ix = freeze_delegated_account(
+            mpl_token_metadata::id(),
+            delegate.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            test_master_edition.mint_pubkey,
+        );
+
+        process_instruction(context, freeze_ix, &[delegate]).await
+    }
+
+    #[tokio::test]
+    async fn set_rule_set_attaches_to_metadata() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, false, None, true)
+            .await
+            .unwrap();
+
+        let denied = Pubkey::new_unique();
+        test_metadata
+            .set_rule_set(&mut context, vec![], vec![denied])
+            .await
+            .unwrap();
+
+        let (rule_set_pubkey, _) = find_rule_set_account(&test_metadata.mint.pubkey());
+        let metadata_account = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(metadata.rule_set, Some(rule_set_pubkey));
+
+        let rule_set_account = get_account(&mut context, &rule_set_pubkey).await;
+        let rule_set = RuleSet::safe_deserialize(&rule_set_account.data).unwrap();
+        assert_eq!(rule_set.mint, test_metadata.mint.pubkey());
+        assert_eq!(rule_set.deny_list, vec![denied]);
+    }
+
+    #[tokio::test]
+    async fn freeze_fails_for_denied_delegate() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 10, false, None, true)
+                .await
+                .unwrap();
+        let delegate = Keypair::new();
+
+        test_metadata
+            .set_rule_set(&mut context, vec![], vec![delegate.pubkey()])
+            .await
+            .unwrap();
+        approve_delegate(&mut context, &test_metadata, &delegate).await;
+
+        let err = freeze(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &delegate,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::DelegateNotAllowedByRuleSet);
+    }
+
+    #[tokio::test]
+    async fn freeze_succeeds_for_allowed_delegate() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 10, false, None, true)
+                .await
+                .unwrap();
+        let delegate = Keypair::new();
+
+        test_metadata
+            .set_rule_set(&mut context, vec![delegate.pubkey()], vec![])
+            .await
+            .unwrap();
+        approve_delegate(&mut context, &test_metadata, &delegate).await;
+
+        freeze(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &delegate,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn freeze_succeeds_without_rule_set_account() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 10, false, None, true)
+                .await
+                .unwrap();
+        let delegate = Keypair::new();
+        approve_delegate(&mut context, &test_metadata, &delegate).await;
+
+        // Account list of callers predating rule sets.
+        let mut freeze_ix = freeze_delegated_account(
+            mpl_token_metadata::id(),
+            delegate.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            test_master_edition.mint_pubkey,
+        );
+        freeze_ix.accounts.pop();
+
+        let freeze_tx = Transaction::new_signed_with_payer(
+            &[freeze_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(freeze_tx)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn approve_collection_authority_fails_when_not_allowed() {
+        let mut context = program_test().start_with_context().await;
+        let (test_collection, _) = Metadata::create_nft(&mut context, None, 10, false, None, true)
+            .await
+            .unwrap();
+        let new_collection_authority = Keypair::new();
+
+        test_collection
+            .set_rule_set(&mut context, vec![Pubkey::new_unique()], vec![])
+            .await
+            .unwrap();
+
+        let (record, _) = find_collection_authority_account(
+            &test_collection.mint.pubkey(),
+            &new_collection_authority.pubkey(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_collection_authority(
+                mpl_token_metadata::id(),
+                record,
+                new_collection_authority.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_collection.pubkey,
+                test_collection.mint.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::DelegateNotAllowedByRuleSet);
+    }
+
+    #[tokio::test]
+    async fn remove_rule_set_lifts_restrictions() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) =
+            Metadata::create_nft(&mut context, None, 10, false, None, true)
+                .await
+                .unwrap();
+        let delegate = Keypair::new();
+
+        test_metadata
+            .set_rule_set(&mut context, vec![], vec![delegate.pubkey()])
+            .await
+            .unwrap();
+
+        let (rule_set_pubkey, _) = find_rule_set_account(&test_metadata.mint.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[remove_rule_set(
+                mpl_token_metadata::id(),
+                rule_set_pubkey,
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata_account = get_account(&mut context, &test_metadata.pubkey).await;
+        let metadata = ProgramMetadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(metadata.rule_set, None);
+
+        approve_delegate(&mut context, &test_metadata, &delegate).await;
+        freeze(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &delegate,
+        )
+        .await
+        .unwrap();
+    }
+}
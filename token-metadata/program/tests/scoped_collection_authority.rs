@@ -0,0 +1,192 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{approve_collection_authority_v2, unverify_sized_collection_item},
+    pda::find_collection_authority_account,
+    state::{
+        Collection, CollectionAuthorityRecord, TokenMetadataAccount, COLLECTION_PERMISSION_VERIFY,
+        SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod scoped_collection_authority {
+    use super::*;
+
+    async fn approve_scoped(
+        context: &mut ProgramTestContext,
+        parent: &Metadata,
+        delegate: &Keypair,
+        permissions: u8,
+        expiry: Option<i64>,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (record, _) =
+            find_collection_authority_account(&parent.mint.pubkey(), &delegate.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_collection_authority_v2(
+                mpl_token_metadata::id(),
+                record,
+                delegate.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                parent.pubkey,
+                parent.mint.pubkey(),
+                permissions,
+                expiry,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(record)
+    }
+
+    async fn create_item(context: &mut ProgramTestContext, parent: &Metadata) -> Metadata {
+        let item = Metadata::new();
+        item.create_v3(
+            context,
+            "Item".to_string(),
+            "ITM".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            Some(Collection {
+                key: parent.mint.pubkey(),
+                verified: false,
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        item
+    }
+
+    #[tokio::test]
+    async fn verify_only_delegate_cannot_unverify() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record = approve_scoped(
+            &mut context,
+            &parent,
+            &delegate,
+            COLLECTION_PERMISSION_VERIFY,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(&mut context, &record).await;
+        assert_eq!(account.data.len(), SCOPED_COLLECTION_AUTHORITY_RECORD_SIZE);
+        let record_data = CollectionAuthorityRecord::safe_deserialize(&account.data).unwrap();
+        assert_eq!(record_data.permissions, COLLECTION_PERMISSION_VERIFY);
+        assert_eq!(record_data.update_authority, Some(context.payer.pubkey()));
+
+        let item = create_item(&mut context, &parent).await;
+        item.verify_sized_collection_item(
+            &mut context,
+            parent.pubkey,
+            &delegate,
+            parent.mint.pubkey(),
+            parent_edition.pubkey,
+            Some(record),
+        )
+        .await
+        .unwrap();
+        assert!(
+            item.get_data(&mut context)
+                .await
+                .collection
+                .unwrap()
+                .verified
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[unverify_sized_collection_item(
+                mpl_token_metadata::id(),
+                item.pubkey,
+                delegate.pubkey(),
+                context.payer.pubkey(),
+                parent.mint.pubkey(),
+                parent.pubkey,
+                parent_edition.pubkey,
+                Some(record),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CollectionAuthorityNotPermitted);
+    }
+
+    #[tokio::test]
+    async fn expired_delegate_cannot_verify() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, parent_edition) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        let record = approve_scoped(
+            &mut context,
+            &parent,
+            &delegate,
+            COLLECTION_PERMISSION_VERIFY,
+            Some(clock.unix_timestamp + 100),
+        )
+        .await
+        .unwrap();
+
+        clock.unix_timestamp += 101;
+        context.set_sysvar(&clock);
+
+        let item = create_item(&mut context, &parent).await;
+        let err = item
+            .verify_sized_collection_item(
+                &mut context,
+                parent.pubkey,
+                &delegate,
+                parent.mint.pubkey(),
+                parent_edition.pubkey,
+                Some(record),
+            )
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CollectionAuthorityExpired);
+    }
+
+    #[tokio::test]
+    async fn fail_to_approve_without_permissions() {
+        let mut context = program_test().start_with_context().await;
+        let (parent, _) = Metadata::create_default_sized_parent(&mut context)
+            .await
+            .unwrap();
+
+        let err = approve_scoped(&mut context, &parent, &Keypair::new(), 0, None)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidCollectionPermissions);
+    }
+}
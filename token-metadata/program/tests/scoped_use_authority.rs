@@ -0,0 +1,158 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{approve_use_authority_v2, utilize},
+    pda::{find_program_as_burner_account, find_use_authority_account},
+    state::{
+        TokenMetadataAccount, UseAuthorityRecord, UseMethod, Uses, SCOPED_USE_AUTHORITY_RECORD_SIZE,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::AccountMeta, pubkey::Pubkey, sysvar};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod scoped_use_authority {
+    use super::*;
+
+    async fn send(
+        context: &mut ProgramTestContext,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn approve_scoped(
+        context: &mut ProgramTestContext,
+        expiry: Option<i64>,
+        caller_program: Option<Pubkey>,
+    ) -> (Metadata, Keypair, Pubkey) {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    total: 10,
+                    remaining: 10,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let use_authority = Keypair::new();
+        let (record, _) =
+            find_use_authority_account(&test_metadata.mint.pubkey(), &use_authority.pubkey());
+        let (burner, _) = find_program_as_burner_account();
+        let ix = approve_use_authority_v2(
+            mpl_token_metadata::id(),
+            record,
+            use_authority.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            burner,
+            5,
+            expiry,
+            caller_program,
+        );
+        send(context, ix, &[]).await.unwrap();
+
+        (test_metadata, use_authority, record)
+    }
+
+    fn utilize_as_authority(
+        context: &ProgramTestContext,
+        test_metadata: &Metadata,
+        use_authority: &Keypair,
+        record: Pubkey,
+        number_of_uses: u64,
+    ) -> Instruction {
+        utilize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            Some(record),
+            use_authority.pubkey(),
+            context.payer.pubkey(),
+            None,
+            number_of_uses,
+        )
+    }
+
+    #[tokio::test]
+    async fn expired_use_authority_cannot_utilize() {
+        let mut context = program_test().start_with_context().await;
+        let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        let (test_metadata, use_authority, record) =
+            approve_scoped(&mut context, Some(clock.unix_timestamp + 100), None).await;
+
+        let account = get_account(&mut context, &record).await;
+        assert_eq!(account.data.len(), SCOPED_USE_AUTHORITY_RECORD_SIZE);
+        let record_data = UseAuthorityRecord::safe_deserialize(&account.data).unwrap();
+        assert_eq!(record_data.allowed_uses, 5);
+        assert_eq!(record_data.expiry, Some(clock.unix_timestamp + 100));
+        assert_eq!(record_data.caller_program, None);
+
+        let ix = utilize_as_authority(&context, &test_metadata, &use_authority, record, 1);
+        send(&mut context, ix, &[&use_authority]).await.unwrap();
+
+        clock.unix_timestamp += 101;
+        context.set_sysvar(&clock);
+
+        let ix = utilize_as_authority(&context, &test_metadata, &use_authority, record, 2);
+        let err = send(&mut context, ix, &[&use_authority]).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::UseAuthorityExpired);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 9);
+    }
+
+    #[tokio::test]
+    async fn use_authority_must_be_invoked_through_caller_program() {
+        let mut context = program_test().start_with_context().await;
+        let caller_program = Pubkey::new_unique();
+        let (test_metadata, use_authority, record) =
+            approve_scoped(&mut context, None, Some(caller_program)).await;
+
+        // Without the instructions sysvar the caller can't be checked.
+        let ix = utilize_as_authority(&context, &test_metadata, &use_authority, record, 1);
+        let err = send(&mut context, ix, &[&use_authority]).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidCallerProgram);
+
+        // Calling token metadata directly isn't going through the caller program.
+        let mut ix = utilize_as_authority(&context, &test_metadata, &use_authority, record, 1);
+        ix.accounts
+            .push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+        let err = send(&mut context, ix, &[&use_authority]).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidCallerProgram);
+    }
+}
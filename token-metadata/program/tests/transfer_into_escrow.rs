@@ -0,0 +1,264 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    escrow::{
+        close_escrow_account, create_escrow_account, find_escrow_account, transfer_into_escrow,
+        transfer_out_of_escrow,
+    },
+    state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use utils::*;
+
+mod transfer_into_escrow {
+    use super::*;
+
+    struct EscrowedNft {
+        nft: Metadata,
+        master_edition: MasterEditionV2,
+        escrow: Pubkey,
+    }
+
+    async fn send(
+        context: &mut ProgramTestContext,
+        ix: Instruction,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn create_escrowed_nft(context: &mut ProgramTestContext) -> EscrowedNft {
+        let (nft, master_edition) = Metadata::create_default_nft(context).await.unwrap();
+        let (escrow, _) = find_escrow_account(&nft.mint.pubkey(), &EscrowAuthority::TokenOwner);
+
+        let ix = create_escrow_account(
+            mpl_token_metadata::id(),
+            escrow,
+            nft.pubkey,
+            nft.mint.pubkey(),
+            nft.token.pubkey(),
+            master_edition.pubkey,
+            context.payer.pubkey(),
+            None,
+        );
+        send(context, ix).await.unwrap();
+
+        EscrowedNft {
+            nft,
+            master_edition,
+            escrow,
+        }
+    }
+
+    fn transfer_in_ix(
+        context: &ProgramTestContext,
+        parent: &EscrowedNft,
+        item: &Metadata,
+    ) -> Instruction {
+        transfer_into_escrow(
+            mpl_token_metadata::id(),
+            parent.escrow,
+            parent.nft.pubkey,
+            context.payer.pubkey(),
+            item.mint.pubkey(),
+            item.token.pubkey(),
+            get_associated_token_address(&parent.escrow, &item.mint.pubkey()),
+            parent.nft.mint.pubkey(),
+            parent.nft.token.pubkey(),
+            vec![],
+            1,
+        )
+    }
+
+    async fn transfer_in(
+        context: &mut ProgramTestContext,
+        parent: &EscrowedNft,
+        item: &Metadata,
+    ) -> Result<(), BanksClientError> {
+        let ix = transfer_in_ix(context, parent, item);
+        send(context, ix).await
+    }
+
+    async fn transfer_out(
+        context: &mut ProgramTestContext,
+        parent: &EscrowedNft,
+        item: &Metadata,
+        nested_escrow: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let ix = transfer_out_of_escrow(
+            mpl_token_metadata::id(),
+            parent.escrow,
+            parent.nft.pubkey,
+            context.payer.pubkey(),
+            item.mint.pubkey(),
+            get_associated_token_address(&parent.escrow, &item.mint.pubkey()),
+            get_associated_token_address(&context.payer.pubkey(), &item.mint.pubkey()),
+            parent.nft.mint.pubkey(),
+            parent.nft.token.pubkey(),
+            None,
+            nested_escrow,
+            vec![],
+            1,
+        );
+        send(context, ix).await
+    }
+
+    async fn get_escrow(context: &mut ProgramTestContext, escrow: Pubkey) -> TokenOwnedEscrow {
+        let account = get_account(context, &escrow).await;
+        TokenOwnedEscrow::safe_deserialize(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn holdings_block_closing_the_escrow() {
+        let mut context = program_test().start_with_context().await;
+        let parent = create_escrowed_nft(&mut context).await;
+        let (item, _) = Metadata::create_default_nft(&mut context).await.unwrap();
+
+        transfer_in(&mut context, &parent, &item).await.unwrap();
+
+        let escrow = get_escrow(&mut context, parent.escrow).await;
+        assert_eq!(escrow.holdings.len(), 1);
+        assert_eq!(escrow.holdings[0].mint, item.mint.pubkey());
+        assert_eq!(escrow.holdings[0].amount, 1);
+
+        let close_ix = close_escrow_account(
+            mpl_token_metadata::id(),
+            parent.escrow,
+            parent.nft.pubkey,
+            parent.nft.mint.pubkey(),
+            parent.master_edition.pubkey,
+            context.payer.pubkey(),
+            parent.nft.token.pubkey(),
+        );
+        let err = send(&mut context, close_ix.clone()).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::EscrowHasHoldings);
+
+        transfer_out(&mut context, &parent, &item, None)
+            .await
+            .unwrap();
+        let escrow = get_escrow(&mut context, parent.escrow).await;
+        assert!(escrow.holdings.is_empty());
+
+        send(&mut context, close_ix).await.unwrap();
+        let account = context
+            .banks_client
+            .get_account(parent.escrow)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn nest_and_unnest_an_escrowed_nft() {
+        let mut context = program_test().start_with_context().await;
+        let parent = create_escrowed_nft(&mut context).await;
+        let child = create_escrowed_nft(&mut context).await;
+
+        transfer_in(&mut context, &parent, &child.nft)
+            .await
+            .unwrap();
+
+        let parent_escrow = get_escrow(&mut context, parent.escrow).await;
+        assert_eq!(parent_escrow.holdings[0].nested_height, 1);
+        assert_eq!(parent_escrow.height(), 2);
+        let child_escrow = get_escrow(&mut context, child.escrow).await;
+        assert_eq!(child_escrow.parent, Some(parent.escrow));
+
+        // The nested escrow must be passed to clear its parent.
+        let err = transfer_out(&mut context, &parent, &child.nft, Some(parent.escrow))
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidEscrowNesting);
+
+        transfer_out(&mut context, &parent, &child.nft, Some(child.escrow))
+            .await
+            .unwrap();
+
+        let parent_escrow = get_escrow(&mut context, parent.escrow).await;
+        assert_eq!(parent_escrow.height(), 1);
+        let child_escrow = get_escrow(&mut context, child.escrow).await;
+        assert_eq!(child_escrow.parent, None);
+    }
+
+    #[tokio::test]
+    async fn fail_to_nest_beyond_max_depth() {
+        let mut context = program_test().start_with_context().await;
+        let mut nfts = vec![];
+        for _ in 0..5 {
+            nfts.push(create_escrowed_nft(&mut context).await);
+        }
+
+        // Nest bottom-up so every outer NFT is still held by the payer.
+        for i in (1..4).rev() {
+            transfer_in(&mut context, &nfts[i], &nfts[i + 1].nft)
+                .await
+                .unwrap();
+        }
+        assert_eq!(get_escrow(&mut context, nfts[1].escrow).await.height(), 4);
+
+        let err = transfer_in(&mut context, &nfts[0], &nfts[1].nft)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::EscrowTooDeep);
+    }
+
+    #[tokio::test]
+    async fn fail_to_nest_without_the_derived_escrow() {
+        let mut context = program_test().start_with_context().await;
+        let parent = create_escrowed_nft(&mut context).await;
+        let child = create_escrowed_nft(&mut context).await;
+
+        let mut ix = transfer_in_ix(&context, &parent, &child.nft);
+        ix.accounts[11].pubkey = Pubkey::new_unique();
+        let err = send(&mut context, ix).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidEscrowNesting);
+    }
+
+    #[tokio::test]
+    async fn fail_to_transfer_into_someone_elses_escrow() {
+        let mut context = program_test().start_with_context().await;
+        let parent = create_escrowed_nft(&mut context).await;
+        let (item, _) = Metadata::create_default_nft(&mut context).await.unwrap();
+
+        // Moving the parent away leaves the payer without authority over its escrow.
+        let new_owner = Pubkey::new_unique();
+        let new_owner_token = get_associated_token_address(&new_owner, &parent.nft.mint.pubkey());
+        let payer_pubkey = context.payer.pubkey();
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer_pubkey,
+                &new_owner,
+                &parent.nft.mint.pubkey(),
+            );
+        send(&mut context, create_ata_ix).await.unwrap();
+        let transfer_ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            &parent.nft.token.pubkey(),
+            &new_owner_token,
+            &payer_pubkey,
+            &[],
+            1,
+        )
+        .unwrap();
+        send(&mut context, transfer_ix).await.unwrap();
+
+        let err = transfer_in(&mut context, &parent, &item).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::MustBeEscrowAuthority);
+    }
+}
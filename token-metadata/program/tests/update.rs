@@ -0,0 +1,290 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    pda::find_master_edition_account,
+    state::{
+        Metadata as ProgramMetadata, MetadataDelegateRole, TokenMetadataAccount, UseMethod, Uses,
+    },
+    update::{revoke_metadata_delegate, update, UpdateArgs},
+    utils::puffed_out_string,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod update {
+    use super::*;
+
+    const USES: Option<Uses> = Some(Uses {
+        use_method: UseMethod::Multiple,
+        remaining: 10,
+        total: 10,
+    });
+
+    async fn send_update(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        authority: &Keypair,
+        delegate_record: Option<solana_program::pubkey::Pubkey>,
+        args: UpdateArgs,
+    ) -> Result<(), BanksClientError> {
+        let (edition, _) = find_master_edition_account(&test_metadata.mint.pubkey());
+        let ix = update(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            authority.pubkey(),
+            test_metadata.mint.pubkey(),
+            edition,
+            delegate_record,
+            None,
+            None,
+            args,
+        );
+
+        process_instruction(context, ix, &[authority]).await
+    }
+
+    async fn get_metadata(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+    ) -> ProgramMetadata {
+        let account = get_account(context, &test_metadata.pubkey).await;
+        ProgramMetadata::safe_deserialize(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_authority_updates_single_fields() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, USES, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        send_update(
+            &mut context,
+            &test_metadata,
+            &payer,
+            None,
+            UpdateArgs::V1 {
+                name: Some("Renamed".to_string()),
+                uri: None,
+                creators: None,
+                collection: None,
+                uses: None,
+                primary_sale_happened: Some(true),
+                token_standard: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let metadata = get_metadata(&mut context, &test_metadata).await;
+        assert_eq!(metadata.data.name, puffed_out_string("Renamed", 32));
+        assert_eq!(metadata.data.uri, puffed_out_string("uri", 200));
+        assert!(metadata.primary_sale_happened);
+    }
+
+    #[tokio::test]
+    async fn uri_delegate_updates_uri() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, USES, false)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record = test_metadata
+            .approve_metadata_delegate(&mut context, &delegate.pubkey(), MetadataDelegateRole::Uri)
+            .await
+            .unwrap();
+
+        send_update(
+            &mut context,
+            &test_metadata,
+            &delegate,
+            Some(record),
+            UpdateArgs::V1 {
+                name: None,
+                uri: Some("https://new.uri".to_string()),
+                creators: None,
+                collection: None,
+                uses: None,
+                primary_sale_happened: None,
+                token_standard: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let metadata = get_metadata(&mut context, &test_metadata).await;
+        assert_eq!(metadata.data.uri, puffed_out_string("https://new.uri", 200));
+    }
+
+    #[tokio::test]
+    async fn uri_delegate_cannot_update_name() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, USES, false)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record = test_metadata
+            .approve_metadata_delegate(&mut context, &delegate.pubkey(), MetadataDelegateRole::Uri)
+            .await
+            .unwrap();
+
+        let err = send_update(
+            &mut context,
+            &test_metadata,
+            &delegate,
+            Some(record),
+            UpdateArgs::V1 {
+                name: Some("Renamed".to_string()),
+                uri: None,
+                creators: None,
+                collection: None,
+                uses: None,
+                primary_sale_happened: None,
+                token_standard: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::DelegateRoleCannotUpdateField);
+    }
+
+    #[tokio::test]
+    async fn revoked_delegate_cannot_update() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, USES, false)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record = test_metadata
+            .approve_metadata_delegate(&mut context, &delegate.pubkey(), MetadataDelegateRole::Uses)
+            .await
+            .unwrap();
+
+        let revoke_ix = revoke_metadata_delegate(
+            mpl_token_metadata::id(),
+            record,
+            delegate.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            MetadataDelegateRole::Uses,
+        );
+        process_instruction(&mut context, revoke_ix, &[])
+            .await
+            .unwrap();
+
+        let err = send_update(
+            &mut context,
+            &test_metadata,
+            &delegate,
+            Some(record),
+            UpdateArgs::V1 {
+                name: None,
+                uri: None,
+                creators: None,
+                collection: None,
+                uses: Some(None),
+                primary_sale_happened: None,
+                token_standard: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::IncorrectOwner);
+    }
+
+    #[tokio::test]
+    async fn revoke_refunds_record_payer() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, true, USES, false)
+            .await
+            .unwrap();
+        let delegate = Keypair::new();
+        let record = test_metadata
+            .approve_metadata_delegate(&mut context, &delegate.pubkey(), MetadataDelegateRole::Uri)
+            .await
+            .unwrap();
+
+        let revoke = |context: &ProgramTestContext, payer| {
+            Transaction::new_signed_with_payer(
+                &[revoke_metadata_delegate(
+                    mpl_token_metadata::id(),
+                    record,
+                    delegate.pubkey(),
+                    test_metadata.pubkey,
+                    test_metadata.mint.pubkey(),
+                    delegate.pubkey(),
+                    payer,
+                    MetadataDelegateRole::Uri,
+                )],
+                Some(&context.payer.pubkey()),
+                &[&context.payer, &delegate],
+                context.last_blockhash,
+            )
+        };
+
+        // The delegate cannot keep the rent the update authority paid for.
+        let tx = revoke(&context, delegate.pubkey());
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::RentReceiverMismatch);
+
+        let record_lamports = get_account(&mut context, &record).await.lamports;
+        let payer = context.payer.pubkey();
+        let payer_before = get_account(&mut context, &payer).await.lamports;
+
+        let tx = revoke(&context, payer);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // The payer also paid the transaction fee.
+        let payer_after = get_account(&mut context, &payer).await.lamports;
+        assert!(payer_after > payer_before);
+        assert!(payer_after <= payer_before + record_lamports);
+        let record_account = context.banks_client.get_account(record).await.unwrap();
+        assert!(record_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_to_update_immutable_metadata() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = Metadata::create_nft(&mut context, None, 10, false, USES, false)
+            .await
+            .unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let err = send_update(
+            &mut context,
+            &test_metadata,
+            &payer,
+            None,
+            UpdateArgs::V1 {
+                name: None,
+                uri: Some("https://new.uri".to_string()),
+                creators: None,
+                collection: None,
+                uses: None,
+                primary_sale_happened: None,
+                token_standard: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::DataIsImmutable);
+    }
+}
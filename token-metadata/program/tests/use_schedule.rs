@@ -0,0 +1,256 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{approve_use_authority, utilize},
+    pda::{find_program_as_burner_account, find_use_authority_account, find_use_schedule_account},
+    state::{TokenMetadataAccount, UseMethod, UseSchedule, UseScheduleKind, Uses},
+    uses::{recharge, set_use_schedule},
+};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::AccountMeta};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod use_schedule {
+    use super::*;
+
+    async fn send(
+        context: &mut ProgramTestContext,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn create_with_uses(
+        context: &mut ProgramTestContext,
+        use_method: UseMethod,
+        total: u64,
+    ) -> Metadata {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Uses {
+                    use_method,
+                    total,
+                    remaining: total,
+                }),
+            )
+            .await
+            .unwrap();
+
+        test_metadata
+    }
+
+    async fn set_schedule(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        kind: UseScheduleKind,
+    ) -> Result<(), BanksClientError> {
+        let (use_schedule, _) = find_use_schedule_account(&test_metadata.mint.pubkey());
+        let ix = set_use_schedule(
+            mpl_token_metadata::id(),
+            use_schedule,
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            kind,
+        );
+        send(context, ix, &[]).await
+    }
+
+    async fn use_asset(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        with_schedule: bool,
+        number_of_uses: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut ix = utilize(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            None,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            None,
+            number_of_uses,
+        );
+        if with_schedule {
+            let (use_schedule, _) = find_use_schedule_account(&test_metadata.mint.pubkey());
+            ix.accounts
+                .push(AccountMeta::new_readonly(use_schedule, false));
+        }
+        send(context, ix, &[]).await
+    }
+
+    async fn get_clock(context: &mut ProgramTestContext) -> Clock {
+        context.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    async fn set_unix_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+        let mut clock = get_clock(context).await;
+        clock.unix_timestamp = unix_timestamp;
+        context.set_sysvar(&clock);
+    }
+
+    async fn get_schedule(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+    ) -> UseSchedule {
+        let (use_schedule, _) = find_use_schedule_account(&test_metadata.mint.pubkey());
+        let account = get_account(context, &use_schedule).await;
+        UseSchedule::safe_deserialize(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn recharge_restores_uses_up_to_total() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_with_uses(&mut context, UseMethod::Recharging, 5).await;
+
+        set_schedule(
+            &mut context,
+            &test_metadata,
+            UseScheduleKind::Recharging {
+                period: 100,
+                amount: 2,
+                last_recharge: 0,
+            },
+        )
+        .await
+        .unwrap();
+        let start = match get_schedule(&mut context, &test_metadata).await.kind {
+            UseScheduleKind::Recharging { last_recharge, .. } => last_recharge,
+            kind => panic!("unexpected schedule {:?}", kind),
+        };
+
+        use_asset(&mut context, &test_metadata, false, 4)
+            .await
+            .unwrap();
+
+        let (use_schedule, _) = find_use_schedule_account(&test_metadata.mint.pubkey());
+        let ix = recharge(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            use_schedule,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            None,
+        );
+        let err = send(&mut context, ix, &[]).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::RechargeNotDue);
+
+        // A use authority can recharge as well.
+        let use_authority = Keypair::new();
+        let (record, _) =
+            find_use_authority_account(&test_metadata.mint.pubkey(), &use_authority.pubkey());
+        let (burner, _) = find_program_as_burner_account();
+        let ix = approve_use_authority(
+            mpl_token_metadata::id(),
+            record,
+            use_authority.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            burner,
+            1,
+        );
+        send(&mut context, ix, &[]).await.unwrap();
+
+        set_unix_timestamp(&mut context, start + 250).await;
+
+        let ix = recharge(
+            mpl_token_metadata::id(),
+            test_metadata.pubkey,
+            use_schedule,
+            test_metadata.mint.pubkey(),
+            use_authority.pubkey(),
+            Some(record),
+        );
+        send(&mut context, ix, &[&use_authority]).await.unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 5);
+        assert_eq!(
+            get_schedule(&mut context, &test_metadata).await.kind,
+            UseScheduleKind::Recharging {
+                period: 100,
+                amount: 2,
+                last_recharge: start + 200,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_to_set_schedule_not_matching_use_method() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_with_uses(&mut context, UseMethod::Multiple, 5).await;
+
+        let err = set_schedule(
+            &mut context,
+            &test_metadata,
+            UseScheduleKind::Expiring { deadline: 0 },
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidUseSchedule);
+    }
+
+    #[tokio::test]
+    async fn expiring_uses_fail_after_deadline() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_with_uses(&mut context, UseMethod::Expiring, 3).await;
+        let now = get_clock(&mut context).await.unix_timestamp;
+
+        set_schedule(
+            &mut context,
+            &test_metadata,
+            UseScheduleKind::Expiring {
+                deadline: now + 100,
+            },
+        )
+        .await
+        .unwrap();
+
+        use_asset(&mut context, &test_metadata, true, 1)
+            .await
+            .unwrap();
+
+        set_unix_timestamp(&mut context, now + 101).await;
+
+        let err = use_asset(&mut context, &test_metadata, true, 2)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UsesExpired);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 2);
+    }
+}
@@ -86,6 +86,14 @@ impl EditionMarker {
     }
 
     pub async fn create(&self, context: &mut ProgramTestContext) -> Result<(), BanksClientError> {
+        self.create_with_extra_accounts(context, vec![]).await
+    }
+
+    pub async fn create_with_extra_accounts(
+        &self,
+        context: &mut ProgramTestContext,
+        extra_accounts: Vec<AccountMeta>,
+    ) -> Result<(), BanksClientError> {
         create_mint(
             context,
             &self.mint,
@@ -111,22 +119,25 @@ impl EditionMarker {
         )
         .await?;
 
+        let mut ix = instruction::mint_new_edition_from_master_edition_via_token(
+            id(),
+            self.new_metadata_pubkey,
+            self.new_edition_pubkey,
+            self.master_edition_pubkey,
+            self.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            self.metadata_token_pubkey,
+            context.payer.pubkey(),
+            self.metadata_pubkey,
+            self.metadata_mint_pubkey,
+            self.edition,
+        );
+        ix.accounts.extend(extra_accounts);
+
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::mint_new_edition_from_master_edition_via_token(
-                id(),
-                self.new_metadata_pubkey,
-                self.new_edition_pubkey,
-                self.master_edition_pubkey,
-                self.mint.pubkey(),
-                context.payer.pubkey(),
-                context.payer.pubkey(),
-                context.payer.pubkey(),
-                self.metadata_token_pubkey,
-                context.payer.pubkey(),
-                self.metadata_pubkey,
-                self.metadata_mint_pubkey,
-                self.edition,
-            )],
+            &[ix],
             Some(&context.payer.pubkey()),
             &[&context.payer, &context.payer],
             context.last_blockhash,
@@ -1,7 +1,12 @@
 use crate::*;
 use mpl_token_metadata::{
     id, instruction,
-    state::{Collection, CollectionDetails, Creator, Data, DataV2, Uses, PREFIX},
+    pda::{find_metadata_delegate_record_account, find_rule_set_account},
+    rule_set,
+    state::{
+        Collection, CollectionDetails, Creator, Data, DataV2, MetadataDelegateRole, Uses, PREFIX,
+    },
+    update,
 };
 use solana_program::borsh::try_from_slice_unchecked;
 
@@ -351,6 +356,85 @@ impl Metadata {
         Ok((nft, master_edition))
     }
 
+    /// Creates an NFT with the given data, adding its master edition (with a
+    /// max supply of zero) only when `with_master_edition` is set. The master
+    /// edition handle is returned either way.
+    pub async fn create_nft(
+        context: &mut ProgramTestContext,
+        creators: Option<Vec<Creator>>,
+        seller_fee_basis_points: u16,
+        is_mutable: bool,
+        uses: Option<Uses>,
+        with_master_edition: bool,
+    ) -> Result<(Metadata, MasterEditionV2), BanksClientError> {
+        let nft = Metadata::new();
+        nft.create_v3(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            creators,
+            seller_fee_basis_points,
+            is_mutable,
+            None,
+            uses,
+            None,
+        )
+        .await?;
+
+        let master_edition = MasterEditionV2::new(&nft);
+        if with_master_edition {
+            master_edition.create_v3(context, Some(0)).await?;
+        }
+
+        Ok((nft, master_edition))
+    }
+
+    pub async fn approve_metadata_delegate(
+        &self,
+        context: &mut ProgramTestContext,
+        delegate: &Pubkey,
+        role: MetadataDelegateRole,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (record, _) =
+            find_metadata_delegate_record_account(&self.mint.pubkey(), role, delegate);
+        let ix = update::approve_metadata_delegate(
+            id(),
+            record,
+            *delegate,
+            self.pubkey,
+            self.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            role,
+        );
+        process_instruction(context, ix, &[]).await?;
+
+        Ok(record)
+    }
+
+    pub async fn set_rule_set(
+        &self,
+        context: &mut ProgramTestContext,
+        allow_list: Vec<Pubkey>,
+        deny_list: Vec<Pubkey>,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (rule_set, _) = find_rule_set_account(&self.mint.pubkey());
+        let ix = rule_set::set_rule_set(
+            id(),
+            rule_set,
+            self.pubkey,
+            self.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            allow_list,
+            deny_list,
+        );
+        process_instruction(context, ix, &[]).await?;
+
+        Ok(rule_set)
+    }
+
     pub async fn create_default_sized_parent(
         context: &mut ProgramTestContext,
     ) -> Result<(Metadata, MasterEditionV2), BanksClientError> {
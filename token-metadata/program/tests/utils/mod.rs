@@ -14,8 +14,8 @@ pub use mpl_token_metadata::instruction;
 use mpl_token_metadata::state::CollectionDetails;
 use solana_program_test::*;
 use solana_sdk::{
-    account::Account, program_pack::Pack, pubkey::Pubkey, signature::Signer,
-    signer::keypair::Keypair, system_instruction, transaction::Transaction,
+    account::Account, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    signature::Signer, signer::keypair::Keypair, system_instruction, transaction::Transaction,
 };
 use spl_token::state::Mint;
 pub use vault::Vault;
@@ -41,6 +41,32 @@ pub async fn get_mint(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Mint
     Mint::unpack(&account.data).unwrap()
 }
 
+/// Sends a single instruction paid for by the context payer. Signers other than
+/// the payer are added to the transaction.
+pub async fn process_instruction(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    let mut signing_keypairs = vec![&context.payer];
+    signing_keypairs.extend(
+        signers
+            .iter()
+            .copied()
+            .filter(|signer| signer.pubkey() != payer),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer),
+        &signing_keypairs,
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
 pub async fn airdrop(
     context: &mut ProgramTestContext,
     receiver: &Pubkey,